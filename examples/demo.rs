@@ -9,14 +9,14 @@ fn main() {
     let b = BigFloat::new(2.5, 100);  // 2.5 * 10^100
     println!("a = {}", a);
     println!("b = {}", b);
-    println!("a + b = {}", a.clone() + b.clone());
-    println!("a * b = {}", a.clone() * b.clone());
+    println!("a + b = {}", &a + &b);
+    println!("a * b = {}", &a * &b);
     
     // Very large numbers
     println!("\n2. Very Large Numbers:");
     let huge = BigFloat::new(1.0, 1000);  // 1e1000
     println!("1e1000 = {}", huge);
-    println!("1e1000 * 2 = {}", huge.clone() * BigFloat::from(2.0));
+    println!("1e1000 * 2 = {}", &huge * 2);
     
     // Nested exponentials
     println!("\n3. Nested Exponentials:");
@@ -0,0 +1,174 @@
+use crate::bigfloat::BigFloat;
+use crate::exponent::Exponent;
+
+/// A running sum that does not lose small terms added to a large total.
+///
+/// `Add` rounds every sum once, so a term below half a unit in the last place
+/// of the total disappears: adding 1.0 to 1e20 a million times leaves 1e20.
+/// The accumulator keeps a Neumaier compensation term for the rounding error
+/// of each addition, and collects terms that are too small to register in a
+/// pending buffer until together they are large enough to move the total.
+#[derive(Debug, Clone, Default)]
+pub struct Accumulator {
+    sum: BigFloat,
+    compensation: BigFloat,
+    pending: BigFloat,
+}
+
+impl Accumulator {
+    pub fn new() -> Self {
+        Accumulator::default()
+    }
+
+    pub fn add(&mut self, value: &BigFloat) {
+        // Special values have no rounding error to track
+        if !value.is_finite() || !self.sum.is_finite() {
+            self.sum = &self.sum + value;
+            return;
+        }
+
+        if !is_negligible(value, &self.sum) {
+            self.add_compensated(value);
+            return;
+        }
+
+        // A term the sum would round away costs one plain addition here, and
+        // the compensation only sees the batch once it registers
+        self.pending += value;
+        if !is_negligible(&self.pending, &self.sum) {
+            let pending = std::mem::take(&mut self.pending);
+            self.add_compensated(&pending);
+        }
+    }
+
+    /// The compensated total of everything added so far.
+    pub fn total(&self) -> BigFloat {
+        if !self.sum.is_finite() {
+            return self.sum.clone();
+        }
+        &self.sum + &(&self.compensation + &self.pending)
+    }
+
+    fn add_compensated(&mut self, value: &BigFloat) {
+        let sum = &self.sum + value;
+        let correction = if self.sum.abs() >= value.abs() {
+            (&self.sum - &sum) + value
+        } else {
+            (value - &sum) + &self.sum
+        };
+        self.compensation += correction;
+        self.sum = sum;
+    }
+}
+
+/// Whether `value` is below half a unit in the last place of `total`, so that
+/// adding it leaves `total` unchanged.
+fn is_negligible(value: &BigFloat, total: &BigFloat) -> bool {
+    if total.is_zero() || value.is_zero() {
+        return false;
+    }
+    total.exponent() - value.exponent() > Exponent::Long(16)
+}
+
+impl Extend<BigFloat> for Accumulator {
+    fn extend<I: IntoIterator<Item = BigFloat>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(&value);
+        }
+    }
+}
+
+impl<'a> Extend<&'a BigFloat> for Accumulator {
+    fn extend<I: IntoIterator<Item = &'a BigFloat>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl FromIterator<BigFloat> for Accumulator {
+    fn from_iter<I: IntoIterator<Item = BigFloat>>(iter: I) -> Self {
+        let mut accumulator = Accumulator::new();
+        accumulator.extend(iter);
+        accumulator
+    }
+}
+
+impl<'a> FromIterator<&'a BigFloat> for Accumulator {
+    fn from_iter<I: IntoIterator<Item = &'a BigFloat>>(iter: I) -> Self {
+        let mut accumulator = Accumulator::new();
+        accumulator.extend(iter);
+        accumulator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_increments_register() {
+        let mut bank = Accumulator::new();
+        bank.add(&BigFloat::new(1.0, 20));
+        let income = BigFloat::from_f64(1.0);
+        for _ in 0..1_000_000 {
+            bank.add(&income);
+        }
+
+        // Plain addition never moves the total
+        let mut naive = BigFloat::new(1.0, 20);
+        for _ in 0..1000 {
+            naive += &income;
+        }
+        assert_eq!(naive, BigFloat::new(1.0, 20));
+
+        let total = bank.total();
+        assert_eq!(total.exponent(), 20);
+        assert!((total.mantissa() - 1.00000000000001).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_pending_terms_flush_together() {
+        let mut bank = Accumulator::new();
+        bank.add(&BigFloat::new(1.0, 20));
+        let income = BigFloat::from_f64(1.0);
+        for _ in 0..9999 {
+            bank.add(&income);
+        }
+        // Every increment is still waiting in the buffer
+        assert_eq!(bank.sum, BigFloat::new(1.0, 20));
+        assert!(bank.compensation.is_zero());
+        assert!((bank.pending.to_f64().unwrap() - 9999.0).abs() < 1e-9);
+
+        // Once the batch reaches the last place of the sum it goes through
+        // the compensated addition in one step
+        bank.add(&income);
+        bank.add(&income);
+        assert!(bank.pending.is_zero());
+        assert!((bank.compensation.to_f64().unwrap() - 10001.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compensation() {
+        // 1 + 1e100 - 1e100 is 1, not 0
+        let values = [BigFloat::from_f64(1.0), BigFloat::new(1.0, 100), BigFloat::new(-1.0, 100)];
+        let accumulator: Accumulator = values.iter().collect();
+        assert_eq!(accumulator.total(), BigFloat::from_f64(1.0));
+
+        let accumulator: Accumulator = (0..10).map(|_| BigFloat::from_f64(0.1)).collect();
+        assert_eq!(accumulator.total(), BigFloat::from_f64(1.0));
+    }
+
+    #[test]
+    fn test_special_values() {
+        let mut accumulator = Accumulator::new();
+        accumulator.add(&BigFloat::new(1.0, 500));
+        accumulator.add(&BigFloat::from_f64(f64::INFINITY));
+        accumulator.add(&BigFloat::from_f64(1.0));
+        assert_eq!(accumulator.total().mantissa(), f64::INFINITY);
+
+        accumulator.add(&BigFloat::from_f64(f64::NEG_INFINITY));
+        assert!(accumulator.total().is_nan());
+        assert_eq!(Accumulator::new().total(), BigFloat::from_f64(0.0));
+    }
+}
@@ -1,9 +1,17 @@
+use crate::big_decimal::{BigDecimal, DecimalContext};
 use crate::bigfloat::BigFloat;
+use crate::error::BigFloatError;
+use crate::exponent::Exponent;
+use crate::mantissa::Mantissa;
+use crate::powers_of_ten::LOG10_2_SCALED;
+use crate::rounding::{Discarded, RoundingMode};
+use std::cmp::Ordering;
 use std::ops::{Add, Sub, Mul, Div};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{mantissa, unit, XorShift};
 
     #[test]
     fn test_add_basic() {
@@ -75,6 +83,148 @@ mod tests {
         assert_eq!(result.mantissa(), f64::INFINITY);
     }
 
+    #[test]
+    fn test_div_signed_zero() {
+        let one = BigFloat::from_f64(1.0);
+        assert_eq!((one.clone() / BigFloat::from_f64(-0.0)).mantissa(), f64::NEG_INFINITY);
+        assert_eq!((-one.clone() / BigFloat::from_f64(0.0)).mantissa(), f64::NEG_INFINITY);
+        assert!((BigFloat::from_f64(0.0) / BigFloat::from_f64(-0.0)).is_nan());
+
+        let inf = BigFloat::from_f64(f64::INFINITY);
+        assert!((inf.clone() / inf.clone()).is_nan());
+        let result = BigFloat::new(-5.0, 1000) / inf;
+        assert!(result.is_zero() && result.is_sign_negative());
+    }
+
+    #[test]
+    fn test_signed_zero_arithmetic() {
+        let zero = BigFloat::from_f64(0.0);
+        let negative_zero = BigFloat::from_f64(-0.0);
+        assert!((negative_zero.clone() + negative_zero.clone()).is_sign_negative());
+        assert!((negative_zero.clone() + zero.clone()).is_sign_positive());
+        assert!((negative_zero.clone() * BigFloat::new(3.0, 500)).is_sign_negative());
+        assert!((negative_zero.clone() * BigFloat::new(-3.0, 500)).is_sign_positive());
+
+        let value = BigFloat::new(2.5, 700);
+        assert!((value.clone() - value).is_sign_positive());
+    }
+
+    #[test]
+    fn test_nan_propagation() {
+        let nan = BigFloat::from_f64(f64::NAN);
+        let value = BigFloat::new(1.0, 1000);
+        assert!((nan.clone() + value.clone()).is_nan());
+        assert!((value.clone() - nan.clone()).is_nan());
+        assert!((nan.clone() * BigFloat::from_f64(0.0)).is_nan());
+        assert!((value / nan).is_nan());
+
+        let inf = BigFloat::from_f64(f64::INFINITY);
+        assert!((inf.clone() - inf.clone()).is_nan());
+        assert!((inf * BigFloat::from_f64(-0.0)).is_nan());
+    }
+
+    #[test]
+    fn test_reference_operands() {
+        let a = BigFloat::new(1.5, 100);
+        let b = BigFloat::new(2.5, 100);
+        assert_eq!(&a + &b, BigFloat::new(4.0, 100));
+        assert_eq!(&b - &a, BigFloat::new(1.0, 100));
+        assert_eq!(&a * b.clone(), BigFloat::new(3.75, 200));
+        assert_eq!(a.clone() / &b, BigFloat::from_f64(0.6));
+        assert_eq!(a, BigFloat::new(1.5, 100));
+    }
+
+    #[test]
+    fn test_primitive_operands() {
+        let x = BigFloat::new(3.0, 1000);
+        assert_eq!(2.0 * &x, BigFloat::new(6.0, 1000));
+        assert_eq!(&x / 3, BigFloat::new(1.0, 1000));
+        assert_eq!(x.clone() * 2.5_f32, BigFloat::new(7.5, 1000));
+        assert_eq!(1_i64 - BigFloat::from_f64(0.25), BigFloat::from_f64(0.75));
+        assert_eq!(10_u64 / BigFloat::from_f64(4.0), BigFloat::from_f64(2.5));
+        assert_eq!(BigFloat::from_f64(1.5) + 2_i32, BigFloat::from_f64(3.5));
+        assert_eq!(u64::MAX * BigFloat::from_f64(1.0), BigFloat::from_f64(u64::MAX as f64));
+    }
+
+    fn deepest_tower() -> BigFloat {
+        let mut tower = BigFloat::new(1.0, 100);
+        for _ in 0..crate::exponent::MAX_EXPONENT_DEPTH {
+            tower = BigFloat::new(1.0, tower);
+        }
+        tower
+    }
+
+    #[test]
+    fn test_checked_ops() {
+        let a = BigFloat::new(2.0, 1000);
+        let b = BigFloat::new(4.0, -1000);
+        assert_eq!(a.checked_add(&b), Some(a.clone()));
+        assert_eq!(a.checked_sub(&a), Some(BigFloat::from_f64(0.0)));
+        assert_eq!(a.checked_mul(&b), Some(BigFloat::from_f64(8.0)));
+        assert_eq!(a.checked_div(&b), Some(BigFloat::new(5.0, 1999)));
+        assert_eq!(a.checked_mul(&BigFloat::from_f64(0.0)), Some(BigFloat::from_f64(0.0)));
+        assert_eq!(BigFloat::from_f64(3.0).checked_pow(&BigFloat::from_f64(2.0)), Some(BigFloat::from_f64(9.0)));
+
+        assert_eq!(a.checked_div(&BigFloat::from_f64(0.0)), None);
+        assert_eq!(BigFloat::from_f64(0.0).checked_div(&BigFloat::from_f64(-0.0)), None);
+        assert_eq!(BigFloat::from_f64(-2.0).checked_pow(&BigFloat::from_f64(0.5)), None);
+
+        // Special operands are passed through rather than reported
+        let inf = BigFloat::from_f64(f64::INFINITY);
+        assert_eq!(inf.checked_mul(&a), Some(inf.clone()));
+        assert!(BigFloat::from_f64(f64::NAN).checked_add(&a).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_try_div() {
+        let a = BigFloat::new(3.0, 500);
+        assert_eq!(a.try_div(&BigFloat::from_f64(2.0)), Ok(BigFloat::new(1.5, 500)));
+        assert_eq!(a.try_div(&BigFloat::from_f64(-0.0)), Err(BigFloatError::DivisionByZero));
+        assert!(matches!(
+            BigFloat::from_f64(f64::NAN).try_div(&a),
+            Err(BigFloatError::Domain { .. })
+        ));
+        let inf = BigFloat::from_f64(f64::INFINITY);
+        assert!(matches!(inf.try_div(&inf), Err(BigFloatError::Domain { .. })));
+        assert_eq!(a.try_div(&inf), Ok(BigFloat::from_f64(0.0)));
+    }
+
+    #[test]
+    fn test_exponent_overflow_reporting() {
+        let tower = deepest_tower();
+        assert!(tower.is_finite());
+
+        // Products only grow the outermost exponent, so they stay in range
+        let (result, overflowed) = tower.overflowing_mul(&tower);
+        assert!(!overflowed && result > tower);
+
+        // A power of the tower needs one more level than is allowed
+        assert_eq!(tower.checked_pow(&tower), None);
+        assert_eq!(tower.saturating_pow(&tower).mantissa(), f64::INFINITY);
+        let (result, overflowed) = tower.overflowing_pow(&tower);
+        assert!(overflowed && result.is_infinite());
+
+        let tiny = BigFloat::from_f64(1.0) / tower.clone();
+        assert!(tiny > BigFloat::from_f64(0.0));
+        assert_eq!(tiny.checked_pow(&tower), None);
+        let (result, overflowed) = tiny.overflowing_pow(&tower);
+        assert!(overflowed && result.is_zero());
+
+        let (result, overflowed) = tower.overflowing_add(&tower);
+        assert!(!overflowed && result.is_finite());
+    }
+
+    #[test]
+    fn test_operators_never_wrap() {
+        let max = BigFloat::new(9.0, i128::MAX);
+        let min = BigFloat::new(9.0, i128::MIN);
+        assert!(&max * &max > max);
+        assert!(&min * &min < min && &min * &min > BigFloat::from_f64(0.0));
+        assert!(&max / &min > max);
+        assert!(&min / &max < min);
+        assert!(BigFloat::new(99.0, i128::MAX) > max);
+    }
+
     #[test]
     fn test_very_large_numbers() {
         let a = BigFloat::new(1.0, 100);  // 1e100
@@ -83,124 +233,931 @@ mod tests {
         assert!((result.mantissa() - 1.0).abs() < 1e-10);
         assert_eq!(result.exponent(), (300));
     }
+
+    #[test]
+    fn test_div_negative_exponent() {
+        let a = BigFloat::new(3.0, 0);
+        let b = BigFloat::new(2.0, 5000);
+        let result = a / b;  // 1.5e-5000
+        assert!((result.mantissa() - 1.5).abs() < 1e-10);
+        assert_eq!(result.exponent(), -5000);
+    }
+
+    #[test]
+    fn test_mul_tiny_numbers() {
+        let a = BigFloat::new(4.0, -3000);
+        let b = BigFloat::new(5.0, -2000);
+        let result = a * b;  // 2e-4999
+        assert!((result.mantissa() - 2.0).abs() < 1e-10);
+        assert_eq!(result.exponent(), -4999);
+    }
+
+    /// `a + b × 10^-shift` rounded through exact decimal digits: the sum is
+    /// formed digit by digit and `str::parse` rounds it once.
+    fn add_reference(a: f64, b: f64, shift: usize) -> (f64, i128) {
+        const PLACES: usize = 70;
+        let digits = |value: f64, places: usize| -> Vec<u8> {
+            format!("{:.*}", places, value.abs()).bytes().filter(u8::is_ascii_digit).map(|d| d - b'0').collect()
+        };
+        let mut x = digits(a, PLACES);
+        let mut y = vec![0; shift];
+        y.extend(digits(b, PLACES - shift));
+        x.insert(0, 0);
+        y.insert(0, 0);
+
+        let negative = if a.is_sign_negative() == b.is_sign_negative() {
+            let mut carry = 0;
+            for (x, y) in x.iter_mut().zip(&y).rev() {
+                let digit = *x + y + carry;
+                *x = digit % 10;
+                carry = digit / 10;
+            }
+            a < 0.0
+        } else {
+            let swap = x < y;
+            if swap {
+                std::mem::swap(&mut x, &mut y);
+            }
+            let mut borrow = 0;
+            for (x, y) in x.iter_mut().zip(&y).rev() {
+                let digit = *x as i8 - *y as i8 - borrow;
+                *x = digit.rem_euclid(10) as u8;
+                borrow = (digit < 0) as i8;
+            }
+            (a < 0.0) != swap
+        };
+
+        let Some(first) = x.iter().position(|&digit| digit != 0) else {
+            return (0.0, 0);
+        };
+        let text: String = x[first + 1..].iter().map(|digit| char::from(b'0' + digit)).collect();
+        let mantissa: f64 = format!("{}.{}", x[first], text).parse().unwrap();
+        let decade = 1 - first as i128;
+        let (mantissa, decade) = if mantissa == 10.0 { (1.0, decade + 1) } else { (mantissa, decade) };
+        (if negative { -mantissa } else { mantissa }, decade)
+    }
+
+    #[test]
+    fn test_add_is_correctly_rounded() {
+        let mut rng = XorShift::new(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..20_000 {
+            let (x, y, choice) = (rng.next_u64(), rng.next_u64(), rng.next_u64());
+            let a = mantissa(x);
+            let shift = (choice % 18) as usize;
+            // Every fourth pair is a few ulps from cancelling
+            let b = if choice % 4 == 0 {
+                let nudged = f64::from_bits(a.abs().to_bits().wrapping_add(choice >> 60) - 8);
+                if y & 1 == 1 { -nudged } else { nudged }
+            } else {
+                mantissa(y)
+            };
+            let b = if b.abs() < 1.0 { b * 10.0 } else { b };
+
+            let sum = BigFloat::new(a, 40) + BigFloat::new(b, 40 - shift as i128);
+            let (expected, decade) = add_reference(a, b, shift);
+            assert_eq!(sum.mantissa(), expected, "{} + {}e-{}", a, b, shift);
+            if expected != 0.0 {
+                assert_eq!(sum.exponent(), 40 + decade, "{} + {}e-{}", a, b, shift);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_rounding_boundaries() {
+        let epsilon = f64::EPSILON;
+
+        // Sixteen decades apart the addend still moves the last bit
+        let sum = BigFloat::new(1.0, 16) + BigFloat::new(3.0, 0);
+        assert_eq!(sum, BigFloat::new(1.0 + epsilon, 16));
+
+        // 5^16 / 2^37 × 10^-16 is exactly 2^-53, half an ulp of 1: ties go to even
+        let half_ulp = 152_587_890_625.0 / 137_438_953_472.0;
+        assert_eq!(BigFloat::new(1.0, 16) + BigFloat::new(half_ulp, 0), BigFloat::new(1.0, 16));
+        let sum = BigFloat::new(1.0 + epsilon, 16) + BigFloat::new(half_ulp, 0);
+        assert_eq!(sum, BigFloat::new(1.0 + 2.0 * epsilon, 16));
+        let difference = BigFloat::new(1.0 + epsilon, 16) - BigFloat::new(half_ulp, 0);
+        assert_eq!(difference, BigFloat::new(1.0, 16));
+
+        // Dropping a decade halves the ulp, so 17 decades can still matter
+        let difference = BigFloat::new(1.0, 17) - BigFloat::new(9.0, 0);
+        assert_eq!(difference, BigFloat::new(10.0 - 8.0 * epsilon, 16));
+        let difference = BigFloat::new(1.0, 17) - BigFloat::new(8.0, 0);
+        assert_eq!(difference, BigFloat::new(1.0, 17));
+        let difference = BigFloat::new(1.0, 18) - BigFloat::new(9.9, 0);
+        assert_eq!(difference, BigFloat::new(1.0, 18));
+
+        // Carries and cancellation are exact before the single rounding
+        assert_eq!(BigFloat::new(9.0, 5) + BigFloat::new(1.0, 5), BigFloat::new(1.0, 6));
+        let difference = BigFloat::new(1.0 + epsilon, -3) - BigFloat::new(1.0, -3);
+        assert_eq!(difference, BigFloat::new(epsilon * 1e16, -19));
+        let difference = BigFloat::new(1.0, 0) - BigFloat::new(10.0 - 8.0 * epsilon, -1);
+        assert_eq!(difference, BigFloat::new(8.0 * epsilon * 1e15, -16));
+
+        // The same holds for the narrower mantissa
+        let sum = BigFloat::from_mantissa(1.0_f32, 8) + BigFloat::from_mantissa(6.0_f32, 0);
+        assert_eq!(sum.mantissa(), 1.0 + f32::EPSILON);
+        let sum = BigFloat::from_mantissa(1.0_f32, 8) + BigFloat::from_mantissa(5.0_f32, 0);
+        assert_eq!(sum.mantissa(), 1.0);
+    }
+
+    #[test]
+    fn test_directed_rounding() {
+        use RoundingMode::*;
+        let one = BigFloat::from_f64(1.0);
+        let three = BigFloat::from_f64(3.0);
+
+        // 1/3 is bracketed by adjacent values, and the brackets hold when
+        // multiplied back with rounding in the same direction
+        let low = one.div_round(&three, Down);
+        let high = one.div_round(&three, Up);
+        assert_eq!(high.mantissa(), low.mantissa().next_up());
+        assert!(low.mul_round(&three, Up) <= one && high.mul_round(&three, Down) >= one);
+        assert_eq!(one.div_round(&three, NearestEven), high);
+        assert_eq!((-one.clone()).div_round(&three, TowardZero), -low.clone());
+        assert_eq!((-one.clone()).div_round(&three, AwayFromZero), -high.clone());
+
+        // Exact results are the same in every mode
+        for mode in [NearestEven, NearestAway, TowardZero, AwayFromZero, Up, Down] {
+            let a = BigFloat::new(1.5, 300);
+            assert_eq!(a.mul_round(&BigFloat::from_f64(2.0), mode), BigFloat::new(3.0, 300));
+            assert_eq!(a.div_round(&BigFloat::new(5.0, -1), mode), BigFloat::new(3.0, 300));
+            assert_eq!(a.add_round(&a, mode), BigFloat::new(3.0, 300));
+            assert_eq!(BigFloat::new(2.5, 1).sqrt_round(mode), BigFloat::from_f64(5.0));
+        }
+
+        // A far smaller addend still moves a directed result by one ulp
+        let big = BigFloat::new(1.0, 100);
+        assert_eq!(big.add_round(&one, Up), BigFloat::new(1.0_f64.next_up(), 100));
+        assert_eq!(big.add_round(&one, Down), big);
+        assert_eq!(big.sub_round(&one, Down), BigFloat::new(10.0_f64.next_down(), 99));
+        assert_eq!(big.sub_round(&one, NearestEven), big);
+
+        // Exact cancellation is -0 only when rounding down
+        assert!(big.sub_round(&big, Down).is_sign_negative());
+        assert!(big.sub_round(&big, Up).is_sign_positive());
+        let zero = BigFloat::from_f64(0.0);
+        assert!(zero.add_round(&-zero.clone(), Down).is_sign_negative());
+    }
+
+    #[test]
+    fn test_directed_rounding_brackets_nearest() {
+        use RoundingMode::*;
+        let mut rng = XorShift::new(0x2545_f491_4f6c_dd1d);
+        let mut next = move || {
+            let bits = rng.next_u64();
+            BigFloat::new(mantissa(bits), (bits >> 60) as i128)
+        };
+        // Adjacent values, stepping into the next decade from the largest mantissa
+        let adjacent = |low: &BigFloat, high: &BigFloat| {
+            let up = if low.mantissa() == 10.0_f64.next_down() {
+                BigFloat::new(1.0, low.exponent() + Exponent::Long(1))
+            } else if low.mantissa() == -1.0 {
+                BigFloat::new(-10.0_f64.next_down(), low.exponent() - Exponent::Long(1))
+            } else {
+                BigFloat::new(low.mantissa().next_up(), low.exponent())
+            };
+            *high == up
+        };
+        for _ in 0..10_000 {
+            let (a, b) = (next(), next());
+            let results = [
+                (a.add_round(&b, Down), a.add_round(&b, NearestEven), a.add_round(&b, Up)),
+                (a.mul_round(&b, Down), a.mul_round(&b, NearestEven), a.mul_round(&b, Up)),
+                (a.div_round(&b, Down), a.div_round(&b, NearestEven), a.div_round(&b, Up)),
+                (a.abs().sqrt_round(Down), a.abs().sqrt(), a.abs().sqrt_round(Up)),
+            ];
+            for (low, nearest, high) in results {
+                assert!(nearest == low || nearest == high, "{} {} {}", low, nearest, high);
+                assert!(low == high || adjacent(&low, &high), "{} {}", low, high);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_round() {
+        use RoundingMode::*;
+        let two = BigFloat::from_f64(2.0);
+        assert_eq!(two.sqrt().mantissa(), 2.0_f64.sqrt());
+        let (low, high) = (two.sqrt_round(Down), two.sqrt_round(Up));
+        assert!(low.mul_round(&low, Up) <= two && high.mul_round(&high, Down) >= two);
+        assert_eq!(high.mantissa(), low.mantissa().next_up());
+
+        // Odd exponents fold a factor of ten into the mantissa
+        assert_eq!(BigFloat::new(4.0, -3).sqrt(), BigFloat::new(40.0_f64.sqrt(), -2));
+        assert_eq!(BigFloat::new(9.0, 2001).sqrt(), BigFloat::new(90.0_f64.sqrt(), 1000));
+        assert_eq!(BigFloat::new(1.0, -4).sqrt(), BigFloat::new(1.0, -2));
+
+        assert!(BigFloat::from_f64(-4.0).sqrt_round(Up).is_nan());
+        assert!(BigFloat::from_f64(-0.0).sqrt_round(Down).is_sign_negative());
+        let googolplex: BigFloat = "1e1e100".parse().unwrap();
+        assert_eq!(googolplex.sqrt(), "1e5e99".parse::<BigFloat>().unwrap());
+    }
+
+    #[test]
+    fn test_round_significant() {
+        use RoundingMode::*;
+        // 1.25 is exact, so it is a true tie; 2.675 is stored just below
+        assert_eq!(BigFloat::from_f64(1.25).round_significant(2, NearestEven), BigFloat::from_f64(1.2));
+        assert_eq!(BigFloat::from_f64(1.25).round_significant(2, NearestAway), BigFloat::from_f64(1.3));
+        assert_eq!(BigFloat::new(2.675, 9).round_significant(3, NearestAway), BigFloat::new(2.67, 9));
+
+        assert_eq!(BigFloat::new(-1.21, -50).round_significant(2, Down), BigFloat::new(-1.3, -50));
+        assert_eq!(BigFloat::new(-1.29, 7).round_significant(2, TowardZero), BigFloat::new(-1.2, 7));
+        assert_eq!(BigFloat::new(9.96, 5).round_significant(2, NearestEven), BigFloat::new(1.0, 6));
+        assert_eq!(BigFloat::new(4.4, 0).round_significant(0, AwayFromZero), BigFloat::from_f64(5.0));
+
+        // Directed modes also round the decimal result to a mantissa in the
+        // same direction: 1.7 is stored below 1.7 and 1.6 above 1.6
+        let x = BigFloat::from_f64(1.7);
+        assert_eq!(x.round_significant(2, Up).mantissa(), 1.7_f64.next_up());
+        assert_eq!(x.round_significant(2, Down).mantissa(), 1.6_f64.next_down());
+        assert_eq!(x.round_significant(40, NearestEven), x);
+
+        let inf = BigFloat::from_f64(f64::INFINITY);
+        assert_eq!(inf.round_significant(3, Down), inf);
+        let value = BigFloat::from_mantissa(1.2345_f32, 3).round_significant(3, Down);
+        assert_eq!(value, BigFloat::from_mantissa(1.23_f32.next_down(), 3));
+    }
+
+    #[test]
+    fn test_mul_add_rounds_once() {
+        // (1 + 2^-30)² - (1 + 2^-29) is exactly 2^-60; rounding twice gives 0
+        let x = BigFloat::from_f64(1.0 + 2f64.powi(-30));
+        let c = BigFloat::from_f64(-(1.0 + 2f64.powi(-29)));
+        assert_eq!(x.mul_add(&x, &c), BigFloat::from_f64(2f64.powi(-60)));
+        assert!((x.clone() * x.clone() + c).is_zero());
+
+        // 3 × (1 + 2^-52) lies halfway between two mantissas, so a term far
+        // below it still picks the side
+        let three = BigFloat::from_f64(3.0);
+        let y = BigFloat::from_f64(1.0 + f64::EPSILON);
+        let above = BigFloat::from_f64(3.0 + 4.0 * f64::EPSILON);
+        let below = BigFloat::from_f64(3.0 + 2.0 * f64::EPSILON);
+        assert_eq!(three.mul_add(&y, &BigFloat::new(1.0, -200)), above);
+        assert_eq!(three.mul_add(&y, &BigFloat::new(-1.0, -200)), below);
+        assert_eq!(three.clone() * y, above);
+
+        // Matches f64 wherever both grids agree, in [1, 10) at exponent 0
+        let mut rng = XorShift::new(0x2545_f491_4f6c_dd1d);
+        let mut next = move || 1.0 + unit(rng.next_u64()) * 3.0;
+        let mut checked = 0;
+        for _ in 0..5_000 {
+            let (a, b, t) = (next(), next(), next() * 3.0);
+            let c = -t.min(9.9);
+            let expected = a.mul_add(b, c);
+            if !(1.0..10.0).contains(&expected) {
+                continue;
+            }
+            let result = BigFloat::new(a, 0).mul_add(&BigFloat::new(b, 0), &BigFloat::new(c, 0));
+            assert_eq!(result, BigFloat::new(expected, 0), "{a} × {b} + {c}");
+            checked += 1;
+        }
+        assert!(checked > 1_000);
+    }
+
+    #[test]
+    fn test_mul_add_special_values() {
+        let two = BigFloat::from_f64(2.0);
+        let inf = BigFloat::from_f64(f64::INFINITY);
+        let zero = BigFloat::from_f64(0.0);
+        assert!(inf.mul_add(&zero, &two).is_nan());
+        assert_eq!(two.mul_add(&inf, &two), inf);
+        assert_eq!(zero.mul_add(&two, &BigFloat::new(1.5, 40)), BigFloat::new(1.5, 40));
+        assert_eq!(BigFloat::new(1.5, 1000).mul_add(&two, &BigFloat::new(1.0, 800)), BigFloat::new(3.0, 1000));
+        assert_eq!(BigFloat::new(1.0, 800).mul_add(&two, &BigFloat::new(1.5, 1000)), BigFloat::new(1.5, 1000));
+        assert!(two.mul_add(&two, &BigFloat::from_f64(-4.0)).is_zero());
+    }
+
+    #[test]
+    fn test_dot_and_polyval() {
+        let x = BigFloat::from_f64(1.0 + 2f64.powi(-30));
+        let a = [BigFloat::from_f64(-1.0), x.clone()];
+        let b = [BigFloat::from_f64(1.0 + 2f64.powi(-29)), x];
+        assert_eq!(BigFloat::dot(&a, &b), BigFloat::from_f64(2f64.powi(-60)));
+
+        // Production rate: counts × rates per building
+        let counts = [BigFloat::new(1.2, 30), BigFloat::from_f64(3.0), BigFloat::from_f64(7.0)];
+        let rates = [BigFloat::from_f64(0.5), BigFloat::new(2.0, 28), BigFloat::from_f64(10.0)];
+        assert_eq!(BigFloat::dot(&counts, &rates), BigFloat::from_f64(6.6e29) + BigFloat::from_f64(70.0));
+        assert!(BigFloat::dot(&[], &rates).is_zero());
+
+        // 2x² - 3x + 1 at 4
+        let coefficients = [BigFloat::from_f64(2.0), BigFloat::from_f64(-3.0), BigFloat::from_f64(1.0)];
+        assert_eq!(BigFloat::polyval(&coefficients, &BigFloat::from_f64(4.0)), BigFloat::from_f64(21.0));
+        assert!(BigFloat::polyval(&[], &BigFloat::from_f64(4.0)).is_zero());
+    }
+
+    #[test]
+    fn test_add_tiny_numbers() {
+        let a = BigFloat::new(1.0, -5000);
+        let b = BigFloat::new(1.0, -5001);
+        let result = a + b;
+        assert!((result.mantissa() - 1.1).abs() < 1e-10);
+        assert_eq!(result.exponent(), -5000);
+    }
+
+    #[test]
+    fn test_mul_promotes_exponent() {
+        let a = BigFloat::new(1.0, i128::MAX - 1);
+        let result = a.clone() * a.clone();
+        assert_eq!(result.exponent().depth(), 1);
+        assert!(result > a);
+
+        let b = BigFloat::new(1.0, i128::MIN + 1);
+        let result = b.clone() * b.clone();
+        assert_eq!(result.exponent().depth(), 1);
+        assert!(result < b && result > BigFloat::from_f64(0.0));
+    }
+
+    #[test]
+    fn test_mul_across_boundary() {
+        let googolplex: BigFloat = "1e1e100".parse().unwrap();
+        let result = googolplex.clone() * BigFloat::new(3.0, 0);
+        assert!((result.mantissa() - 3.0).abs() < 1e-10);
+        assert_eq!(result.exponent(), googolplex.exponent());
+
+        let squared = googolplex.clone() * googolplex;
+        let expected: BigFloat = "1e2e100".parse().unwrap();
+        assert_eq!(squared, expected);
+    }
+
+    #[test]
+    fn test_div_across_boundary() {
+        let a: BigFloat = "5e2e100".parse().unwrap();
+        let b: BigFloat = "2e1e100".parse().unwrap();
+        let expected: BigFloat = "2.5e1e100".parse().unwrap();
+        assert_eq!(a.clone() / b.clone(), expected);
+
+        // Dividing back down to the i128 range demotes the exponent
+        let result = b.clone() / b;
+        assert_eq!(result, BigFloat::from_f64(1.0));
+    }
 }
 
-impl BigFloat {
+impl<M: Mantissa> BigFloat<M> {
+    /// True for both +0 and -0; use `is_sign_negative` to tell them apart.
     pub fn is_zero(&self) -> bool {
-        self.mantissa == 0.0
+        self.mantissa().to_f64() == 0.0
     }
 
     pub fn is_finite(&self) -> bool {
-        self.mantissa.is_finite()
+        self.mantissa().to_f64().is_finite()
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.mantissa().to_f64().is_nan()
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.mantissa().to_f64().is_infinite()
     }
 
-    pub fn compare_exponents(&self, other: &BigFloat) -> std::cmp::Ordering {
-        self.exponent.cmp(&other.exponent)
+    pub fn compare_exponents(&self, other: &BigFloat<M>) -> std::cmp::Ordering {
+        self.exponent_cow().cmp(&other.exponent_cow())
     }
 }
 
-impl Add for BigFloat {
-    type Output = BigFloat;
+impl BigFloat {
+    /// `self + other`, or `None` if finite operands gave an infinite or NaN result.
+    pub fn checked_add(&self, other: &BigFloat) -> Option<BigFloat> {
+        checked(self.overflowing_add(other), &[self, other])
+    }
+
+    /// `self - other`, or `None` if finite operands gave an infinite or NaN result.
+    pub fn checked_sub(&self, other: &BigFloat) -> Option<BigFloat> {
+        checked(self.overflowing_sub(other), &[self, other])
+    }
+
+    /// `self * other`, or `None` if the exponent overflowed to ±∞ or
+    /// underflowed to ±0.
+    pub fn checked_mul(&self, other: &BigFloat) -> Option<BigFloat> {
+        checked(self.overflowing_mul(other), &[self, other])
+    }
+
+    /// `self / other`, or `None` on division by zero, 0/0, or an exponent
+    /// that overflowed or underflowed.
+    pub fn checked_div(&self, other: &BigFloat) -> Option<BigFloat> {
+        checked(self.overflowing_div(other), &[self, other])
+    }
 
-    fn add(self, other: BigFloat) -> BigFloat {
+    /// `self.pow(exponent)`, or `None` if the result overflowed, underflowed
+    /// or is undefined (a negative base with a non-integer exponent).
+    pub fn checked_pow(&self, exponent: &BigFloat) -> Option<BigFloat> {
+        checked(self.overflowing_pow(exponent), &[self, exponent])
+    }
+
+    /// `self / other`, with division by zero, undefined quotients (0/0, ∞/∞,
+    /// NaN operands) and exponent overflow reported as errors.
+    pub fn try_div(&self, other: &BigFloat) -> Result<BigFloat, BigFloatError> {
+        if self.is_nan() || other.is_nan() {
+            return Err(BigFloatError::Domain { operation: "division with a NaN operand" });
+        }
+        if other.is_zero() {
+            return Err(BigFloatError::DivisionByZero);
+        }
+        let (result, overflowed) = self.overflowing_div(other);
+        if overflowed {
+            return Err(BigFloatError::ExponentOverflow);
+        }
+        if result.is_nan() {
+            return Err(BigFloatError::Domain { operation: "division of infinity by infinity" });
+        }
+        Ok(result)
+    }
+
+    // The operators already saturate: an exponent too large for
+    // MAX_EXPONENT_DEPTH becomes ±∞, one too small becomes ±0
+
+    pub fn saturating_add(&self, other: &BigFloat) -> BigFloat {
+        self + other
+    }
+
+    pub fn saturating_sub(&self, other: &BigFloat) -> BigFloat {
+        self - other
+    }
+
+    pub fn saturating_mul(&self, other: &BigFloat) -> BigFloat {
+        self * other
+    }
+
+    pub fn saturating_div(&self, other: &BigFloat) -> BigFloat {
+        self / other
+    }
+
+    pub fn saturating_pow(&self, exponent: &BigFloat) -> BigFloat {
+        self.pow(exponent)
+    }
+
+    /// The saturated sum and whether it overflowed.
+    pub fn overflowing_add(&self, other: &BigFloat) -> (BigFloat, bool) {
+        let result = self + other;
+        let overflowed = out_of_range(&[self, other], &result, false);
+        (result, overflowed)
+    }
+
+    /// The saturated difference and whether it overflowed.
+    pub fn overflowing_sub(&self, other: &BigFloat) -> (BigFloat, bool) {
+        let result = self - other;
+        let overflowed = out_of_range(&[self, other], &result, false);
+        (result, overflowed)
+    }
+
+    /// The saturated product and whether it overflowed or underflowed.
+    pub fn overflowing_mul(&self, other: &BigFloat) -> (BigFloat, bool) {
+        let result = self * other;
+        let overflowed = out_of_range(&[self, other], &result, true);
+        (result, overflowed)
+    }
+
+    /// The saturated quotient and whether it overflowed or underflowed.
+    /// Division by zero counts as overflow.
+    pub fn overflowing_div(&self, other: &BigFloat) -> (BigFloat, bool) {
+        let result = self / other;
+        let overflowed = out_of_range(&[self, other], &result, true);
+        (result, overflowed)
+    }
+
+    /// The saturated power and whether it overflowed or underflowed.
+    pub fn overflowing_pow(&self, exponent: &BigFloat) -> (BigFloat, bool) {
+        let result = self.pow(exponent);
+        let overflowed = out_of_range(&[self, exponent], &result, !exponent.is_zero());
+        (result, overflowed)
+    }
+}
+
+/// Whether finite operands produced an infinite result, or, when
+/// `can_underflow`, non-zero operands produced a zero.
+fn out_of_range(operands: &[&BigFloat], result: &BigFloat, can_underflow: bool) -> bool {
+    if !operands.iter().all(|operand| operand.is_finite()) {
+        return false;
+    }
+    result.is_infinite()
+        || can_underflow && result.is_zero() && operands.iter().all(|operand| !operand.is_zero())
+}
+
+fn checked((result, overflowed): (BigFloat, bool), operands: &[&BigFloat]) -> Option<BigFloat> {
+    let undefined = result.is_nan() && !operands.iter().any(|operand| operand.is_nan());
+    if overflowed || undefined { None } else { Some(result) }
+}
+
+impl<M: Mantissa> BigFloat<M> {
+    /// `self + other` rounded once in `mode`; `+` rounds to nearest-even.
+    pub fn add_round(&self, other: &BigFloat<M>, mode: RoundingMode) -> BigFloat<M> {
+        // Special values follow f64: NaN propagates, ∞ - ∞ is NaN and
+        // -0 + -0 is the only sum that gives -0, except when rounding down
+        if self.is_zero() && other.is_zero() || !self.is_finite() || !other.is_finite() {
+            let sum = self.mantissa() + other.mantissa();
+            if mode == RoundingMode::Down && self.is_zero() && other.is_zero() {
+                return BigFloat::from_mantissa(-M::default(), 0);
+            }
+            return BigFloat::from_mantissa(sum, 0);
+        }
         if self.is_zero() {
-            return other;
+            return other.clone();
         }
         if other.is_zero() {
-            return self;
+            return self.clone();
         }
 
-        if !self.is_finite() || !other.is_finite() {
-            return BigFloat::from_f64(self.mantissa + other.mantissa);
+        if self.compare_exponents(other) == Ordering::Less {
+            return other.add_round(self, mode);
         }
+        // An addend more than two decades beyond the resolved digits is
+        // below half an ulp of any sum, even one that drops a decade, so
+        // only its sign matters and a nearer stand-in rounds the same way
+        let exp_diff = (self.exponent() - other.exponent())
+            .as_i128()
+            .unwrap_or(i128::MAX)
+            .min(M::DIGITS + 3);
 
-        let exp_cmp = self.compare_exponents(&other);
-        
-        match exp_cmp {
-            std::cmp::Ordering::Equal => {
-                let new_mantissa = self.mantissa + other.mantissa;
-                BigFloat::new(new_mantissa, self.exponent)
-            }
-            std::cmp::Ordering::Greater => {
-                // self has larger exponent
-                let exp_diff = self.exponent - other.exponent;
-                if exp_diff > 15 {
-                    // Other number is too small to affect the result
-                    return self;
-                }
-                let scale_factor = 10.0_f64.powi(exp_diff as i32);
-                let scaled_other = other.mantissa / scale_factor;
-                let new_mantissa = self.mantissa + scaled_other;
-                BigFloat::new(new_mantissa, self.exponent)
+        let sum = scaled(self.mantissa()) * 10_i128.pow(exp_diff as u32) + scaled(other.mantissa());
+        if sum == 0 {
+            // Exact cancellation gives +0, or -0 when rounding down, as in f64
+            let zero = if mode == RoundingMode::Down { -M::default() } else { M::default() };
+            return BigFloat::from_mantissa(zero, 0);
+        }
+        let (mantissa, decade) =
+            round_ratio(sum < 0, sum.unsigned_abs(), 1, M::BITS - 1, mode);
+        BigFloat::from_mantissa(mantissa, self.exponent() + Exponent::Long(decade - exp_diff))
+    }
+
+    /// `self - other` rounded once in `mode`; `-` rounds to nearest-even.
+    pub fn sub_round(&self, other: &BigFloat<M>, mode: RoundingMode) -> BigFloat<M> {
+        self.add_round(&-other.clone(), mode)
+    }
+
+    /// `self * other` rounded once in `mode`; `*` rounds to nearest-even.
+    pub fn mul_round(&self, other: &BigFloat<M>, mode: RoundingMode) -> BigFloat<M> {
+        // A finite non-zero mantissa has magnitude in [1, 10), so the
+        // product of the mantissas has the right special value and sign
+        if self.is_zero() || other.is_zero() || !self.is_finite() || !other.is_finite() {
+            let result = self.mantissa() * other.mantissa();
+            return BigFloat::from_mantissa(result, 0);
+        }
+
+        let product = scaled(self.mantissa()) * scaled(other.mantissa());
+        let fraction = 2 * (M::BITS - 1);
+        let (mantissa, decade) = round_ratio(product < 0, product.unsigned_abs(), 1, fraction, mode);
+        BigFloat::from_mantissa(mantissa, self.exponent() + other.exponent() + Exponent::Long(decade))
+    }
+
+    /// `self / other` rounded once in `mode`; `/` rounds to nearest-even.
+    pub fn div_round(&self, other: &BigFloat<M>, mode: RoundingMode) -> BigFloat<M> {
+        // As in `mul_round`: 0/0 and ∞/∞ are NaN, x/±0 is a signed infinity
+        if self.is_zero() || other.is_zero() || !self.is_finite() || !other.is_finite() {
+            let result = self.mantissa() / other.mantissa();
+            return BigFloat::from_mantissa(result, 0);
+        }
+
+        let (dividend, divisor) = (scaled(self.mantissa()), scaled(other.mantissa()));
+        let negative = (dividend < 0) != (divisor < 0);
+        let (mantissa, decade) =
+            round_ratio(negative, dividend.unsigned_abs(), divisor.unsigned_abs(), 0, mode);
+        BigFloat::from_mantissa(mantissa, self.exponent() - other.exponent() + Exponent::Long(decade))
+    }
+
+    /// The square root rounded once in `mode`. Negative operands give NaN;
+    /// -0 gives -0, as in IEEE 754.
+    pub fn sqrt_round(&self, mode: RoundingMode) -> BigFloat<M> {
+        if self.is_nan() || self.mantissa() < M::default() {
+            return BigFloat::from_mantissa(M::from_f64(f64::NAN), 0);
+        }
+        if self.is_zero() || self.is_infinite() {
+            return self.clone();
+        }
+
+        // Fold an odd exponent into the mantissa so the root's is exact;
+        // a promoted exponent is beyond 2^53 and therefore even
+        let (odd, exponent) = match &*self.exponent_cow() {
+            Exponent::Long(exponent) => {
+                (exponent.rem_euclid(2) == 1, Exponent::Long(exponent.div_euclid(2)))
             }
-            std::cmp::Ordering::Less => {
-                // other has larger exponent
-                other.add(self)
+            Exponent::BigFloat(exponent) => {
+                (false, Exponent::from_bigfloat(&**exponent / BigFloat::from_f64(2.0)))
             }
+        };
+        let (mantissa, decade) = sqrt_mantissa(self.mantissa(), odd, mode);
+        BigFloat::from_mantissa(mantissa, exponent + Exponent::Long(decade))
+    }
+
+    /// The value rounded in `mode` to `digits` significant decimal digits,
+    /// then to the nearest mantissa in the same direction, so a directed
+    /// mode still gives a bound on the value. At least one digit is kept,
+    /// and counts above `MAX_SIGNIFICANT_DIGITS` are resolved at that count.
+    pub fn round_significant(&self, digits: u32, mode: RoundingMode) -> BigFloat<M> {
+        if self.is_zero() || !self.is_finite() {
+            return self.clone();
         }
+        let fraction = M::BITS - 1;
+        let places = digits.clamp(1, MAX_SIGNIFICANT_DIGITS) - 1;
+        if places >= fraction {
+            // mantissa × 10^places is already an integer
+            return self.clone();
+        }
+
+        // mantissa × 10^places = scaled × 5^places × 2^(places - fraction)
+        let scaled = scaled(self.mantissa());
+        let negative = scaled < 0;
+        let product = scaled.unsigned_abs() * 5_u128.pow(places);
+        let dropped = fraction - places;
+        let mut kept = product >> dropped;
+        let discarded = discarded(product & ((1 << dropped) - 1), 1 << dropped);
+        if mode.rounds_away(negative, kept % 2 == 1, discarded) {
+            kept += 1;
+        }
+        let (mantissa, decade) = round_ratio(negative, kept, 10_u128.pow(places), 0, mode);
+        BigFloat::from_mantissa(mantissa, self.exponent() + Exponent::Long(decade))
     }
 }
 
-impl Sub for BigFloat {
-    type Output = BigFloat;
+/// Largest digit count `round_significant` resolves exactly.
+pub const MAX_SIGNIFICANT_DIGITS: u32 = 22;
+
+/// Decades below the larger `mul_add` term past which the smaller one only
+/// decides the direction of rounding. The product has at most 106 digits,
+/// so the larger term is at least 10^-105 of its leading digit away from
+/// any rounding boundary it is not on; a smaller term below 10^-109 of it,
+/// or a stand-in of the same sign, lands on the same side of all of them.
+const FUSED_GAP: i128 = 110;
 
-    fn sub(self, other: BigFloat) -> BigFloat {
-        let neg_other = BigFloat {
-            mantissa: -other.mantissa,
-            exponent: other.exponent,
+/// Digits that hold every exact `mul_add` sum: the product's 106, the gap
+/// and a carry.
+const FUSED_PRECISION: usize = 224;
+
+impl BigFloat {
+    /// `self * a + b` rounded once to nearest-even, as `f64::mul_add`.
+    ///
+    /// The product and the sum are formed exactly in decimal, so `b` counts
+    /// in full even when it nearly cancels the product. Values with nested
+    /// exponents fall back to `self * a + b`.
+    pub fn mul_add(&self, a: &BigFloat, b: &BigFloat) -> BigFloat {
+        let operands = [self, a, b];
+        let exact = operands.iter().all(|operand| {
+            operand.is_finite() && !operand.is_zero() && operand.exponent_ref().as_i128().is_some()
+        });
+        // Specials and zeros leave at most one rounding, in the product
+        if !exact {
+            return self * a + b;
+        }
+
+        let product = exact_decimal(self) * exact_decimal(a);
+        let addend = exact_decimal(b);
+        let (Some(high), Some(low)) = (product.exponent().as_i128(), addend.exponent().as_i128()) else {
+            return self * a + b;
         };
-        self.add(neg_other)
+        let sum = if high - low > FUSED_GAP {
+            product + stand_in(&addend, high - FUSED_GAP)
+        } else if low - high > FUSED_GAP {
+            stand_in(&product, low - FUSED_GAP) + addend
+        } else {
+            product + addend
+        };
+        sum.to_bigfloat()
+    }
+
+    /// Σ a[i]·b[i], each term added with `mul_add`, so the sum rounds once
+    /// per element. Extra elements of the longer slice are ignored.
+    pub fn dot(a: &[BigFloat], b: &[BigFloat]) -> BigFloat {
+        a.iter().zip(b).fold(BigFloat::from_f64(0.0), |sum, (a, b)| a.mul_add(b, &sum))
+    }
+
+    /// The polynomial with `coefficients`, highest power first, at `x`, by
+    /// Horner's rule with `mul_add`. No coefficients give zero.
+    pub fn polyval(coefficients: &[BigFloat], x: &BigFloat) -> BigFloat {
+        coefficients
+            .iter()
+            .fold(BigFloat::from_f64(0.0), |value, coefficient| value.mul_add(x, coefficient))
     }
 }
 
-impl Mul for BigFloat {
-    type Output = BigFloat;
+/// A finite non-zero `BigFloat` with a `Long` exponent as a `BigDecimal`
+/// holding every digit of its binary mantissa.
+fn exact_decimal(value: &BigFloat) -> BigDecimal {
+    // A mantissa in [1, 10) is a multiple of 2^-52, which ends within 52 places
+    let exponent = value.exponent_ref().as_i128().unwrap_or(0);
+    let digits = format!("{:.52}e{}", value.mantissa(), exponent);
+    let context = DecimalContext::new(FUSED_PRECISION, RoundingMode::NearestEven);
+    BigDecimal::parse_with(&digits, context).unwrap_or_else(|_| BigDecimal::from(value.clone()))
+}
 
-    fn mul(self, other: BigFloat) -> BigFloat {
-        if self.is_zero() || other.is_zero() {
-            return BigFloat::from_f64(0.0);
-        }
+/// ±10^`exponent` with the sign of `term`.
+fn stand_in(term: &BigDecimal, exponent: i128) -> BigDecimal {
+    let sign = if term.is_sign_negative() { -1.0 } else { 1.0 };
+    let context = DecimalContext::new(FUSED_PRECISION, RoundingMode::NearestEven);
+    BigDecimal::new(sign, exponent).round_to(context)
+}
 
-        if !self.is_finite() || !other.is_finite() {
-            let result = self.mantissa * other.mantissa;
-            return BigFloat::from_f64(result);
-        }
+/// A finite mantissa as an exact integer multiple of 2^(1 - BITS); one in
+/// [1, 10) is below 2^57.
+fn scaled<M: Mantissa>(mantissa: M) -> i128 {
+    (mantissa.to_f64() * (1_u64 << (M::BITS - 1)) as f64) as i128
+}
 
-        let new_mantissa = self.mantissa * other.mantissa;
-        let new_exp = self.exponent + other.exponent;
-        
-        BigFloat::new(new_mantissa, new_exp)
+/// How `remainder` compares with half of `divisor`.
+fn discarded(remainder: u128, divisor: u128) -> Discarded {
+    match (remainder * 2).cmp(&divisor) {
+        _ if remainder == 0 => Discarded::Zero,
+        Ordering::Less => Discarded::BelowHalf,
+        Ordering::Equal => Discarded::Half,
+        Ordering::Greater => Discarded::AboveHalf,
     }
 }
 
-impl Div for BigFloat {
-    type Output = BigFloat;
+/// `numerator / denominator × 2^-fraction` for a non-zero ratio, rounded
+/// once to `M` in `mode` and split into a mantissa in [1, 10) and its
+/// decade.
+///
+/// The operators reduce their operands to such an exact ratio of integers:
+/// both mantissas are multiples of 2^(1 - BITS), so a sum aligned to the
+/// larger exponent, a product or a quotient is formed without error. The
+/// decade and the rounding are then decided on integers, which avoids the
+/// double rounding of an f64 result that is renormalized afterwards.
+fn round_ratio<M: Mantissa>(
+    negative: bool,
+    numerator: u128,
+    denominator: u128,
+    fraction: u32,
+    mode: RoundingMode,
+) -> (M, i128) {
+    let bit_length = |n: u128| (128 - n.leading_zeros()) as i32;
 
-    fn div(self, other: BigFloat) -> BigFloat {
-        if other.is_zero() {
-            return BigFloat::from_f64(f64::INFINITY);
-        }
+    // Find the power of ten with 10^decade <= ratio × 2^-fraction < 10^(decade + 1)
+    let bits = bit_length(numerator) - bit_length(denominator) - fraction as i32;
+    let mut decade = (bits * LOG10_2_SCALED) >> 18;
+    let below = |decade: i32| match decade {
+        0.. => numerator < (denominator * 10_u128.pow(decade as u32)) << fraction,
+        _ => numerator * 10_u128.pow(decade.unsigned_abs()) < denominator << fraction,
+    };
+    while below(decade) {
+        decade -= 1;
+    }
+    while !below(decade + 1) {
+        decade += 1;
+    }
+    let (mut numerator, mut denominator) = match decade {
+        0.. => (numerator, denominator * 10_u128.pow(decade as u32)),
+        _ => (numerator * 10_u128.pow(decade.unsigned_abs()), denominator),
+    };
 
-        if self.is_zero() {
-            return BigFloat::from_f64(0.0);
-        }
+    // The mantissa is now numerator / denominator × 2^-fraction, in
+    // [1, 10); keep the BITS bits of its binade and round the rest
+    let kept_bits = M::BITS as i32 - 1;
+    let binade = bit_length(numerator / denominator) - 1 - fraction as i32;
+    let shift = kept_bits - binade - fraction as i32;
+    if shift >= 0 {
+        numerator <<= shift;
+    } else {
+        denominator <<= -shift;
+    }
+    let mut quotient = numerator / denominator;
+    let discarded = discarded(numerator % denominator, denominator);
+    if mode.rounds_away(negative, quotient % 2 == 1, discarded) {
+        quotient += 1;
+    }
+    finish(negative, quotient, kept_bits - binade, decade as i128)
+}
 
-        if !self.is_finite() || !other.is_finite() {
-            let result = self.mantissa / other.mantissa;
-            return BigFloat::from_f64(result);
-        }
+/// The root of `mantissa`, or of 10 × `mantissa` when `odd`, rounded once
+/// in `mode` and split like `round_ratio`.
+fn sqrt_mantissa<M: Mantissa>(mantissa: M, odd: bool, mode: RoundingMode) -> (M, i128) {
+    let fraction = M::BITS as i32 - 1;
+    // value × 2^-fraction is in [1, 100) and its root in [1, 10)
+    let value = scaled(mantissa).unsigned_abs() * if odd { 10 } else { 1 };
+    let binade = (127 - value.leading_zeros() as i32 - fraction) / 2;
 
-        let new_mantissa = self.mantissa / other.mantissa;
-        
-        // Handle underflow case where other.exponent > self.exponent
-        if other.exponent > self.exponent {
-            // Result would have negative exponent, so we scale mantissa and use exponent 0
-            let exp_diff = other.exponent - self.exponent;
-            let scaled_mantissa = new_mantissa / 10.0_f64.powi(exp_diff as i32);
-            BigFloat::new(scaled_mantissa, 0)
-        } else {
-            let new_exp = self.exponent - other.exponent;
-            BigFloat::new(new_mantissa, new_exp)
-        }
+    // The kept bits are floor(root × 2^(fraction - binade)), the integer
+    // root of value × 2^(fraction - 2 × binade)
+    let radicand = value << (fraction - 2 * binade);
+    let mut root = radicand.isqrt();
+    let remainder = radicand - root * root;
+    // The root is never exactly halfway: (root + 1/2)² is not an integer
+    let discarded = match remainder {
+        0 => Discarded::Zero,
+        _ if remainder > root => Discarded::AboveHalf,
+        _ => Discarded::BelowHalf,
+    };
+    if mode.rounds_away(false, root % 2 == 1, discarded) {
+        root += 1;
+    }
+    finish(false, root, fraction - binade, 0)
+}
+
+/// `quotient` × 2^-`shift` as a signed `M`, moving a mantissa that rounded
+/// up to 10 into the next decade.
+fn finish<M: Mantissa>(negative: bool, quotient: u128, shift: i32, decade: i128) -> (M, i128) {
+    // At most BITS + 1 bits, so both conversions are exact
+    let mantissa = quotient as f64 / (1_u64 << shift) as f64;
+    let (mantissa, decade) = if mantissa == 10.0 { (1.0, decade + 1) } else { (mantissa, decade) };
+    (M::from_f64(if negative { -mantissa } else { mantissa }), decade)
+}
+
+impl<M: Mantissa> Add for BigFloat<M> {
+    type Output = BigFloat<M>;
+
+    fn add(self, other: BigFloat<M>) -> BigFloat<M> {
+        self.add_round(&other, RoundingMode::NearestEven)
+    }
+}
+
+impl<M: Mantissa> Sub for BigFloat<M> {
+    type Output = BigFloat<M>;
+
+    fn sub(self, other: BigFloat<M>) -> BigFloat<M> {
+        self.add(-other)
+    }
+}
+
+impl<M: Mantissa> Mul for BigFloat<M> {
+    type Output = BigFloat<M>;
+
+    fn mul(self, other: BigFloat<M>) -> BigFloat<M> {
+        self.mul_round(&other, RoundingMode::NearestEven)
+    }
+}
+
+impl<M: Mantissa> Div for BigFloat<M> {
+    type Output = BigFloat<M>;
+
+    fn div(self, other: BigFloat<M>) -> BigFloat<M> {
+        self.div_round(&other, RoundingMode::NearestEven)
     }
-}
\ No newline at end of file
+}
+
+// Borrowed operands forward to the owned implementations above
+macro_rules! impl_ref_ops {
+    ($($trait:ident, $method:ident);*) => {
+        $(
+            impl<M: Mantissa> $trait<&BigFloat<M>> for BigFloat<M> {
+                type Output = BigFloat<M>;
+
+                fn $method(self, other: &BigFloat<M>) -> BigFloat<M> {
+                    self.$method(other.clone())
+                }
+            }
+
+            impl<M: Mantissa> $trait<BigFloat<M>> for &BigFloat<M> {
+                type Output = BigFloat<M>;
+
+                fn $method(self, other: BigFloat<M>) -> BigFloat<M> {
+                    self.clone().$method(other)
+                }
+            }
+
+            impl<M: Mantissa> $trait<&BigFloat<M>> for &BigFloat<M> {
+                type Output = BigFloat<M>;
+
+                fn $method(self, other: &BigFloat<M>) -> BigFloat<M> {
+                    self.clone().$method(other.clone())
+                }
+            }
+        )*
+    };
+}
+
+impl_ref_ops!(Add, add; Sub, sub; Mul, mul; Div, div);
+
+// Primitive operands on either side convert through `BigFloat::from`
+macro_rules! impl_primitive_ops {
+    ($($t:ty),*) => {
+        $(
+            impl_primitive_ops!(@ops $t; Add, add; Sub, sub; Mul, mul; Div, div);
+        )*
+    };
+    (@ops $t:ty; $($trait:ident, $method:ident);*) => {
+        $(
+            impl $trait<$t> for BigFloat {
+                type Output = BigFloat;
+
+                fn $method(self, other: $t) -> BigFloat {
+                    self.$method(BigFloat::from(other))
+                }
+            }
+
+            impl $trait<$t> for &BigFloat {
+                type Output = BigFloat;
+
+                fn $method(self, other: $t) -> BigFloat {
+                    self.clone().$method(BigFloat::from(other))
+                }
+            }
+
+            impl $trait<BigFloat> for $t {
+                type Output = BigFloat;
+
+                fn $method(self, other: BigFloat) -> BigFloat {
+                    BigFloat::from(self).$method(other)
+                }
+            }
+
+            impl $trait<&BigFloat> for $t {
+                type Output = BigFloat;
+
+                fn $method(self, other: &BigFloat) -> BigFloat {
+                    BigFloat::from(self).$method(other.clone())
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_ops!(f64, f32, i32, i64, u64);
@@ -0,0 +1,1045 @@
+use crate::bigfloat::BigFloat;
+use crate::convert::write_digits;
+use crate::error::BigFloatError;
+use crate::exponent::Exponent;
+use crate::rounding::{Discarded, RoundingMode};
+use crate::traits::Category;
+use std::cmp::Ordering;
+use std::fmt;
+use crate::operators::impl_forwarded_ops;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+// Extra digits carried through the steps of `ln`, `exp`, `pow` and `sqrt`
+const GUARD_DIGITS: usize = 10;
+
+// `exp` and `pow` expand their arguments as plain decimals up to this decimal
+// exponent, with the working precision growing by one digit per integer digit
+// of the argument; past it the result's exponent no longer fits an i128
+const ARGUMENT_DIGITS: i128 = 37;
+
+/// Precision (in significant decimal digits) and rounding mode of a
+/// `BigDecimal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecimalContext {
+    precision: usize,
+    rounding: RoundingMode,
+}
+
+impl DecimalContext {
+    /// 34 digits, as in IEEE 754 decimal128.
+    pub const DEFAULT_PRECISION: usize = 34;
+
+    pub fn new(precision: usize, rounding: RoundingMode) -> Self {
+        DecimalContext {
+            precision: precision.max(1),
+            rounding,
+        }
+    }
+
+    /// Significant decimal digits of a result, at least 1.
+    pub fn precision(self) -> usize {
+        self.precision
+    }
+
+    pub fn rounding(self) -> RoundingMode {
+        self.rounding
+    }
+
+    /// The context of a result: the larger precision of the two operands and
+    /// the rounding mode of the left one.
+    fn combine(self, other: DecimalContext) -> DecimalContext {
+        DecimalContext::new(self.precision.max(other.precision), self.rounding)
+    }
+
+    // Intermediate results round to nearest with extra digits
+    fn guarded(self, extra: usize) -> DecimalContext {
+        DecimalContext::new(self.precision + extra, RoundingMode::NearestEven)
+    }
+}
+
+impl Default for DecimalContext {
+    fn default() -> Self {
+        DecimalContext::new(DecimalContext::DEFAULT_PRECISION, RoundingMode::NearestEven)
+    }
+}
+
+/// A floating-point number with an arbitrary-precision decimal mantissa:
+/// d.ddd… × 10^exponent, exact to the digit.
+///
+/// The exponent works as in `BigFloat`. The mantissa is a vector of decimal
+/// digits with no leading or trailing zeros, rounded to the precision of the
+/// value's `DecimalContext`. A result takes the larger precision of its
+/// operands and the rounding mode of the left operand.
+#[derive(Debug, Clone)]
+pub struct BigDecimal {
+    category: Category,
+    negative: bool,
+    digits: Vec<u8>,
+    exponent: Exponent,
+    context: DecimalContext,
+}
+
+impl BigDecimal {
+    /// `mantissa` × 10^`exponent`, taking the shortest decimal digits that
+    /// round-trip `mantissa`, in the default context.
+    pub fn new(mantissa: f64, exponent: impl Into<Exponent>) -> Self {
+        from_shortest(mantissa, exponent.into(), DecimalContext::default())
+    }
+
+    /// Parses `s` and rounds it to `context`.
+    pub fn parse_with(s: &str, context: DecimalContext) -> Result<BigDecimal, BigFloatError> {
+        s.parse::<BigDecimal>().map(|value| value.round_to(context))
+    }
+
+    /// The value rounded to `context`, which the result then carries.
+    pub fn round_to(&self, context: DecimalContext) -> BigDecimal {
+        match self.category {
+            Category::Normal => {
+                finish(self.negative, self.digits.clone(), self.exponent(), false, context)
+            }
+            _ => BigDecimal { context, ..self.clone() },
+        }
+    }
+
+    pub fn context(&self) -> DecimalContext {
+        self.context
+    }
+
+    /// The significant digits, most significant first; empty for zeros,
+    /// infinities and NaN.
+    pub fn digits(&self) -> &[u8] {
+        &self.digits
+    }
+
+    /// The exponent of the first significant digit.
+    pub fn exponent(&self) -> Exponent {
+        self.exponent.clone()
+    }
+
+    pub fn exponent_ref(&self) -> &Exponent {
+        &self.exponent
+    }
+
+    pub fn classify(&self) -> Category {
+        self.category
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.category == Category::Zero
+    }
+
+    pub fn is_finite(&self) -> bool {
+        matches!(self.category, Category::Zero | Category::Normal)
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.category == Category::Nan
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.category == Category::Infinite
+    }
+
+    pub fn is_sign_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> BigDecimal {
+        BigDecimal { negative: false, ..self.clone() }
+    }
+
+    /// Rounds the mantissa to the nearest f64, giving the nearest `BigFloat`.
+    pub fn to_bigfloat(&self) -> BigFloat {
+        if self.category != Category::Normal {
+            return BigFloat::from_f64(self.stand_in());
+        }
+        let digits: String = self.digits.iter().map(|&digit| char::from(b'0' + digit)).collect();
+        let mantissa: f64 = format!("{}.{}", &digits[..1], &digits[1..]).parse().unwrap_or(f64::NAN);
+        BigFloat::new(if self.negative { -mantissa } else { mantissa }, self.exponent())
+    }
+
+    pub fn to_f64(&self) -> Option<f64> {
+        self.to_bigfloat().to_f64()
+    }
+
+    /// Natural logarithm, rounded to the value's context.
+    pub fn ln(&self) -> BigDecimal {
+        match (self.category, self.exponent.as_i128()) {
+            (Category::Normal, Some(exponent)) if !self.negative => {
+                let work = self.context.guarded(GUARD_DIGITS + decimal_length(exponent));
+                self.ln_positive(exponent, work).round_to(self.context)
+            }
+            _ => from_bigfloat(&self.to_bigfloat().ln(), self.context),
+        }
+    }
+
+    /// Base-10 logarithm; exact for powers of ten.
+    pub fn log10(&self) -> BigDecimal {
+        match (self.category, self.exponent.as_i128()) {
+            (Category::Normal, Some(exponent)) if !self.negative => {
+                let work = self.context.guarded(GUARD_DIGITS + decimal_length(exponent));
+                let fraction = self.mantissa_part(work).ln_mantissa(work).div_with(&ln_10(work), work);
+                BigDecimal::from_i128_with(exponent, work).add_with(&fraction, self.context)
+            }
+            _ => from_bigfloat(&self.to_bigfloat().log10(), self.context),
+        }
+    }
+
+    /// e^self, computed as e^r × 10^k with k = floor(self / ln 10).
+    pub fn exp(&self) -> BigDecimal {
+        self.exp_with(self.context)
+    }
+
+    /// self^exponent. Integer exponents use repeated squaring; others go
+    /// through `exp` and `ln`, and a negative base needs an integer exponent
+    /// (otherwise the result is NaN).
+    pub fn pow(&self, exponent: &BigDecimal) -> BigDecimal {
+        let context = self.context.combine(exponent.context);
+        if let Some(n) = exponent.to_i128().and_then(|n| i64::try_from(n).ok()) {
+            return self.powi_with(n, context);
+        }
+
+        let arguments = (self.exponent.as_i128(), exponent.exponent.as_i128());
+        if self.category == Category::Normal && exponent.category == Category::Normal {
+            if let (Some(base_exponent), Some(power_exponent)) = arguments {
+                if power_exponent <= ARGUMENT_DIGITS {
+                    // Past i64 an odd integer exponent still ends in an odd units digit
+                    let odd = if exponent.is_integer() {
+                        exponent.digits.len() as i128 == power_exponent + 1
+                            && exponent.digits.last().is_some_and(|digit| digit % 2 == 1)
+                    } else if self.negative {
+                        return special(f64::NAN, context);
+                    } else {
+                        false
+                    };
+                    let work = context.guarded(GUARD_DIGITS + decimal_length(base_exponent) + 40);
+                    let ln = self.abs().ln_positive(base_exponent, work);
+                    let magnitude = exponent.mul_with(&ln, work).exp_with(context);
+                    return if self.negative && odd { -magnitude } else { magnitude };
+                }
+            }
+        }
+        from_bigfloat(&self.to_bigfloat().pow(&exponent.to_bigfloat()), context)
+    }
+
+    /// self^n by binary exponentiation.
+    pub fn powi(&self, n: i64) -> BigDecimal {
+        self.powi_with(n, self.context)
+    }
+
+    /// Square root by Newton's method, rounded to the value's context.
+    pub fn sqrt(&self) -> BigDecimal {
+        if self.category != Category::Normal || self.negative || self.exponent.as_i128().is_none() {
+            return from_bigfloat(&self.to_bigfloat().sqrt(), self.context);
+        }
+        let work = self.context.guarded(GUARD_DIGITS);
+        let two = BigDecimal::from_i128_with(2, work);
+        let mut root = from_bigfloat(&self.to_bigfloat().sqrt(), work);
+        let mut previous = root.clone();
+        for _ in 0..64 {
+            let next = root.add_with(&self.div_with(&root, work), work).div_with(&two, work);
+            // Stop at a fixed point, or when the last digit alternates
+            if next == root || next == previous {
+                break;
+            }
+            previous = std::mem::replace(&mut root, next);
+        }
+        root.round_to(self.context)
+    }
+
+    fn from_i128_with(value: i128, context: DecimalContext) -> BigDecimal {
+        let digits = value.unsigned_abs().to_string().bytes().map(|byte| byte - b'0').collect::<Vec<_>>();
+        let exponent = Exponent::Long(digits.len() as i128 - 1);
+        finish(value < 0, digits, exponent, false, context)
+    }
+
+    // ±1 for ordinary values, so that f64 arithmetic gives the special results
+    fn stand_in(&self) -> f64 {
+        let magnitude = match self.category {
+            Category::Nan => return f64::NAN,
+            Category::Infinite => f64::INFINITY,
+            Category::Zero => 0.0,
+            Category::Normal => 1.0,
+        };
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    fn is_integer(&self) -> bool {
+        match self.category {
+            Category::Zero => true,
+            Category::Normal => match self.exponent.as_i128() {
+                Some(exponent) => self.digits.len() as i128 <= exponent + 1,
+                None => !self.exponent.is_negative(),
+            },
+            _ => false,
+        }
+    }
+
+    /// The value as an `i128` if it is an integer that fits.
+    fn to_i128(&self) -> Option<i128> {
+        match self.floor_i128() {
+            Some(integer) if self.is_integer() => Some(integer),
+            _ => None,
+        }
+    }
+
+    /// The largest integer not above the value, if it fits in an `i128`.
+    fn floor_i128(&self) -> Option<i128> {
+        match (self.category, self.exponent.as_i128()) {
+            (Category::Zero, _) => Some(0),
+            (Category::Normal, Some(exponent)) if exponent < 39 => {
+                let mut whole: i128 = 0;
+                for place in 0..exponent + 1 {
+                    let digit = *self.digits.get(place as usize).unwrap_or(&0) as i128;
+                    whole = whole.checked_mul(10)?.checked_add(digit)?;
+                }
+                let fractional = self.digits.len() as i128 > exponent + 1;
+                Some(match (self.negative, fractional) {
+                    (false, _) => whole,
+                    (true, false) => -whole,
+                    (true, true) => -whole - 1,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    // The mantissa d.ddd… on its own, in [1, 10)
+    fn mantissa_part(&self, context: DecimalContext) -> BigDecimal {
+        BigDecimal {
+            category: Category::Normal,
+            negative: false,
+            digits: self.digits.clone(),
+            exponent: Exponent::Long(0),
+            context,
+        }
+    }
+
+    /// ln(self) = ln(mantissa) + exponent·ln 10 for a positive normal value.
+    fn ln_positive(&self, exponent: i128, work: DecimalContext) -> BigDecimal {
+        let ln_mantissa = self.mantissa_part(work).ln_mantissa(work);
+        if exponent == 0 {
+            return ln_mantissa;
+        }
+        let decades = BigDecimal::from_i128_with(exponent, work).mul_with(&ln_10(work), work);
+        ln_mantissa.add_with(&decades, work)
+    }
+
+    /// ln of a value in [1, 10): halve it below 1.5, then
+    /// ln m = k·ln 2 + 2·atanh((m - 1)/(m + 1)).
+    fn ln_mantissa(&self, work: DecimalContext) -> BigDecimal {
+        let one = BigDecimal::from_i128_with(1, work);
+        let two = BigDecimal::from_i128_with(2, work);
+        let limit = BigDecimal::new(1.5, 0);
+        let mut reduced = self.clone();
+        let mut halvings = 0;
+        while reduced > limit {
+            reduced = reduced.div_with(&two, work);
+            halvings += 1;
+        }
+        let ratio = reduced.sub_with(&one, work).div_with(&reduced.add_with(&one, work), work);
+        let ln_reduced = atanh(&ratio, work).mul_with(&two, work);
+        if halvings == 0 {
+            return ln_reduced;
+        }
+        let scaled_ln_2 = ln_2(work).mul_with(&BigDecimal::from_i128_with(halvings, work), work);
+        scaled_ln_2.add_with(&ln_reduced, work)
+    }
+
+    fn exp_with(&self, context: DecimalContext) -> BigDecimal {
+        const SQUARINGS: usize = 10;
+
+        let exponent = match (self.category, self.exponent.as_i128()) {
+            (Category::Zero, _) => return BigDecimal::from_i128_with(1, context),
+            (Category::Normal, Some(exponent)) if exponent <= ARGUMENT_DIGITS => exponent,
+            _ => return from_bigfloat(&self.to_bigfloat().exp(), context),
+        };
+        if exponent < -((context.precision + GUARD_DIGITS) as i128) {
+            // e^x = 1 + x to well past the last digit
+            return BigDecimal::from_i128_with(1, context).add_with(self, context);
+        }
+
+        // The integer digits of self become exponent digits of the result
+        let work = context.guarded(GUARD_DIGITS + (exponent + 1).max(0) as usize);
+        let ln_10 = ln_10(work);
+        let decades = match self.div_with(&ln_10, work).floor_i128() {
+            Some(decades) => decades,
+            None => return from_bigfloat(&self.to_bigfloat().exp(), context),
+        };
+        let reduced = self.sub_with(&BigDecimal::from_i128_with(decades, work).mul_with(&ln_10, work), work);
+
+        // e^r = (e^(r / 2^10))^(2^10), with e^s - 1 from its Taylor series
+        let small = reduced.div_with(&BigDecimal::from_i128_with(1 << SQUARINGS, work), work);
+        let mut term = small.clone();
+        let mut sum = small.clone();
+        for n in 2.. {
+            term = term.mul_with(&small, work).div_with(&BigDecimal::from_i128_with(n, work), work);
+            if negligible(&term, &sum, work) {
+                break;
+            }
+            sum = sum.add_with(&term, work);
+        }
+        let mut result = sum.add_with(&BigDecimal::from_i128_with(1, work), work);
+        for _ in 0..SQUARINGS {
+            result = result.mul_with(&result, work);
+        }
+
+        let exponent = result.exponent() + Exponent::from(decades);
+        finish(false, result.digits, exponent, false, context)
+    }
+
+    fn powi_with(&self, n: i64, context: DecimalContext) -> BigDecimal {
+        // Each squaring and multiplication can cost half a unit in the last place
+        let work = context.guarded(GUARD_DIGITS + 64 - n.unsigned_abs().leading_zeros() as usize);
+        let mut base = self.clone();
+        let mut remaining = n.unsigned_abs();
+        let mut result = BigDecimal::from_i128_with(1, work);
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result.mul_with(&base, work);
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                base = base.mul_with(&base, work);
+            }
+        }
+        if n < 0 {
+            BigDecimal::from_i128_with(1, context).div_with(&result, context)
+        } else {
+            result.round_to(context)
+        }
+    }
+
+    fn add_with(&self, other: &BigDecimal, context: DecimalContext) -> BigDecimal {
+        match (self.category, other.category) {
+            (Category::Normal, Category::Normal) => {}
+            (Category::Zero, Category::Normal) => return other.round_to(context),
+            (Category::Normal, Category::Zero) => return self.round_to(context),
+            _ => return special(self.stand_in() + other.stand_in(), context),
+        }
+
+        let (big, small) = if self.exponent >= other.exponent { (self, other) } else { (other, self) };
+        // An operand entirely below the rounding position only matters as a
+        // sticky digit that keeps directed rounding honest
+        let limit = context.precision + 2;
+        let (small_digits, offset) = match (big.exponent() - small.exponent()).as_i128() {
+            Some(difference) if difference <= limit as i128 => (small.digits.as_slice(), difference as usize),
+            _ => (&[1][..], limit + 1),
+        };
+
+        // One extra leading place for a carry
+        let length = big.digits.len().max(offset + small_digits.len()) + 1;
+        let mut big_aligned = vec![0; length];
+        big_aligned[1..=big.digits.len()].copy_from_slice(&big.digits);
+        let mut small_aligned = vec![0; length];
+        small_aligned[offset + 1..=offset + small_digits.len()].copy_from_slice(small_digits);
+        let exponent = big.exponent() + Exponent::Long(1);
+
+        if big.negative == small.negative {
+            add_digits(&mut big_aligned, &small_aligned);
+            return finish(big.negative, big_aligned, exponent, false, context);
+        }
+        match big_aligned.cmp(&small_aligned) {
+            Ordering::Greater => {
+                subtract_digits(&mut big_aligned, &small_aligned);
+                finish(big.negative, big_aligned, exponent, false, context)
+            }
+            Ordering::Less => {
+                subtract_digits(&mut small_aligned, &big_aligned);
+                finish(small.negative, small_aligned, exponent, false, context)
+            }
+            // x - x is +0, except that rounding toward -∞ gives -0
+            Ordering::Equal => special(if context.rounding == RoundingMode::Down { -0.0 } else { 0.0 }, context),
+        }
+    }
+
+    fn sub_with(&self, other: &BigDecimal, context: DecimalContext) -> BigDecimal {
+        self.add_with(&-other, context)
+    }
+
+    fn mul_with(&self, other: &BigDecimal, context: DecimalContext) -> BigDecimal {
+        if self.category != Category::Normal || other.category != Category::Normal {
+            return special(self.stand_in() * other.stand_in(), context);
+        }
+
+        // Schoolbook product; place 0 holds the tens digit of the mantissas' product
+        let mut product = vec![0u64; self.digits.len() + other.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            for (j, &b) in other.digits.iter().enumerate() {
+                product[i + j + 1] += a as u64 * b as u64;
+            }
+        }
+        let mut carry = 0;
+        for place in product.iter_mut().rev() {
+            let value = *place + carry;
+            *place = value % 10;
+            carry = value / 10;
+        }
+
+        let digits = product.into_iter().map(|digit| digit as u8).collect();
+        let exponent = self.exponent() + other.exponent() + Exponent::Long(1);
+        finish(self.negative != other.negative, digits, exponent, false, context)
+    }
+
+    fn div_with(&self, other: &BigDecimal, context: DecimalContext) -> BigDecimal {
+        if self.category != Category::Normal || other.category != Category::Normal {
+            return special(self.stand_in() / other.stand_in(), context);
+        }
+
+        // Long division, one quotient digit per step, until the digit after
+        // the last kept one; a non-zero remainder is the sticky digit. Both
+        // mantissas are aligned at their leading digit, so the first quotient
+        // digit comes from as many dividend digits as the divisor has.
+        let mut next = other.digits.len() - 1;
+        let mut remainder: Vec<u8> = (0..next).map(|place| self.digits.get(place).copied().unwrap_or(0)).collect();
+        let mut quotient = Vec::with_capacity(context.precision + 2);
+        while quotient.iter().skip_while(|&&digit| digit == 0).count() <= context.precision {
+            remainder.push(self.digits.get(next).copied().unwrap_or(0));
+            next += 1;
+            let mut digit = 0;
+            while compare_digits(&remainder, &other.digits) != Ordering::Less {
+                subtract_unaligned(&mut remainder, &other.digits);
+                digit += 1;
+            }
+            quotient.push(digit);
+        }
+        let sticky = remainder.iter().any(|&digit| digit != 0)
+            || self.digits.iter().skip(next).any(|&digit| digit != 0);
+
+        let exponent = self.exponent() - other.exponent();
+        finish(self.negative != other.negative, quotient, exponent, sticky, context)
+    }
+}
+
+/// Brings digits whose first place has value 10^`exponent` into canonical
+/// form: strips leading zeros, rounds to `context` (with `sticky` standing
+/// for non-zero digits past the end), strips trailing zeros and
+/// canonicalizes the exponent.
+fn finish(
+    negative: bool,
+    mut digits: Vec<u8>,
+    exponent: Exponent,
+    sticky: bool,
+    context: DecimalContext,
+) -> BigDecimal {
+    let leading = digits.iter().take_while(|&&digit| digit == 0).count();
+    if leading == digits.len() {
+        return special(if negative { -0.0 } else { 0.0 }, context);
+    }
+    digits.drain(..leading);
+    let mut exponent = exponent - Exponent::Long(leading as i128);
+
+    let carried = round_digits(&mut digits, sticky, negative, context);
+    if carried {
+        exponent = exponent + Exponent::Long(1);
+    }
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    let exponent = match exponent.saturating_canonical(negative) {
+        Ok(exponent) => exponent,
+        Err(value) => return special(value, context),
+    };
+    BigDecimal {
+        category: Category::Normal,
+        negative,
+        digits,
+        exponent,
+        context,
+    }
+}
+
+/// Rounds `digits` to `context.precision` digits, returning whether rounding
+/// carried into a new leading digit.
+fn round_digits(digits: &mut Vec<u8>, sticky: bool, negative: bool, context: DecimalContext) -> bool {
+    let precision = context.precision;
+    if digits.len() <= precision && !sticky {
+        return false;
+    }
+    digits.resize(digits.len().max(precision), 0);
+    let dropped = digits.split_off(precision);
+    let tail = sticky || dropped.iter().skip(1).any(|&digit| digit != 0);
+    let discarded = match (dropped.first().copied().unwrap_or(0), tail) {
+        (0, false) => Discarded::Zero,
+        (5, false) => Discarded::Half,
+        (5, true) => Discarded::AboveHalf,
+        (first, _) if first < 5 => Discarded::BelowHalf,
+        _ => Discarded::AboveHalf,
+    };
+
+    let odd = digits.last().is_some_and(|digit| digit % 2 == 1);
+    if !context.rounding.rounds_away(negative, odd, discarded) {
+        return false;
+    }
+    match digits.iter().rposition(|&digit| digit != 9) {
+        Some(position) => {
+            digits[position] += 1;
+            digits.truncate(position + 1);
+            false
+        }
+        None => {
+            *digits = vec![1];
+            true
+        }
+    }
+}
+
+fn special(value: f64, context: DecimalContext) -> BigDecimal {
+    let category = if value.is_nan() {
+        Category::Nan
+    } else if value.is_infinite() {
+        Category::Infinite
+    } else if value == 0.0 {
+        Category::Zero
+    } else {
+        Category::Normal
+    };
+    debug_assert!(category != Category::Normal, "not a special value: {}", value);
+    BigDecimal {
+        category,
+        negative: value.is_sign_negative() && !value.is_nan(),
+        digits: Vec::new(),
+        exponent: Exponent::Long(0),
+        context,
+    }
+}
+
+/// The shortest decimal digits that round-trip `mantissa`, times
+/// 10^`exponent`.
+fn from_shortest(mantissa: f64, exponent: Exponent, context: DecimalContext) -> BigDecimal {
+    if mantissa == 0.0 || !mantissa.is_finite() {
+        return special(mantissa, context);
+    }
+    let formatted = format!("{:e}", mantissa.abs());
+    let (digits, power) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+    let digits = digits.bytes().filter(u8::is_ascii_digit).map(|byte| byte - b'0').collect();
+    let power: i128 = power.parse().unwrap_or(0);
+    finish(mantissa < 0.0, digits, exponent + Exponent::Long(power), false, context)
+}
+
+fn from_bigfloat(value: &BigFloat, context: DecimalContext) -> BigDecimal {
+    from_shortest(value.mantissa(), value.exponent(), context)
+}
+
+/// Number of decimal digits in |value|.
+fn decimal_length(value: i128) -> usize {
+    value.unsigned_abs().checked_ilog10().map_or(1, |log| log as usize + 1)
+}
+
+/// Whether `term` no longer changes `sum` at the working precision.
+fn negligible(term: &BigDecimal, sum: &BigDecimal, work: DecimalContext) -> bool {
+    if term.is_zero() {
+        return true;
+    }
+    match (term.exponent.as_i128(), sum.exponent.as_i128()) {
+        (Some(term), Some(sum)) => term < sum - work.precision as i128 - 1,
+        _ => true,
+    }
+}
+
+/// atanh(z) = z + z³/3 + z⁵/5 + … for small |z|.
+fn atanh(z: &BigDecimal, work: DecimalContext) -> BigDecimal {
+    let square = z.mul_with(z, work);
+    let mut power = z.clone();
+    let mut sum = z.clone();
+    for n in (3..).step_by(2) {
+        power = power.mul_with(&square, work);
+        let term = power.div_with(&BigDecimal::from_i128_with(n, work), work);
+        if negligible(&term, &sum, work) {
+            break;
+        }
+        sum = sum.add_with(&term, work);
+    }
+    sum
+}
+
+// ln 2 = 2·atanh(1/3)
+fn ln_2(work: DecimalContext) -> BigDecimal {
+    let third = BigDecimal::from_i128_with(1, work).div_with(&BigDecimal::from_i128_with(3, work), work);
+    atanh(&third, work).mul_with(&BigDecimal::from_i128_with(2, work), work)
+}
+
+// ln 10 = 3·ln 2 + ln 1.25, with ln 1.25 = 2·atanh(1/9)
+fn ln_10(work: DecimalContext) -> BigDecimal {
+    let ninth = BigDecimal::from_i128_with(1, work).div_with(&BigDecimal::from_i128_with(9, work), work);
+    let ln_1_25 = atanh(&ninth, work).mul_with(&BigDecimal::from_i128_with(2, work), work);
+    ln_2(work).mul_with(&BigDecimal::from_i128_with(3, work), work).add_with(&ln_1_25, work)
+}
+
+/// `a += b` for equal-length digit vectors; the sum must fit.
+fn add_digits(a: &mut [u8], b: &[u8]) {
+    let mut carry = 0;
+    for (a, &b) in a.iter_mut().zip(b).rev() {
+        let sum = *a + b + carry;
+        *a = sum % 10;
+        carry = sum / 10;
+    }
+}
+
+/// `a -= b` for equal-length digit vectors with a >= b.
+fn subtract_digits(a: &mut [u8], b: &[u8]) {
+    let mut borrow = 0;
+    for (a, &b) in a.iter_mut().zip(b).rev() {
+        let subtrahend = b + borrow;
+        borrow = (*a < subtrahend) as u8;
+        *a = *a + 10 * borrow - subtrahend;
+    }
+}
+
+/// Compares digit vectors as integers, ignoring leading zeros.
+fn compare_digits(a: &[u8], b: &[u8]) -> Ordering {
+    let a = &a[a.iter().take_while(|&&digit| digit == 0).count()..];
+    let b = &b[b.iter().take_while(|&&digit| digit == 0).count()..];
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// `a -= b` for digit vectors aligned at their last digit, with a >= b.
+fn subtract_unaligned(a: &mut Vec<u8>, b: &[u8]) {
+    let mut aligned = vec![0; a.len() - b.len().min(a.len())];
+    aligned.extend_from_slice(&b[b.len() - b.len().min(a.len())..]);
+    subtract_digits(a, &aligned);
+    let leading = a.iter().take_while(|&&digit| digit == 0).count();
+    a.drain(..leading);
+}
+
+impl Add for BigDecimal {
+    type Output = BigDecimal;
+
+    fn add(self, other: BigDecimal) -> BigDecimal {
+        self.add_with(&other, self.context.combine(other.context))
+    }
+}
+
+impl Sub for BigDecimal {
+    type Output = BigDecimal;
+
+    fn sub(self, other: BigDecimal) -> BigDecimal {
+        self.sub_with(&other, self.context.combine(other.context))
+    }
+}
+
+impl Mul for BigDecimal {
+    type Output = BigDecimal;
+
+    fn mul(self, other: BigDecimal) -> BigDecimal {
+        self.mul_with(&other, self.context.combine(other.context))
+    }
+}
+
+impl Div for BigDecimal {
+    type Output = BigDecimal;
+
+    fn div(self, other: BigDecimal) -> BigDecimal {
+        self.div_with(&other, self.context.combine(other.context))
+    }
+}
+
+impl Neg for BigDecimal {
+    type Output = BigDecimal;
+
+    fn neg(self) -> BigDecimal {
+        if self.is_nan() {
+            return self;
+        }
+        BigDecimal { negative: !self.negative, ..self }
+    }
+}
+
+impl Neg for &BigDecimal {
+    type Output = BigDecimal;
+
+    fn neg(self) -> BigDecimal {
+        -self.clone()
+    }
+}
+
+/// A primitive operand, exact and in the context of the other operand, so
+/// that it does not change the precision or rounding of the result.
+fn in_context(value: impl Into<BigDecimal>, other: &BigDecimal) -> BigDecimal {
+    BigDecimal { context: other.context, ..value.into() }
+}
+
+impl_forwarded_ops!(BigDecimal, in_context);
+
+// Numeric equality: the context is not part of the value, and -0 == +0
+impl PartialEq for BigDecimal {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.category, other.category) {
+            (Category::Normal, Category::Normal) => {
+                self.negative == other.negative
+                    && self.exponent == other.exponent
+                    && self.digits == other.digits
+            }
+            _ => self.stand_in() == other.stand_in(),
+        }
+    }
+}
+
+impl PartialOrd for BigDecimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.category != Category::Normal || other.category != Category::Normal {
+            return self.stand_in().partial_cmp(&other.stand_in());
+        }
+        match (self.negative, other.negative) {
+            (false, true) => return Some(Ordering::Greater),
+            (true, false) => return Some(Ordering::Less),
+            _ => {}
+        }
+
+        // Same sign: the exponent, then the digits, decide the magnitude
+        let magnitude = self
+            .exponent
+            .cmp(&other.exponent)
+            .then_with(|| self.digits.cmp(&other.digits));
+        Some(if self.negative { magnitude.reverse() } else { magnitude })
+    }
+}
+
+impl Default for BigDecimal {
+    fn default() -> Self {
+        special(0.0, DecimalContext::default())
+    }
+}
+
+impl From<BigFloat> for BigDecimal {
+    fn from(value: BigFloat) -> Self {
+        from_bigfloat(&value, DecimalContext::default())
+    }
+}
+
+impl From<BigDecimal> for BigFloat {
+    fn from(value: BigDecimal) -> Self {
+        value.to_bigfloat()
+    }
+}
+
+impl From<f64> for BigDecimal {
+    fn from(value: f64) -> Self {
+        BigDecimal::new(value, 0)
+    }
+}
+
+impl From<i32> for BigDecimal {
+    fn from(value: i32) -> Self {
+        BigDecimal::from(value as i128)
+    }
+}
+
+impl From<i64> for BigDecimal {
+    fn from(value: i64) -> Self {
+        BigDecimal::from(value as i128)
+    }
+}
+
+impl From<i128> for BigDecimal {
+    fn from(value: i128) -> Self {
+        BigDecimal::from_i128_with(value, DecimalContext::default())
+    }
+}
+
+impl fmt::Display for BigDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.category != Category::Normal {
+            return write!(f, "{}", BigFloat::from_f64(self.stand_in()));
+        }
+        let digits: String = self.digits.iter().map(|&digit| char::from(b'0' + digit)).collect();
+        write_digits(f, self.negative, &digits, &self.exponent)
+    }
+}
+
+impl FromStr for BigDecimal {
+    type Err = BigFloatError;
+
+    /// Parsing never rounds: the precision is the default or the number of
+    /// significant digits written, whichever is larger. Special values and
+    /// nested exponents are left to the `BigFloat` parser.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_digits(s.trim()) {
+            Some((negative, digits, exponent)) => {
+                let significant = digits.iter().skip_while(|&&digit| digit == 0).count();
+                let context = DecimalContext::new(
+                    significant.max(DecimalContext::DEFAULT_PRECISION),
+                    RoundingMode::NearestEven,
+                );
+                Ok(finish(negative, digits, Exponent::from(exponent), false, context))
+            }
+            None => s.parse::<BigFloat>().map(BigDecimal::from),
+        }
+    }
+}
+
+/// Splits `[+-]digits[.digits][e[+-]digits]` into its sign, its digits and
+/// the exponent of the first digit.
+fn parse_digits(s: &str) -> Option<(bool, Vec<u8>, i128)> {
+    let (negative, s) = match s.as_bytes().first()? {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(position) => (&s[..position], s[position + 1..].parse::<i128>().ok()?),
+        None => (s, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let mut digits = Vec::with_capacity(integer.len() + fraction.len());
+    for byte in integer.bytes().chain(fraction.bytes()) {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        digits.push(byte - b'0');
+    }
+    let exponent = exponent.checked_add(integer.len() as i128 - 1)?;
+    Some((negative, digits, exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(s: &str) -> BigDecimal {
+        s.parse().unwrap()
+    }
+
+    fn context(precision: usize, rounding: RoundingMode) -> DecimalContext {
+        DecimalContext::new(precision, rounding)
+    }
+
+    #[test]
+    fn test_parse_keeps_every_digit() {
+        let value = decimal("-00123.4567890123456789012345678901234567890e-3");
+        assert_eq!(value.to_string(), "-0.123456789012345678901234567890123456789");
+        assert_eq!(value.context().precision(), 40);
+        assert_eq!(decimal("1.500").digits(), &[1, 5]);
+        assert_eq!(decimal("0.000").to_string(), "0");
+        assert_eq!(decimal("-0").to_string(), "-0");
+        assert_eq!(decimal("2.5e1000").to_string(), "2.5e1000");
+        assert_eq!(decimal("1e1e100").to_string(), "1e1e100");
+        assert!(decimal("-inf").is_infinite());
+        assert_eq!("1.2x".parse::<BigDecimal>(), Err(BigFloatError::InvalidMantissa { offset: 0 }));
+    }
+
+    #[test]
+    fn test_decimal_fractions_are_exact() {
+        let sum = decimal("0.1") + decimal("0.2");
+        assert_eq!(sum, decimal("0.3"));
+        assert_eq!(sum.to_string(), "0.3");
+
+        let mut total = BigDecimal::from(0);
+        for _ in 0..1000 {
+            total += decimal("0.01");
+        }
+        assert_eq!(total, BigDecimal::from(10));
+        assert_eq!((decimal("1e30") + 1_i64 - decimal("1e30")).to_string(), "1");
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let cases = [
+            (RoundingMode::NearestEven, "2.5", "2"),
+            (RoundingMode::NearestEven, "3.5", "4"),
+            (RoundingMode::NearestAway, "2.5", "3"),
+            (RoundingMode::NearestAway, "-2.5", "-3"),
+            (RoundingMode::TowardZero, "-2.9", "-2"),
+            (RoundingMode::AwayFromZero, "2.1", "3"),
+            (RoundingMode::Up, "-2.9", "-2"),
+            (RoundingMode::Up, "2.1", "3"),
+            (RoundingMode::Down, "2.9", "2"),
+            (RoundingMode::Down, "-2.1", "-3"),
+            (RoundingMode::NearestEven, "9.96", "10"),
+        ];
+        for (mode, input, expected) in cases {
+            let rounded = BigDecimal::parse_with(input, context(1, mode)).unwrap();
+            assert_eq!(rounded.round_to(context(2, mode)).to_string(), expected, "{:?} {}", mode, input);
+        }
+    }
+
+    #[test]
+    fn test_directed_rounding_sees_tiny_operands() {
+        let one = BigDecimal::from(1).round_to(context(5, RoundingMode::Up));
+        let tiny = decimal("1e-100").round_to(one.context());
+        assert_eq!((&one + &tiny).to_string(), "1.0001");
+        assert_eq!((&one - &tiny).to_string(), "1");
+
+        let one = one.round_to(context(5, RoundingMode::Down));
+        assert_eq!((&one - &tiny).to_string(), "0.99999");
+        assert!((&one - &one).is_sign_negative());
+    }
+
+    #[test]
+    fn test_division() {
+        let third = BigDecimal::from(1).round_to(context(50, RoundingMode::NearestEven)) / 3_i64;
+        assert_eq!(third.to_string(), format!("0.{}", "3".repeat(50)));
+        let two_thirds = BigDecimal::from(2).round_to(context(10, RoundingMode::TowardZero)) / 3_i64;
+        assert_eq!(two_thirds.to_string(), "0.6666666666");
+        assert_eq!((BigDecimal::from(1) / 8_i64).to_string(), "0.125");
+        assert_eq!((decimal("1e1000") / decimal("4e-1000")).to_string(), "2.5e1999");
+        assert!((BigDecimal::from(1) / BigDecimal::from(0)).is_infinite());
+        assert!((BigDecimal::from(0) / BigDecimal::from(0)).is_nan());
+    }
+
+    #[test]
+    fn test_multiplication_and_powers() {
+        let wide = context(40, RoundingMode::NearestEven);
+        let product = decimal("123456789.123456789").round_to(wide) * decimal("987654321.987654321");
+        assert_eq!(product.to_string(), "1.21932631356500531347203169112635269e17");
+        let narrow = decimal("123456789.123456789") * decimal("987654321.987654321");
+        assert_eq!(narrow.to_string(), "1.219326313565005313472031691126353e17");
+        assert_eq!(BigDecimal::from(2).powi(100).to_string(), "1.267650600228229401496703205376e30");
+        assert_eq!(BigDecimal::from(-2).pow(&BigDecimal::from(3)), BigDecimal::from(-8));
+        assert_eq!(BigDecimal::from(2).powi(-3).to_string(), "0.125");
+        assert!(BigDecimal::from(-8).pow(&decimal("0.5")).is_nan());
+    }
+
+    #[test]
+    fn test_math_functions() {
+        let work = context(50, RoundingMode::NearestEven);
+        let two = BigDecimal::from(2).round_to(work);
+        assert_eq!(two.sqrt().to_string(), "1.4142135623730950488016887242096980785696718753769");
+        assert_eq!(two.ln().to_string(), "0.69314718055994530941723212145817656807550013436026");
+        assert_eq!(BigDecimal::from(1).round_to(work).exp().to_string(), "2.7182818284590452353602874713526624977572470937");
+        assert_eq!(BigDecimal::from(10).round_to(work).ln().to_string(), "2.3025850929940456840179914546843642076011014886288");
+        assert_eq!(two.pow(&decimal("0.5")).to_string(), "1.4142135623730950488016887242096980785696718753769");
+        assert_eq!(decimal("1e-300").log10().to_string(), "-300");
+        assert_eq!(decimal("6.25e100").sqrt().to_string(), "2.5e50");
+        assert_eq!(decimal("-1000").round_to(work).exp().to_string(), "5.0759588975494567652918094795743369193055992828928e-435");
+    }
+
+    #[test]
+    fn test_exp_of_wide_arguments() {
+        assert_eq!(decimal("1e35").exp().to_string(), "1.696054098052084592084547616052809e43429448190325182765112891891660508");
+        assert_eq!(decimal("-1e35").exp().to_string(), "5.896038346586340577801074286176669e-43429448190325182765112891891660509");
+        assert_eq!(decimal("9.9e37").exp().to_string(), "1.397344772505366119597959202085777e42995153708421930937461762972743903147");
+    }
+
+    #[test]
+    fn test_context_keeps_one_digit() {
+        let context = DecimalContext::new(0, RoundingMode::NearestEven);
+        assert_eq!(context.precision(), 1);
+        let five = BigDecimal::from(5).round_to(context);
+        assert_eq!(five.to_bigfloat(), BigFloat::from(5.0));
+        assert_eq!(decimal("2.5").round_to(context).to_string(), "2");
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert!(decimal("1.0000000000000000000000000000000000001") > BigDecimal::from(1));
+        assert!(decimal("-1e10") < decimal("-9.99e9"));
+        assert!(decimal("1e-5") > BigDecimal::from(0));
+        assert_eq!(decimal("-0"), decimal("0"));
+        assert!(BigDecimal::from(f64::NAN).partial_cmp(&BigDecimal::from(1)).is_none());
+    }
+
+    #[test]
+    fn test_bigfloat_conversions() {
+        assert_eq!(BigDecimal::from(0.1).to_string(), "0.1");
+        assert_eq!(BigDecimal::from(BigFloat::new(1.25, 1000)).to_string(), "1.25e1000");
+        assert_eq!(decimal("0.3").to_bigfloat(), BigFloat::from_f64(0.3));
+        assert_eq!(decimal("0.3").to_f64(), Some(0.3));
+        let nested = BigFloat::new(1.0, BigFloat::new(1.0, 100));
+        assert_eq!(BigFloat::from(BigDecimal::from(nested.clone())), nested);
+    }
+}
@@ -0,0 +1,639 @@
+use crate::bigfloat::BigFloat;
+use crate::bigfloat_dd::BigFloatDD;
+use crate::double_double::two_product;
+use crate::exponent::Exponent;
+use crate::powers_of_ten::exact;
+use crate::rounding::RoundingMode::{self, Down, Up};
+use std::cmp::Ordering;
+use std::f64::consts::{LN_10, LOG10_E};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// Arguments of `exp` below this magnitude are evaluated in double-double;
+// past it the power of ten is bounded through its integer part
+const EXP_ARGUMENT_DIGITS: i128 = 14;
+
+/// A closed interval [lo, hi] of `BigFloat` values that is guaranteed to
+/// contain the exact result of the computation that produced it.
+///
+/// Every operation rounds its lower bound down and its upper bound up:
+/// `+ - * /` and `sqrt` through the directed rounding modes of `BigFloat`,
+/// and `ln`, `exp` and `pow` by widening a double-double evaluation by a
+/// bound on its error. Comparisons report an ordering only when it holds
+/// for every pair of values in the two intervals, so an ordering that is an
+/// artifact of rounding shows up as uncertain rather than as a wrong
+/// answer. An undefined result, such as the square root of an interval
+/// that is entirely negative, has NaN bounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BigInterval {
+    lo: BigFloat,
+    hi: BigFloat,
+}
+
+impl BigInterval {
+    /// The interval between `a` and `b`, in either order; NaN bounds if
+    /// either is NaN.
+    pub fn new(a: BigFloat, b: BigFloat) -> Self {
+        if a.is_nan() || b.is_nan() {
+            return BigInterval::nan();
+        }
+        if a <= b { BigInterval { lo: a, hi: b } } else { BigInterval { lo: b, hi: a } }
+    }
+
+    /// The interval holding exactly `value`.
+    pub fn point(value: BigFloat) -> Self {
+        BigInterval { lo: value.clone(), hi: value }
+    }
+
+    /// The smallest interval containing the f64 `value`: a single point
+    /// when its decimal mantissa is exact, the two neighbours of the
+    /// nearest `BigFloat` otherwise.
+    pub fn from_f64(value: f64) -> Self {
+        let nearest = BigFloat::from_f64(value);
+        if nearest.is_zero() || !nearest.is_finite() || is_exact(value, &nearest) {
+            return BigInterval::point(nearest);
+        }
+        BigInterval { lo: step(&nearest, false), hi: step(&nearest, true) }
+    }
+
+    /// The whole extended real line, [-∞, +∞].
+    pub fn entire() -> Self {
+        BigInterval {
+            lo: BigFloat::from_f64(f64::NEG_INFINITY),
+            hi: BigFloat::from_f64(f64::INFINITY),
+        }
+    }
+
+    /// The lower bound.
+    pub fn lo(&self) -> &BigFloat {
+        &self.lo
+    }
+
+    /// The upper bound.
+    pub fn hi(&self) -> &BigFloat {
+        &self.hi
+    }
+
+    fn nan() -> Self {
+        BigInterval::point(BigFloat::from_f64(f64::NAN))
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.lo.is_nan() || self.hi.is_nan()
+    }
+
+    pub fn is_point(&self) -> bool {
+        self.lo == self.hi
+    }
+
+    /// hi - lo, rounded up.
+    pub fn width(&self) -> BigFloat {
+        self.hi.sub_round(&self.lo, Up)
+    }
+
+    /// The value halfway between the bounds, rounded to nearest.
+    pub fn midpoint(&self) -> BigFloat {
+        (&self.lo + &self.hi) / BigFloat::from_f64(2.0)
+    }
+
+    pub fn contains(&self, value: &BigFloat) -> bool {
+        self.lo <= *value && *value <= self.hi
+    }
+
+    /// Whether every value of `other` lies in `self`.
+    pub fn encloses(&self, other: &BigInterval) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
+
+    /// The values in both intervals, or `None` if they are disjoint.
+    pub fn intersection(&self, other: &BigInterval) -> Option<BigInterval> {
+        let lo = max(&self.lo, &other.lo);
+        let hi = min(&self.hi, &other.hi);
+        (lo <= hi).then_some(BigInterval { lo, hi })
+    }
+
+    /// The smallest interval containing both.
+    pub fn hull(&self, other: &BigInterval) -> BigInterval {
+        if self.is_nan() || other.is_nan() {
+            return BigInterval::nan();
+        }
+        BigInterval { lo: min(&self.lo, &other.lo), hi: max(&self.hi, &other.hi) }
+    }
+
+    /// The ordering of every value in `self` against every value in
+    /// `other`, or `None` when it is uncertain: the intervals overlap (and
+    /// are not the same single point) or either is NaN.
+    pub fn compare(&self, other: &BigInterval) -> Option<Ordering> {
+        if self.hi < other.lo {
+            Some(Ordering::Less)
+        } else if self.lo > other.hi {
+            Some(Ordering::Greater)
+        } else if self.is_point() && other.is_point() && self.lo == other.lo {
+            Some(Ordering::Equal)
+        } else {
+            None
+        }
+    }
+
+    /// Whether every value in `self` is below every value in `other`.
+    pub fn certainly_lt(&self, other: &BigInterval) -> bool {
+        self.hi < other.lo
+    }
+
+    pub fn certainly_le(&self, other: &BigInterval) -> bool {
+        self.hi <= other.lo
+    }
+
+    pub fn certainly_gt(&self, other: &BigInterval) -> bool {
+        self.lo > other.hi
+    }
+
+    pub fn certainly_ge(&self, other: &BigInterval) -> bool {
+        self.lo >= other.hi
+    }
+
+    /// The square root, over the part of the interval that is not negative.
+    pub fn sqrt(&self) -> BigInterval {
+        if self.is_nan() || self.hi < BigFloat::from_f64(0.0) {
+            return BigInterval::nan();
+        }
+        let lo = max(&self.lo, &BigFloat::from_f64(0.0));
+        BigInterval { lo: lo.sqrt_round(Down), hi: self.hi.sqrt_round(Up) }
+    }
+
+    /// Natural logarithm, over the part of the interval that is not
+    /// negative; a lower bound of zero gives -∞.
+    pub fn ln(&self) -> BigInterval {
+        if self.is_nan() || self.hi < BigFloat::from_f64(0.0) {
+            return BigInterval::nan();
+        }
+        let lo = max(&self.lo, &BigFloat::from_f64(0.0));
+        BigInterval { lo: ln_bounds(&lo).lo, hi: ln_bounds(&self.hi).hi }
+    }
+
+    pub fn exp(&self) -> BigInterval {
+        if self.is_nan() {
+            return BigInterval::nan();
+        }
+        BigInterval { lo: exp_bounds(&self.lo).lo, hi: exp_bounds(&self.hi).hi }
+    }
+
+    /// self^exponent. An exponent that is exactly one integer uses `powi`,
+    /// so negative bases work; otherwise the result is e^(exponent · ln self)
+    /// over the part of `self` that is not negative.
+    pub fn pow(&self, exponent: &BigInterval) -> BigInterval {
+        if self.is_nan() || exponent.is_nan() {
+            return BigInterval::nan();
+        }
+        let integer = exponent.lo.to_f64().filter(|n| n.fract() == 0.0 && n.abs() <= 2f64.powi(62));
+        if let Some(n) = integer.filter(|&n| exponent.is_point() && is_exact(n, &exponent.lo)) {
+            return self.powi(n as i64);
+        }
+        (exponent * self.ln()).exp()
+    }
+
+    /// self^n by binary exponentiation of the bounds, rounding each
+    /// product outward.
+    pub fn powi(&self, n: i64) -> BigInterval {
+        if n == 0 {
+            return BigInterval::point(BigFloat::from_f64(1.0));
+        }
+        if self.is_nan() {
+            return BigInterval::nan();
+        }
+        if n < 0 {
+            return BigInterval::point(BigFloat::from_f64(1.0)) / self.powi_magnitude(n.unsigned_abs());
+        }
+        self.powi_magnitude(n as u64)
+    }
+
+    fn powi_magnitude(&self, n: u64) -> BigInterval {
+        let zero = BigFloat::from_f64(0.0);
+        if n % 2 == 1 || self.lo >= zero {
+            // Monotone increasing
+            BigInterval { lo: power(&self.lo, n, Down), hi: power(&self.hi, n, Up) }
+        } else if self.hi <= zero {
+            BigInterval { lo: power(&self.hi, n, Down), hi: power(&self.lo, n, Up) }
+        } else {
+            let hi = max(&power(&self.lo, n, Up), &power(&self.hi, n, Up));
+            BigInterval { lo: zero, hi }
+        }
+    }
+}
+
+/// Whether `nearest` is exactly the f64 `value`, checked with an exact
+/// product against a power of ten that is itself exact.
+fn is_exact(value: f64, nearest: &BigFloat) -> bool {
+    let mantissa = nearest.mantissa();
+    let Some(exponent) = nearest.exponent_ref().as_i128() else {
+        return false;
+    };
+    match exact(exponent.unsigned_abs()) {
+        Some(power) if exponent >= 0 => two_product(mantissa, power) == (value, 0.0),
+        Some(power) => two_product(value, power) == (mantissa, 0.0),
+        None => false,
+    }
+}
+
+/// The neighbouring `BigFloat` above or below a finite non-zero `value`.
+fn step(value: &BigFloat, up: bool) -> BigFloat {
+    let mantissa = value.mantissa();
+    let exponent = value.exponent();
+    if mantissa.abs() == 1.0 && (mantissa > 0.0) != up {
+        // Toward zero from a power of ten: the largest mantissa a decade down
+        return BigFloat::new(mantissa * 10.0_f64.next_down(), exponent - Exponent::Long(1));
+    }
+    let next = if up { mantissa.next_up() } else { mantissa.next_down() };
+    // A mantissa that steps onto ±10 normalizes exactly to the next decade
+    BigFloat::new(next, exponent)
+}
+
+/// [value - margin, value + margin], rounded outward.
+fn around(value: &BigFloat, margin: &BigFloat) -> BigInterval {
+    BigInterval { lo: value.sub_round(margin, Down), hi: value.add_round(margin, Up) }
+}
+
+/// An interval around the constant whose correctly rounded f64 is `value`.
+fn constant(value: f64) -> BigInterval {
+    BigInterval::from_f64(value.next_down()).hull(&BigInterval::from_f64(value.next_up()))
+}
+
+/// Bounds on ln(value) for a value that is not negative.
+fn ln_bounds(value: &BigFloat) -> BigInterval {
+    if value.is_zero() || value.is_infinite() {
+        return BigInterval::point(value.ln());
+    }
+    if *value == BigFloat::from_f64(1.0) {
+        return BigInterval::point(BigFloat::from_f64(0.0));
+    }
+    match value.exponent_ref().as_i128() {
+        Some(exponent) => {
+            // ln m + exponent · ln 10 in double-double is off by far less
+            // than 2^-90 of its terms; the margin adds two ulps of the result
+            let ln = BigFloatDD::from(value.clone()).ln().to_bigfloat();
+            let terms = (exponent.unsigned_abs() as f64 + 1.0) * LN_10;
+            let margin = ln.abs().to_f64_saturating() * 2f64.powi(-51) + terms * 2f64.powi(-90);
+            around(&ln, &BigFloat::from_f64(margin))
+        }
+        None => {
+            // exponent · ln 10 + ln m, with ln m in [0, ln 10)
+            let exponent = BigInterval::point(value.exponent_ref().to_bigfloat());
+            let ln_10 = constant(LN_10);
+            let ln_mantissa = BigInterval { lo: BigFloat::from_f64(0.0), hi: ln_10.hi.clone() };
+            exponent * ln_10 + ln_mantissa
+        }
+    }
+}
+
+/// Bounds on e^value.
+fn exp_bounds(value: &BigFloat) -> BigInterval {
+    if value.is_zero() || value.is_infinite() {
+        return BigInterval::point(value.exp());
+    }
+    if value.abs() < BigFloat::new(1.0, EXP_ARGUMENT_DIGITS) {
+        // The double-double power value · log10 e is off by under 2^-100
+        // of itself, which moves the result by under |value| · 2^-100
+        let exp = BigFloatDD::from(value.clone()).exp().to_bigfloat();
+        let factor = 2f64.powi(-51) + value.abs().to_f64_saturating() * 2f64.powi(-96);
+        let margin = exp.abs().mul_round(&BigFloat::from_f64(factor), Up);
+        return around(&exp, &margin);
+    }
+    // e^value = 10^power lies between the powers of ten around it; the
+    // power's own uncertainty is more than a decade here
+    let power = BigInterval::point(value.clone()) * constant(LOG10_E);
+    BigInterval {
+        lo: BigFloat::new(1.0, Exponent::from_bigfloat(power.lo)),
+        hi: BigFloat::new(1.0, Exponent::from_bigfloat(power.hi) + Exponent::Long(1)),
+    }
+}
+
+/// value^n rounded in `direction` (`Up` or `Down`).
+fn power(value: &BigFloat, n: u64, direction: RoundingMode) -> BigFloat {
+    // A negative result is rounded by rounding its magnitude the other way
+    let negative = value.is_sign_negative() && n % 2 == 1;
+    let mode = match (direction, negative) {
+        (Up, false) | (Down, true) => Up,
+        _ => Down,
+    };
+    let mut base = value.abs();
+    let mut remaining = n;
+    let mut result = BigFloat::from_f64(1.0);
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            result = result.mul_round(&base, mode);
+        }
+        remaining >>= 1;
+        if remaining > 0 {
+            base = base.mul_round(&base, mode);
+        }
+    }
+    if negative { -result } else { result }
+}
+
+/// a · b rounded in `mode`, taking 0 · ∞ as 0 so that unbounded intervals
+/// still multiply.
+fn product(a: &BigFloat, b: &BigFloat, mode: RoundingMode) -> BigFloat {
+    if a.is_zero() || b.is_zero() {
+        return BigFloat::from_f64(0.0);
+    }
+    a.mul_round(b, mode)
+}
+
+fn min(a: &BigFloat, b: &BigFloat) -> BigFloat {
+    if b < a { b.clone() } else { a.clone() }
+}
+
+fn max(a: &BigFloat, b: &BigFloat) -> BigFloat {
+    if b > a { b.clone() } else { a.clone() }
+}
+
+/// The least of the lower and the greatest of the upper candidates, or a
+/// NaN interval if there are none.
+fn bounds(candidates: impl Iterator<Item = (BigFloat, BigFloat)>) -> BigInterval {
+    candidates
+        .filter(|(lo, hi)| !lo.is_nan() && !hi.is_nan())
+        .map(|(lo, hi)| BigInterval { lo, hi })
+        .reduce(|a, b| BigInterval { lo: min(&a.lo, &b.lo), hi: max(&a.hi, &b.hi) })
+        .unwrap_or_else(BigInterval::nan)
+}
+
+impl Add for BigInterval {
+    type Output = BigInterval;
+
+    fn add(self, other: BigInterval) -> BigInterval {
+        BigInterval { lo: self.lo.add_round(&other.lo, Down), hi: self.hi.add_round(&other.hi, Up) }
+    }
+}
+
+impl Sub for BigInterval {
+    type Output = BigInterval;
+
+    fn sub(self, other: BigInterval) -> BigInterval {
+        BigInterval { lo: self.lo.sub_round(&other.hi, Down), hi: self.hi.sub_round(&other.lo, Up) }
+    }
+}
+
+impl Mul for BigInterval {
+    type Output = BigInterval;
+
+    fn mul(self, other: BigInterval) -> BigInterval {
+        if self.is_nan() || other.is_nan() {
+            return BigInterval::nan();
+        }
+        let corners = [(&self.lo, &other.lo), (&self.lo, &other.hi), (&self.hi, &other.lo), (&self.hi, &other.hi)];
+        bounds(corners.into_iter().map(|(a, b)| (product(a, b, Down), product(a, b, Up))))
+    }
+}
+
+impl Div for BigInterval {
+    type Output = BigInterval;
+
+    /// A divisor that contains zero gives the entire line, or NaN bounds
+    /// when it is zero itself.
+    fn div(self, other: BigInterval) -> BigInterval {
+        if self.is_nan() || other.is_nan() {
+            return BigInterval::nan();
+        }
+        let zero = BigFloat::from_f64(0.0);
+        if other.contains(&zero) {
+            return if other.is_point() { BigInterval::nan() } else { BigInterval::entire() };
+        }
+        // ∞/∞ corners drop out: the other corners already bound the result
+        let corners = [(&self.lo, &other.lo), (&self.lo, &other.hi), (&self.hi, &other.lo), (&self.hi, &other.hi)];
+        bounds(corners.into_iter().map(|(a, b)| (a.div_round(b, Down), a.div_round(b, Up))))
+    }
+}
+
+impl Neg for BigInterval {
+    type Output = BigInterval;
+
+    fn neg(self) -> BigInterval {
+        BigInterval { lo: -self.hi, hi: -self.lo }
+    }
+}
+
+impl Neg for &BigInterval {
+    type Output = BigInterval;
+
+    fn neg(self) -> BigInterval {
+        -self.clone()
+    }
+}
+
+// Borrowed operands and the assignment operators forward to the owned
+// implementations above
+macro_rules! impl_interval_ops {
+    ($($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident);*) => {
+        $(
+            impl $trait<&BigInterval> for BigInterval {
+                type Output = BigInterval;
+
+                fn $method(self, other: &BigInterval) -> BigInterval {
+                    self.$method(other.clone())
+                }
+            }
+
+            impl $trait<BigInterval> for &BigInterval {
+                type Output = BigInterval;
+
+                fn $method(self, other: BigInterval) -> BigInterval {
+                    self.clone().$method(other)
+                }
+            }
+
+            impl $trait<&BigInterval> for &BigInterval {
+                type Output = BigInterval;
+
+                fn $method(self, other: &BigInterval) -> BigInterval {
+                    self.clone().$method(other.clone())
+                }
+            }
+
+            impl $assign_trait for BigInterval {
+                fn $assign_method(&mut self, other: BigInterval) {
+                    *self = (&*self).$method(other);
+                }
+            }
+
+            impl $assign_trait<&BigInterval> for BigInterval {
+                fn $assign_method(&mut self, other: &BigInterval) {
+                    *self = (&*self).$method(other);
+                }
+            }
+        )*
+    };
+}
+
+impl_interval_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign
+);
+
+impl From<BigFloat> for BigInterval {
+    fn from(value: BigFloat) -> Self {
+        BigInterval::point(value)
+    }
+}
+
+impl From<f64> for BigInterval {
+    fn from(value: f64) -> Self {
+        BigInterval::from_f64(value)
+    }
+}
+
+impl fmt::Display for BigInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{mantissa, XorShift};
+
+    /// Whether the interval contains a value given to ~32 digits.
+    fn encloses(interval: &BigInterval, digits: &str) -> bool {
+        let value: BigFloatDD = digits.parse().unwrap();
+        BigFloatDD::from(interval.lo.clone()) < value && value < BigFloatDD::from(interval.hi.clone())
+    }
+
+    fn sample_values(count: usize) -> Vec<BigFloat> {
+        let mut rng = XorShift::new(0x9e37_79b9_7f4a_7c15);
+        (0..count)
+            .map(|_| {
+                let bits = rng.next_u64();
+                BigFloat::new(mantissa(bits), (bits % 61) as i128 - 30)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_arithmetic_is_one_ulp_wide() {
+        let values = sample_values(2_000);
+        for pair in values.chunks(2) {
+            let (a, b) = (BigInterval::point(pair[0].clone()), BigInterval::point(pair[1].clone()));
+            let (x, y) = (BigFloatDD::from(pair[0].clone()), BigFloatDD::from(pair[1].clone()));
+            let results = [(&a + &b, &x + &y), (&a - &b, &x - &y), (&a * &b, &x * &y), (&a / &b, &x / &y)];
+            for (interval, reference) in results {
+                assert!(BigFloatDD::from(interval.lo.clone()) <= reference, "{}", interval);
+                assert!(reference <= BigFloatDD::from(interval.hi.clone()), "{}", interval);
+                assert!(interval.is_point() || step(&interval.lo, true) == interval.hi, "{}", interval);
+            }
+        }
+    }
+
+    #[test]
+    fn test_functions_enclose_exact_values() {
+        let one = BigInterval::from_f64(1.0);
+        let two = BigInterval::from_f64(2.0);
+        assert!(encloses(&one.exp(), "2.7182818284590452353602874713527"));
+        assert!(encloses(&two.ln(), "0.69314718055994530941723212145818"));
+        assert!(encloses(&two.sqrt(), "1.4142135623730950488016887242097"));
+        assert!(encloses(&two.pow(&BigInterval::from_f64(0.5)), "1.4142135623730950488016887242097"));
+        assert!(encloses(&BigInterval::from_f64(1e100).ln(), "230.25850929940456840179914546844"));
+        let googolplex: BigFloat = "1e1e100".parse().unwrap();
+        assert!(encloses(&BigInterval::point(googolplex).ln(), "2.3025850929940456840179914546844e100"));
+
+        // Just below 1, ln m - ln 10 cancels to 1e-16, but the bounds stay
+        // ten decades tighter than the f64 rounding of ln 10
+        let below_one = BigInterval::point(BigFloat::new(10.0_f64.next_down(), -1)).ln();
+        assert!(encloses(&below_one, "-1.7763568394002506224499917131477e-16"));
+        assert!(below_one.width() < BigFloat::new(1.0, -25));
+
+        assert_eq!(one.ln(), BigInterval::from_f64(0.0));
+        assert_eq!(BigInterval::from_f64(0.0).exp(), one);
+        for value in sample_values(500) {
+            let x = BigInterval::point(value.abs());
+            assert!(x.ln().exp().encloses(&x), "{}", x);
+            assert!(x.ln().width() <= x.ln().midpoint().abs() * BigFloat::from_f64(1e-14) + BigFloat::new(1.0, -25));
+        }
+    }
+
+    #[test]
+    fn test_exp_of_huge_arguments() {
+        // e^1e20 = 10^43429448190325182765.11..., and the power is only
+        // known to a few thousand at this magnitude
+        let result = BigInterval::from_f64(1e20).exp();
+        let power = BigFloat::from_f64(43429448190325182765.0);
+        assert!(result.lo <= BigFloat::new(1.0, Exponent::from_bigfloat(power.clone())));
+        assert!(result.hi > BigFloat::new(1.0, Exponent::from_bigfloat(power)));
+        let reciprocal = BigInterval::from_f64(-1e20).exp();
+        assert!(reciprocal.lo <= BigFloat::new(1.0, -43429448190325182766_i128));
+        assert!(reciprocal.hi >= BigFloat::new(1.0, -43429448190325182765_i128));
+
+        let large = BigInterval::from_f64(1e5).exp();
+        assert!(large.lo.exponent() == 43429 && large.hi.exponent() == 43429);
+        assert!(large.width() < large.lo.mul_round(&BigFloat::new(1.0, -14), Up));
+    }
+
+    #[test]
+    fn test_domains() {
+        let negative = BigInterval::new(BigFloat::from_f64(-3.0), BigFloat::from_f64(-2.0));
+        assert!(negative.sqrt().is_nan() && negative.ln().is_nan());
+        assert_eq!(negative.powi(2), BigInterval::new(BigFloat::from_f64(4.0), BigFloat::from_f64(9.0)));
+        assert_eq!(negative.powi(3), BigInterval::new(BigFloat::from_f64(-27.0), BigFloat::from_f64(-8.0)));
+        assert_eq!(negative.pow(&BigInterval::from_f64(2.0)), negative.powi(2));
+        assert!(negative.pow(&BigInterval::from_f64(0.5)).is_nan());
+
+        let straddle = BigInterval::new(BigFloat::from_f64(-1.0), BigFloat::from_f64(4.0));
+        assert_eq!(straddle.sqrt(), BigInterval::new(BigFloat::from_f64(0.0), BigFloat::from_f64(2.0)));
+        assert_eq!(straddle.powi(2), BigInterval::new(BigFloat::from_f64(0.0), BigFloat::from_f64(16.0)));
+        assert!(straddle.ln().lo.is_infinite() && straddle.ln().hi < BigFloat::from_f64(1.39));
+        assert_eq!(BigInterval::from_f64(1.0) / &straddle, BigInterval::entire());
+        assert!((BigInterval::from_f64(1.0) / BigInterval::from_f64(0.0)).is_nan());
+
+        let quarter = BigInterval::from_f64(2.0).powi(-2);
+        assert_eq!(quarter, BigInterval::from_f64(0.25));
+        let unbounded = BigInterval { lo: BigFloat::from_f64(0.0), hi: BigFloat::from_f64(f64::INFINITY) };
+        assert_eq!(&unbounded * &unbounded, unbounded);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = BigInterval::new(BigFloat::new(3.0, 100), BigFloat::new(1.0, 100));
+        let b = BigInterval::new(BigFloat::new(2.0, 100), BigFloat::new(5.0, 100));
+        assert_eq!(a.lo, BigFloat::new(1.0, 100));
+        assert!(a.contains(&BigFloat::new(2.5, 100)) && !a.contains(&BigFloat::new(3.5, 100)));
+        assert_eq!(a.intersection(&b), Some(BigInterval::new(BigFloat::new(2.0, 100), BigFloat::new(3.0, 100))));
+        assert_eq!(a.hull(&b), BigInterval::new(BigFloat::new(1.0, 100), BigFloat::new(5.0, 100)));
+        assert!(a.hull(&b).encloses(&a) && !a.encloses(&b));
+        assert_eq!(a.intersection(&(&b + &b)), None);
+        assert_eq!(a.to_string(), "[1e100, 3e100]");
+
+        // 0.1 has no exact decimal mantissa in binary, 0.5 does
+        let tenth = BigInterval::from_f64(0.1);
+        assert!(!tenth.is_point() && tenth.contains(&BigFloat::from_f64(0.1)));
+        assert!(encloses(&tenth, "0.1000000000000000055511151231257827"));
+        assert!(BigInterval::from_f64(0.5).is_point());
+        assert!(BigInterval::from_f64(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_certain_comparisons() {
+        // Two balances that agree to every digit the mantissa holds: the
+        // nearest results may differ, but the intervals know they overlap
+        let tenth = BigInterval::from_f64(0.1);
+        let mut forward = BigInterval::from_f64(0.0);
+        let mut backward = BigInterval::from_f64(0.0);
+        for _ in 0..10 {
+            forward += &tenth;
+        }
+        for _ in 0..10 {
+            backward = &tenth + &backward;
+        }
+        assert_eq!(forward.compare(&backward), None);
+        assert!(!forward.certainly_lt(&backward) && !forward.certainly_gt(&backward));
+
+        let more = forward.clone() + BigInterval::from_f64(1e-10);
+        assert_eq!(forward.compare(&more), Some(Ordering::Less));
+        assert!(forward.certainly_lt(&more) && more.certainly_ge(&forward));
+
+        let point = BigInterval::from_f64(2.0);
+        assert_eq!(point.compare(&BigInterval::from_f64(2.0)), Some(Ordering::Equal));
+        assert!(point.certainly_le(&BigInterval::new(BigFloat::from_f64(2.0), BigFloat::from_f64(3.0))));
+        assert_eq!(point.compare(&BigInterval::from_f64(f64::NAN)), None);
+    }
+}
@@ -1,108 +1,163 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct BigFloat {
-    pub mantissa: f64,
-    pub exponent: u128,
+use crate::error::BigFloatError;
+use crate::exponent::{Exponent, ExponentStorage, MAX_EXPONENT_DEPTH};
+use crate::mantissa::Mantissa;
+use std::borrow::Cow;
+
+/// A floating-point number mantissa × 10^exponent.
+///
+/// Values are always kept in canonical form: finite non-zero values have
+/// 1 <= |mantissa| < 10 and a canonical `Exponent`, while zeros, infinities
+/// and NaN have a `Long(0)` exponent. Every constructor and operator goes
+/// through `normalize`, so the derived `PartialEq` is numeric equality.
+///
+/// The mantissa type defaults to `f64`; see `Mantissa` for `f32`. Nested
+/// exponents are always `BigFloat<f64>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BigFloat<M: Mantissa = f64> {
+    mantissa: M,
+    exponent: M::StoredExponent,
 }
 
-impl BigFloat {
-    pub fn new(mantissa: f64, exponent: u128) -> Self {
-        let mut bf = BigFloat {
-            mantissa,
-            exponent,
-        };
-        bf.normalize();
+impl<M: Mantissa> BigFloat<M> {
+    /// Like `new`, for any mantissa type: `BigFloat::<f32>::from_mantissa(1.5, 3)`.
+    pub fn from_mantissa(mantissa: M, exponent: impl Into<Exponent>) -> Self {
+        let (mantissa, exponent) = normalize(mantissa, exponent.into());
+        let bf = BigFloat { mantissa, exponent: M::StoredExponent::store(exponent) };
+        debug_assert!(bf.is_canonical(), "non-canonical BigFloat: {:?}", bf);
         bf
     }
 
-    pub fn new_from_i64_exponent(mantissa: f64, exponent: i64) -> Self {
-        if exponent < 0 {
-            // For negative exponents, we represent as mantissa with exponent 0
-            let scaled_mantissa = mantissa * 10.0_f64.powi(exponent as i32);
-            BigFloat {
-                mantissa: scaled_mantissa,
-                exponent: 0,
+    /// `value` normalized in f64 and then rounded once to the mantissa type.
+    pub(crate) fn from_float(value: f64) -> Self {
+        BigFloat::from_f64(value).cast()
+    }
+
+    /// The same value with another mantissa type, rounding the mantissa if
+    /// the new type is narrower.
+    pub fn cast<N: Mantissa>(&self) -> BigFloat<N> {
+        BigFloat::from_mantissa(N::from_f64(self.mantissa.to_f64()), self.exponent())
+    }
+
+    /// Builds a value from a mantissa and exponent, rejecting inputs that
+    /// `new` would have to saturate or could not give a meaning: a nested
+    /// exponent that is NaN or infinite, one deeper than
+    /// `MAX_EXPONENT_DEPTH`, or an infinite or NaN mantissa paired with a
+    /// non-zero exponent.
+    pub fn try_from_parts(mantissa: M, exponent: Exponent) -> Result<BigFloat<M>, BigFloatError> {
+        if !mantissa.to_f64().is_finite() && exponent != 0 {
+            return Err(BigFloatError::Domain { operation: "infinite or NaN mantissa with an exponent" });
+        }
+        if exponent.depth() > MAX_EXPONENT_DEPTH {
+            return Err(BigFloatError::ExponentOverflow);
+        }
+        if let Exponent::BigFloat(value) = &exponent {
+            if !value.is_finite() {
+                return Err(BigFloatError::ExponentOverflow);
             }
-        } else {
-            Self::new(mantissa, exponent as u128)
         }
+        Ok(BigFloat::from_mantissa(mantissa, exponent))
     }
 
+    /// Splits the value into its mantissa and exponent.
+    pub fn into_parts(self) -> (M, Exponent) {
+        (self.mantissa, self.exponent.into_exponent())
+    }
 
-    pub fn from_f64(value: f64) -> Self {
-        if value == 0.0 {
-            return BigFloat {
-                mantissa: 0.0,
-                exponent: 0,
-            };
+    /// Whether the value is in the canonical form described on `BigFloat`.
+    /// Always true for values built through the public API.
+    pub fn is_canonical(&self) -> bool {
+        let exponent = self.exponent.load();
+        let mantissa = self.mantissa.to_f64();
+        if mantissa == 0.0 || !mantissa.is_finite() {
+            return *exponent == 0;
         }
-        
-        if !value.is_finite() {
-            return BigFloat {
-                mantissa: value,
-                exponent: 0,
-            };
+        if !(1.0..10.0).contains(&mantissa.abs()) || exponent.depth() > MAX_EXPONENT_DEPTH {
+            return false;
         }
-
-        let abs_value = value.abs();
-        if abs_value < 1.0 {
-            // For small numbers, keep exponent 0 and allow mantissa < 1
-            BigFloat {
-                mantissa: value,
-                exponent: 0,
-            }
-        } else {
-            // For large numbers, normalize to mantissa >= 1 and < 10
-            let log10_value = abs_value.log10();
-            let exponent = log10_value.floor() as u128;
-            let mantissa = abs_value / 10.0_f64.powi(exponent as i32);
-            
-            BigFloat {
-                mantissa: if value.is_sign_negative() { -mantissa } else { mantissa },
-                exponent,
+        match &*exponent {
+            Exponent::Long(_) => exponent.clone().into_owned().canonical() == *exponent,
+            Exponent::BigFloat(value) => {
+                value.is_finite()
+                    && value.is_canonical()
+                    && exponent.clone().into_owned().canonical() == *exponent
             }
         }
     }
 
-    fn normalize(&mut self) {
-        if self.mantissa == 0.0 || !self.mantissa.is_finite() {
-            return;
-        }
+    pub fn mantissa(&self) -> M {
+        self.mantissa
+    }
 
-        let abs_mantissa = self.mantissa.abs();
-        
-        if self.exponent == 0 {
-            // When exponent is 0, allow mantissa to be < 1 for fractional numbers
-            if abs_mantissa >= 10.0 {
-                // Only normalize if mantissa >= 10
-                let log_mantissa = abs_mantissa.log10().floor();
-                let adjustment = log_mantissa as u128;
-                self.mantissa /= 10.0_f64.powi(adjustment as i32);
-                self.exponent = adjustment;
-            }
-            // Do nothing if mantissa < 1 when exponent is 0
-        } else {
-            // For non-zero exponents, maintain standard normalization (1 <= |mantissa| < 10)
-            if abs_mantissa >= 10.0 {
-                let log_mantissa = abs_mantissa.log10().floor();
-                let adjustment = log_mantissa as u128;
-                self.mantissa /= 10.0_f64.powi(adjustment as i32);
-                self.exponent += adjustment;
-            } else if abs_mantissa < 1.0 {
-                // Move to exponent 0 and allow fractional mantissa
-                let scale_factor = 10.0_f64.powi(self.exponent as i32);
-                self.mantissa *= scale_factor;
-                self.exponent = 0;
-            }
+    pub fn exponent(&self) -> Exponent {
+        self.exponent.load().into_owned()
+    }
+
+    /// The exponent, borrowed where the mantissa type stores it as an
+    /// `Exponent` (as `f64` does) and rebuilt from its compact form otherwise.
+    pub fn exponent_cow(&self) -> Cow<'_, Exponent> {
+        self.exponent.load()
+    }
+}
+
+impl BigFloat {
+    /// The exponent by reference, to avoid cloning a nested `BigFloat`.
+    pub fn exponent_ref(&self) -> &Exponent {
+        &self.exponent
+    }
+}
+
+// The one place where a `BigFloat` is brought into canonical form
+fn normalize<M: Mantissa>(mantissa: M, exponent: Exponent) -> (M, Exponent) {
+    let value = mantissa.to_f64();
+    if value == 0.0 || !value.is_finite() {
+        return (mantissa, Exponent::Long(0));
+    }
+
+    if let Exponent::BigFloat(exponent) = &exponent {
+        if exponent.mantissa.is_nan() {
+            return (M::from_f64(f64::NAN), Exponent::Long(0));
+        }
+        if exponent.mantissa.is_infinite() {
+            return overflow_parts(mantissa, exponent.mantissa > 0.0);
         }
     }
+    let exponent = exponent.canonical();
 
+    // Maintain standard normalization (1 <= |mantissa| < 10) for every exponent
+    let (mantissa, exponent) = if (1.0..10.0).contains(&value.abs()) {
+        (mantissa, exponent)
+    } else {
+        let (magnitude, adjustment) = mantissa.normalize();
+        let mantissa = if value.is_sign_negative() { -magnitude } else { magnitude };
+        (mantissa, exponent + Exponent::Long(adjustment))
+    };
 
-    pub fn mantissa(&self) -> f64 {
-        self.mantissa
+    if exponent.depth() > MAX_EXPONENT_DEPTH {
+        return overflow_parts(mantissa, !exponent.is_negative());
+    }
+    (mantissa, exponent)
+}
+
+/// Result of an exponent that is too large to represent: ±∞ when it grew
+/// too large and a signed zero when it became too small.
+fn overflow_parts<M: Mantissa>(mantissa: M, positive: bool) -> (M, Exponent) {
+    let magnitude = if positive { f64::INFINITY } else { 0.0 };
+    let signed = if mantissa.to_f64().is_sign_negative() { -magnitude } else { magnitude };
+    (M::from_f64(signed), Exponent::Long(0))
+}
+
+impl BigFloat {
+    pub fn new(mantissa: f64, exponent: impl Into<Exponent>) -> Self {
+        BigFloat::from_mantissa(mantissa, exponent)
+    }
+
+    pub fn new_from_i64_exponent(mantissa: f64, exponent: i64) -> Self {
+        Self::new(mantissa, exponent)
     }
 
-    pub fn exponent(&self) -> u128 {
-        self.exponent
+    pub fn from_f64(value: f64) -> Self {
+        // Zeros keep their sign, as in IEEE 754
+        BigFloat::new(value, 0)
     }
 }
 
@@ -127,10 +182,16 @@ mod tests {
     #[test]
     fn test_new_small_normalization() {
         let bf = BigFloat::new(0.15, 2);
-        // With new logic: if exponent != 0 and mantissa < 1, move to exponent 0
-        // 0.15 * 10^2 = 15, so it should be stored as mantissa 15 with exponent 0
-        assert!((bf.mantissa() - 15.0).abs() < 1e-10);
-        assert_eq!(bf.exponent(), 0);
+        // 0.15 * 10^2 = 15 = 1.5 * 10^1
+        assert!((bf.mantissa() - 1.5).abs() < 1e-10);
+        assert_eq!(bf.exponent(), 1);
+    }
+
+    #[test]
+    fn test_new_negative_exponent() {
+        let bf = BigFloat::new(25.0, -5000);
+        assert!((bf.mantissa() - 2.5).abs() < 1e-10);
+        assert_eq!(bf.exponent(), -4999);
     }
 
     #[test]
@@ -157,9 +218,26 @@ mod tests {
     #[test]
     fn test_from_f64_small() {
         let bf = BigFloat::from_f64(0.00123);
-        // Small numbers keep exponent 0 and allow mantissa < 1
-        assert!((bf.mantissa() - 0.00123).abs() < 1e-10);
-        assert_eq!(bf.exponent(), 0);
+        assert!((bf.mantissa() - 1.23).abs() < 1e-10);
+        assert_eq!(bf.exponent(), -3);
+    }
+
+    #[test]
+    fn test_from_f64_subnormal() {
+        let bf = BigFloat::from_f64(5e-324);
+        assert!((bf.mantissa() - 4.94065645841247).abs() < 1e-10);
+        assert_eq!(bf.exponent(), -324);
+    }
+
+    #[test]
+    fn test_from_f64_decade_boundary() {
+        let bf = BigFloat::from_f64(1000.0);
+        assert_eq!(bf.mantissa(), 1.0);
+        assert_eq!(bf.exponent(), 3);
+
+        let bf = BigFloat::from_f64(0.001);
+        assert!((bf.mantissa() - 1.0).abs() < 1e-12);
+        assert_eq!(bf.exponent(), -3);
     }
 
     #[test]
@@ -186,8 +264,45 @@ mod tests {
     #[test]
     fn test_small_fraction() {
         let bf = BigFloat::new(0.123, 0);
-        assert_eq!(bf.mantissa(), 0.123);
-        assert_eq!(bf.exponent(), 0);
+        assert!((bf.mantissa() - 1.23).abs() < 1e-10);
+        assert_eq!(bf.exponent(), -1);
+    }
+
+    #[test]
+    fn test_new_with_bigfloat_exponent() {
+        let googol = BigFloat::new(1.0, 100);
+        let googolplex = BigFloat::new(1.0, googol.clone());
+        assert_eq!(googolplex.mantissa(), 1.0);
+        assert_eq!(googolplex.exponent(), Exponent::BigFloat(Box::new(googol)));
+    }
+
+    #[test]
+    fn test_new_promotes_on_normalization() {
+        let bf = BigFloat::new(50.0, i128::MAX);
+        assert!((bf.mantissa() - 5.0).abs() < 1e-10);
+        assert_eq!(bf.exponent().depth(), 1);
+    }
+
+    #[test]
+    fn test_new_demotes_small_bigfloat_exponent() {
+        let bf = BigFloat::new(2.0, Exponent::BigFloat(Box::new(BigFloat::new(1.0, 3))));
+        assert_eq!(bf.exponent(), 1000);
+    }
+
+    #[test]
+    fn test_depth_limit_saturates() {
+        let mut bf = BigFloat::new(1.0, 100);
+        for _ in 0..MAX_EXPONENT_DEPTH + 1 {
+            bf = BigFloat::new(1.0, bf);
+        }
+        assert_eq!(bf.mantissa(), f64::INFINITY);
+
+        let mut bf = BigFloat::new(1.0, 100);
+        for _ in 0..MAX_EXPONENT_DEPTH {
+            bf = BigFloat::new(1.0, bf);
+        }
+        assert!(bf.is_finite());
+        assert_eq!(BigFloat::new(1.0, -bf).mantissa(), 0.0);
     }
 
     #[test]
@@ -196,4 +311,90 @@ mod tests {
         assert!((bf.mantissa() - 1.2345).abs() < 1e-10);
         assert_eq!(bf.exponent(), 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_constructors_are_canonical() {
+        let bf = BigFloat::new_from_i64_exponent(1.0, -2);
+        assert!(bf.is_canonical());
+        assert_eq!(bf, BigFloat::from_f64(0.01));
+
+        // Numerically equal values compare equal however they were built
+        assert_eq!(BigFloat::new(0.5, 3), BigFloat::new(50.0, 1));
+        assert_eq!(BigFloat::new(-0.0, 7), BigFloat::from_f64(0.0));
+        assert_eq!(BigFloat::new(1.0, 20) - BigFloat::new(1.0, 19), BigFloat::new(9.0, 19));
+
+        for value in [
+            BigFloat::from_f64(f64::NAN),
+            BigFloat::new(12.5, i128::MAX),
+            BigFloat::new(3.0, BigFloat::new(2.0, 40)),
+            -BigFloat::new(0.25, -3),
+        ] {
+            assert!(value.is_canonical(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_try_from_parts() {
+        let bf = BigFloat::try_from_parts(25.0, Exponent::Long(-1)).unwrap();
+        assert_eq!(bf, BigFloat::from_f64(2.5));
+
+        let bf = BigFloat::try_from_parts(1.0, Exponent::BigFloat(Box::new(BigFloat::new(5.0, 3)))).unwrap();
+        assert_eq!(bf, BigFloat::new(1.0, 5000));
+
+        assert!(matches!(
+            BigFloat::try_from_parts(f64::INFINITY, Exponent::Long(3)),
+            Err(BigFloatError::Domain { .. })
+        ));
+        assert!(BigFloat::try_from_parts(f64::NAN, Exponent::Long(0)).unwrap().is_nan());
+        let infinite_exponent = Exponent::BigFloat(Box::new(BigFloat::from_f64(f64::INFINITY)));
+        assert_eq!(BigFloat::try_from_parts(1.0, infinite_exponent), Err(BigFloatError::ExponentOverflow));
+
+        let mut deep = BigFloat::new(1.0, 100);
+        for _ in 0..MAX_EXPONENT_DEPTH {
+            deep = BigFloat::new(1.0, deep);
+        }
+        assert_eq!(
+            BigFloat::try_from_parts(1.0, Exponent::from_bigfloat(deep)),
+            Err(BigFloatError::ExponentOverflow)
+        );
+    }
+
+    #[test]
+    fn test_f32_mantissa() {
+        let a = BigFloat::<f32>::from_mantissa(15.0, 1000);
+        assert_eq!(a.mantissa(), 1.5_f32);
+        assert_eq!(a.exponent(), 1001);
+        assert!(a.is_canonical());
+
+        let b: BigFloat<f32> = "2.5e1001".parse().unwrap();
+        assert_eq!(&a + &b, BigFloat::from_mantissa(4.0, 1001));
+        assert_eq!(&a * &b, BigFloat::from_mantissa(3.75, 2002));
+        assert_eq!(&b / &a, BigFloat::from_mantissa(2.5 / 1.5, 0));
+        assert!(a < b && -b.clone() < a);
+        assert_eq!(b.to_string(), "2.5e1001");
+        assert_eq!("0.1".parse::<BigFloat<f32>>().unwrap().mantissa(), 1.0_f32);
+
+        // Mantissas convert exactly from f32 and round once to it
+        assert_eq!(a.cast::<f64>(), BigFloat::new(1.5, 1001));
+        assert_eq!(BigFloat::from_f64(0.1).cast::<f32>().mantissa(), 1.0_f32);
+        assert_eq!(BigFloat::from_f64(9.99999999).cast::<f32>(), BigFloat::from_mantissa(1.0_f32, 1));
+
+        let ln = BigFloat::<f32>::from_mantissa(1.0, 100).ln();
+        assert_eq!(ln.mantissa(), (100.0 * std::f64::consts::LN_10 / 100.0) as f32);
+        assert_eq!(ln.exponent(), 2);
+    }
+
+    #[test]
+    fn test_f32_storage_is_compact() {
+        assert_eq!(std::mem::size_of::<BigFloat<f32>>(), 24);
+        assert_eq!(std::mem::size_of::<BigFloat>(), 48);
+
+        // Exponents past the inline i64 and nested ones keep their value
+        let wide = BigFloat::<f32>::from_mantissa(2.5, i64::MAX as i128 + 1);
+        assert_eq!(wide.exponent(), i64::MAX as i128 + 1);
+        let nested = BigFloat::<f32>::from_mantissa(2.5, BigFloat::new(1.0, 100));
+        assert_eq!(*nested.exponent_cow(), Exponent::from_bigfloat(BigFloat::new(1.0, 100)));
+        assert!(wide < nested && wide.is_canonical() && nested.is_canonical());
+        assert_eq!(nested.to_string(), "2.5e1e100");
+    }
+}
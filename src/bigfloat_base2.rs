@@ -0,0 +1,906 @@
+use crate::bigfloat::BigFloat;
+use crate::double_double::{split_product, DoubleDouble, LN_10, LN_2, LOG10_2, LOG10_2_FIXED, LOG2_10, LOG2_10_FIXED};
+use crate::error::BigFloatError;
+use crate::exponent::Exponent;
+use crate::operators::{convert, impl_forwarded_ops};
+use crate::traits::Category;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+const EXPONENT_MASK: u64 = 0x7ff << 52;
+const EXPONENT_BIAS: i128 = 1023;
+
+// An operand this many binary places below the other is under a quarter of
+// an ulp of the sum, so it cannot change the rounded result
+const ADD_BITS: i128 = 54;
+
+// Decimal exponents up to this size convert through exact double-double
+// scaling; past it the value leaves the f64 range anyway
+const SCALE_DIGITS: i128 = 290;
+
+/// A floating-point number mantissa × 2^exponent.
+///
+/// The base-2 counterpart of `BigFloat`: finite non-zero values have
+/// 1 <= |mantissa| < 2, which is exactly the significand of an f64, so
+/// normalization only moves the f64's exponent bits into `exponent` and
+/// never rounds. Multiplying or dividing by a power of two (`ldexp`) is
+/// exact, and `+ - * /` and `sqrt` round once, like the f64 operations.
+/// Zeros, infinities and NaN have a `Long(0)` exponent; the exponent
+/// promotes and saturates exactly as in `BigFloat`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BigFloatBase2 {
+    mantissa: f64,
+    exponent: Exponent,
+}
+
+impl BigFloatBase2 {
+    pub fn new(mantissa: f64, exponent: impl Into<Exponent>) -> Self {
+        normalize(mantissa, exponent.into())
+    }
+
+    /// The f64 `value`, exactly: subnormals included.
+    pub fn from_f64(value: f64) -> Self {
+        BigFloatBase2::new(value, 0)
+    }
+
+    /// Splits the value into its mantissa and exponent.
+    pub fn into_parts(self) -> (f64, Exponent) {
+        (self.mantissa, self.exponent)
+    }
+
+    pub fn mantissa(&self) -> f64 {
+        self.mantissa
+    }
+
+    pub fn exponent(&self) -> Exponent {
+        self.exponent.clone()
+    }
+
+    pub fn exponent_ref(&self) -> &Exponent {
+        &self.exponent
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0.0
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.mantissa.is_finite()
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.mantissa.is_nan()
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.mantissa.is_infinite()
+    }
+
+    pub fn is_sign_positive(&self) -> bool {
+        self.mantissa.is_sign_positive()
+    }
+
+    pub fn is_sign_negative(&self) -> bool {
+        self.mantissa.is_sign_negative()
+    }
+
+    pub fn abs(&self) -> BigFloatBase2 {
+        if self.is_sign_negative() { -self.clone() } else { self.clone() }
+    }
+
+    /// ±1 for non-zero values; zeros and NaN are returned unchanged.
+    pub fn signum(&self) -> BigFloatBase2 {
+        if self.is_zero() || self.is_nan() {
+            self.clone()
+        } else {
+            BigFloatBase2::from_f64(self.mantissa.signum())
+        }
+    }
+
+    pub fn classify(&self) -> Category {
+        if self.is_nan() {
+            Category::Nan
+        } else if self.is_infinite() {
+            Category::Infinite
+        } else if self.is_zero() {
+            Category::Zero
+        } else {
+            Category::Normal
+        }
+    }
+
+    /// IEEE 754 totalOrder, as `BigFloat::total_cmp`.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        if self.is_nan() || other.is_nan() || self.is_zero() && other.is_zero() {
+            // Stand in ±1 for ordinary values and let f64 order the rest
+            let proxy = |value: &BigFloatBase2| {
+                if value.is_nan() || value.is_zero() { value.mantissa } else { 1.0_f64.copysign(value.mantissa) }
+            };
+            return proxy(self).total_cmp(&proxy(other));
+        }
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+    }
+
+    /// The smaller value; as with `f64::min`, a NaN operand is ignored.
+    pub fn min(self, other: BigFloatBase2) -> BigFloatBase2 {
+        if self.is_nan() || other < self { other } else { self }
+    }
+
+    /// The larger value; as with `f64::max`, a NaN operand is ignored.
+    pub fn max(self, other: BigFloatBase2) -> BigFloatBase2 {
+        if self.is_nan() || other > self { other } else { self }
+    }
+
+    /// `self + other`, or `None` if finite operands gave an infinite or NaN result.
+    pub fn checked_add(&self, other: &BigFloatBase2) -> Option<BigFloatBase2> {
+        checked(self + other, &[self, other], false)
+    }
+
+    /// `self - other`, or `None` if finite operands gave an infinite or NaN result.
+    pub fn checked_sub(&self, other: &BigFloatBase2) -> Option<BigFloatBase2> {
+        checked(self - other, &[self, other], false)
+    }
+
+    /// `self * other`, or `None` if the exponent overflowed to ±∞ or
+    /// underflowed to ±0.
+    pub fn checked_mul(&self, other: &BigFloatBase2) -> Option<BigFloatBase2> {
+        checked(self * other, &[self, other], true)
+    }
+
+    /// `self / other`, or `None` on division by zero, 0/0, or an exponent
+    /// that overflowed or underflowed.
+    pub fn checked_div(&self, other: &BigFloatBase2) -> Option<BigFloatBase2> {
+        checked(self / other, &[self, other], true)
+    }
+
+    /// `self.pow(exponent)`, or `None` if the result overflowed, underflowed
+    /// or is undefined (a negative base with a non-integer exponent).
+    pub fn checked_pow(&self, exponent: &BigFloatBase2) -> Option<BigFloatBase2> {
+        checked(self.pow(exponent), &[self, exponent], !exponent.is_zero())
+    }
+
+    // The operators already saturate to ±∞ and ±0, as in `BigFloat`
+
+    pub fn saturating_add(&self, other: &BigFloatBase2) -> BigFloatBase2 {
+        self + other
+    }
+
+    pub fn saturating_sub(&self, other: &BigFloatBase2) -> BigFloatBase2 {
+        self - other
+    }
+
+    pub fn saturating_mul(&self, other: &BigFloatBase2) -> BigFloatBase2 {
+        self * other
+    }
+
+    pub fn saturating_div(&self, other: &BigFloatBase2) -> BigFloatBase2 {
+        self / other
+    }
+
+    pub fn saturating_pow(&self, exponent: &BigFloatBase2) -> BigFloatBase2 {
+        self.pow(exponent)
+    }
+
+    /// `self` × 2^`n`, exact: only the exponent changes.
+    pub fn ldexp(&self, n: i64) -> BigFloatBase2 {
+        if self.is_zero() || !self.is_finite() {
+            return self.clone();
+        }
+        normalize(self.mantissa, self.exponent() + Exponent::from(n))
+    }
+
+    /// The C `frexp` split: a fraction with 0.5 <= |fraction| < 1 and an
+    /// exponent with `self` = fraction × 2^exponent. Zeros, infinities and
+    /// NaN come back unchanged with a zero exponent.
+    pub fn frexp(&self) -> (f64, Exponent) {
+        if self.is_zero() || !self.is_finite() {
+            return (self.mantissa, Exponent::Long(0));
+        }
+        (self.mantissa / 2.0, self.exponent() + Exponent::Long(1))
+    }
+
+    /// The nearest `BigFloat`, rounding once from a double-double
+    /// intermediate for an `i128` exponent.
+    pub fn to_bigfloat(&self) -> BigFloat {
+        if self.is_zero() || !self.is_finite() {
+            return BigFloat::from_f64(self.mantissa);
+        }
+        // 2^exponent = 10^integer · 10^fraction
+        match self.exponent.as_i128().and_then(|exponent| split_product(exponent, &LOG10_2_FIXED)) {
+            Some((mut integer, fraction)) => {
+                let mut mantissa = DoubleDouble::from_f64(self.mantissa) * (fraction * LN_10).exp();
+                if mantissa.hi.abs() >= 10.0 {
+                    mantissa = mantissa / DoubleDouble::from_f64(10.0);
+                    integer += 1;
+                }
+                BigFloat::new(mantissa.to_f64(), integer)
+            }
+            None => self.to_bigfloat_nested(),
+        }
+    }
+
+    /// The f64 value, or `None` beyond the f64 range. Exact wherever the
+    /// value is an f64, and otherwise rounded once.
+    pub fn to_f64(&self) -> Option<f64> {
+        match self.exponent.as_i128() {
+            _ if self.is_zero() || !self.is_finite() => Some(self.mantissa),
+            Some(exponent) if exponent <= 1023 => Some(scale2(self.mantissa, exponent.max(-1200) as i32)),
+            _ => None,
+        }
+    }
+
+    /// Natural logarithm, ln(mantissa) + exponent·ln 2 in double-double.
+    pub fn ln(&self) -> BigFloatBase2 {
+        match self.log_parts() {
+            Some((mantissa, exponent)) => {
+                BigFloatBase2::from_double_double(mantissa.ln() + exponent * LN_2)
+            }
+            None => BigFloatBase2::from(self.to_bigfloat().ln()),
+        }
+    }
+
+    pub fn log10(&self) -> BigFloatBase2 {
+        match self.log_parts() {
+            Some((mantissa, exponent)) => {
+                BigFloatBase2::from_double_double(mantissa.ln() / LN_10 + exponent * LOG10_2)
+            }
+            None => BigFloatBase2::from(self.to_bigfloat().log10()),
+        }
+    }
+
+    /// e^self, computed as 2^(self / ln 2) with the integer part of the
+    /// power as the exponent.
+    pub fn exp(&self) -> BigFloatBase2 {
+        match self.to_f64() {
+            Some(x) if x.is_finite() => exp2_double_double(DoubleDouble::from_f64(x) / LN_2)
+                .unwrap_or_else(|| BigFloatBase2::from(self.to_bigfloat().exp())),
+            _ => BigFloatBase2::from(self.to_bigfloat().exp()),
+        }
+    }
+
+    /// 2^self, with the integer part of the power as the exponent, so it is
+    /// exact for integer powers.
+    pub fn exp2(&self) -> BigFloatBase2 {
+        match self.to_f64() {
+            Some(x) if x.is_finite() => exp2_double_double(DoubleDouble::from_f64(x))
+                .unwrap_or_else(|| BigFloatBase2::from(self.to_bigfloat().exp2())),
+            _ => BigFloatBase2::from(self.to_bigfloat().exp2()),
+        }
+    }
+
+    /// 10^self, computed as 2^(self·log2 10).
+    pub fn exp10(&self) -> BigFloatBase2 {
+        match self.to_f64() {
+            Some(x) if x.is_finite() => exp2_double_double(DoubleDouble::from_f64(x) * LOG2_10)
+                .unwrap_or_else(|| BigFloatBase2::from(self.to_bigfloat().exp10())),
+            _ => BigFloatBase2::from(self.to_bigfloat().exp10()),
+        }
+    }
+
+    /// self^exponent. Integer exponents use repeated squaring; others go
+    /// through log2, and a negative base needs an integer exponent
+    /// (otherwise the result is NaN).
+    pub fn pow(&self, exponent: &BigFloatBase2) -> BigFloatBase2 {
+        let y = exponent.to_f64().filter(|y| y.is_finite());
+        if let Some(n) = y.filter(|y| y.fract() == 0.0 && y.abs() < 9.2e18) {
+            return self.powi(n as i64);
+        }
+
+        if let (Some(y), Some((mantissa, exponent))) = (y, self.abs().log_parts()) {
+            if self.is_sign_negative() {
+                return BigFloatBase2::from_f64(f64::NAN);
+            }
+            let log2 = mantissa.ln() / LN_2 + exponent;
+            if let Some(result) = exp2_double_double(log2 * DoubleDouble::from_f64(y)) {
+                return result;
+            }
+        }
+        BigFloatBase2::from(self.to_bigfloat().pow(&exponent.to_bigfloat()))
+    }
+
+    /// self^n by binary exponentiation.
+    pub fn powi(&self, n: i64) -> BigFloatBase2 {
+        let mut base = self.clone();
+        let mut remaining = n.unsigned_abs();
+        let mut result = BigFloatBase2::from_f64(1.0);
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = &result * &base;
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                base = &base * &base;
+            }
+        }
+        if n < 0 { BigFloatBase2::from_f64(1.0) / result } else { result }
+    }
+
+    /// Square root, correctly rounded for an `i128` exponent.
+    pub fn sqrt(&self) -> BigFloatBase2 {
+        match self.exponent.as_i128() {
+            Some(exponent) if self.is_finite() && self.mantissa > 0.0 => {
+                // Halve an even exponent; an odd one moves a factor 2 into the mantissa
+                let odd = exponent.rem_euclid(2);
+                let mantissa = (self.mantissa * (1 + odd) as f64).sqrt();
+                normalize(mantissa, Exponent::from((exponent - odd) / 2))
+            }
+            _ => BigFloatBase2::from(self.to_bigfloat().sqrt()),
+        }
+    }
+
+    fn from_double_double(value: DoubleDouble) -> BigFloatBase2 {
+        BigFloatBase2::from_f64(value.to_f64())
+    }
+
+    /// A finite positive value as its mantissa and `i128` exponent, for
+    /// the logarithms.
+    fn log_parts(&self) -> Option<(DoubleDouble, DoubleDouble)> {
+        match self.exponent.as_i128() {
+            Some(exponent) if self.is_finite() && self.mantissa > 0.0 => {
+                Some((DoubleDouble::from_f64(self.mantissa), DoubleDouble::from_i128(exponent)))
+            }
+            _ => None,
+        }
+    }
+
+    // mantissa × 10^(exponent·log10 2) in `BigFloat` arithmetic; past an
+    // i128 exponent the fraction of the power is below the exponent's precision
+    fn to_bigfloat_nested(&self) -> BigFloat {
+        let power = scale_by(self.exponent.to_bigfloat(), LOG10_2);
+        BigFloat::new(self.mantissa, Exponent::from_bigfloat(power))
+    }
+}
+
+// Every `BigFloatBase2` result passes through here
+fn normalize(mantissa: f64, exponent: Exponent) -> BigFloatBase2 {
+    if mantissa == 0.0 || !mantissa.is_finite() {
+        return special(mantissa);
+    }
+
+    let (mantissa, shift) = split_binary(mantissa);
+    match (exponent + Exponent::Long(shift)).saturating_canonical(mantissa < 0.0) {
+        Ok(exponent) => BigFloatBase2 { mantissa, exponent },
+        Err(value) => special(value),
+    }
+}
+
+/// `result`, unless finite operands gave an infinite result, non-zero ones a
+/// zero (when `can_underflow`), or operands that are not NaN gave NaN.
+fn checked(result: BigFloatBase2, operands: &[&BigFloatBase2], can_underflow: bool) -> Option<BigFloatBase2> {
+    let finite = operands.iter().all(|operand| operand.is_finite());
+    let overflowed = finite
+        && (result.is_infinite()
+            || can_underflow && result.is_zero() && operands.iter().all(|operand| !operand.is_zero()));
+    let undefined = result.is_nan() && !operands.iter().any(|operand| operand.is_nan());
+    if overflowed || undefined { None } else { Some(result) }
+}
+
+fn special(value: f64) -> BigFloatBase2 {
+    BigFloatBase2 {
+        mantissa: value,
+        exponent: Exponent::Long(0),
+    }
+}
+
+/// Splits a finite non-zero f64 into its signed significand in [1, 2) and
+/// binary exponent by rewriting the exponent bits, so the split is exact.
+fn split_binary(value: f64) -> (f64, i128) {
+    let bits = value.to_bits();
+    let biased = ((bits & EXPONENT_MASK) >> 52) as i128;
+    if biased == 0 {
+        // Subnormal: 2^64 brings it into the normal range exactly
+        let (mantissa, exponent) = split_binary(value * 2.0_f64.powi(64));
+        return (mantissa, exponent - 64);
+    }
+    let mantissa = f64::from_bits(bits & !EXPONENT_MASK | (EXPONENT_BIAS as u64) << 52);
+    (mantissa, biased - EXPONENT_BIAS)
+}
+
+/// 2^`power` for a power within the normal f64 exponent range, built from its bits.
+fn pow2(power: i32) -> f64 {
+    debug_assert!((-1022..=1023).contains(&power));
+    f64::from_bits(((power as i64 + EXPONENT_BIAS as i64) as u64) << 52)
+}
+
+/// `mantissa` × 2^`power`, exact unless the result is subnormal, where it
+/// is rounded once.
+fn scale2(mantissa: f64, power: i32) -> f64 {
+    if power >= -1022 {
+        mantissa * pow2(power.min(1023))
+    } else {
+        // The first factor stays normal, so only the second rounds
+        mantissa * pow2(-1022) * pow2((power + 1022).max(-1022))
+    }
+}
+
+/// `value` × `factor` with both halves of the double-double factor.
+fn scale_by(value: BigFloat, factor: DoubleDouble) -> BigFloat {
+    value.clone() * BigFloat::from_f64(factor.hi) + value * BigFloat::from_f64(factor.lo)
+}
+
+/// 2^`power` with the integer part of the power as the exponent, or `None`
+/// if the power is too large to split.
+fn exp2_double_double(power: DoubleDouble) -> Option<BigFloatBase2> {
+    if !power.is_finite() || power.hi.abs() >= 1e37 {
+        return None;
+    }
+    let (integer, fraction) = power.floor_split();
+    Some(normalize((fraction * LN_2).exp().to_f64(), Exponent::from(integer)))
+}
+
+impl Add for BigFloatBase2 {
+    type Output = BigFloatBase2;
+
+    fn add(self, other: BigFloatBase2) -> BigFloatBase2 {
+        // Special values follow f64, as in `BigFloat`
+        if self.is_zero() && other.is_zero() || !self.is_finite() || !other.is_finite() {
+            return BigFloatBase2::from_f64(self.mantissa + other.mantissa);
+        }
+        if self.is_zero() {
+            return other;
+        }
+        if other.is_zero() {
+            return self;
+        }
+
+        let (big, small) = if self.exponent >= other.exponent { (self, other) } else { (other, self) };
+        let difference = (big.exponent() - small.exponent()).as_i128().unwrap_or(i128::MAX);
+        if difference > ADD_BITS {
+            return big;
+        }
+        // The shifted mantissa is exact, so the sum rounds once
+        let mantissa = big.mantissa + scale2(small.mantissa, -(difference as i32));
+        normalize(mantissa, big.exponent)
+    }
+}
+
+impl Sub for BigFloatBase2 {
+    type Output = BigFloatBase2;
+
+    fn sub(self, other: BigFloatBase2) -> BigFloatBase2 {
+        self.add(-other)
+    }
+}
+
+impl Mul for BigFloatBase2 {
+    type Output = BigFloatBase2;
+
+    fn mul(self, other: BigFloatBase2) -> BigFloatBase2 {
+        if self.is_zero() || other.is_zero() || !self.is_finite() || !other.is_finite() {
+            return BigFloatBase2::from_f64(self.mantissa * other.mantissa);
+        }
+        normalize(self.mantissa * other.mantissa, self.exponent + other.exponent)
+    }
+}
+
+impl Div for BigFloatBase2 {
+    type Output = BigFloatBase2;
+
+    fn div(self, other: BigFloatBase2) -> BigFloatBase2 {
+        if self.is_zero() || other.is_zero() || !self.is_finite() || !other.is_finite() {
+            return BigFloatBase2::from_f64(self.mantissa / other.mantissa);
+        }
+        normalize(self.mantissa / other.mantissa, self.exponent - other.exponent)
+    }
+}
+
+impl Neg for BigFloatBase2 {
+    type Output = BigFloatBase2;
+
+    fn neg(self) -> BigFloatBase2 {
+        BigFloatBase2 {
+            mantissa: -self.mantissa,
+            exponent: self.exponent,
+        }
+    }
+}
+
+impl Neg for &BigFloatBase2 {
+    type Output = BigFloatBase2;
+
+    fn neg(self) -> BigFloatBase2 {
+        -self.clone()
+    }
+}
+
+impl_forwarded_ops!(BigFloatBase2, convert);
+
+impl PartialOrd for BigFloatBase2 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        if !self.is_finite() || !other.is_finite() || self.is_zero() || other.is_zero() {
+            return self.mantissa.partial_cmp(&other.mantissa);
+        }
+        match (self.is_sign_negative(), other.is_sign_negative()) {
+            (false, true) => return Some(Ordering::Greater),
+            (true, false) => return Some(Ordering::Less),
+            _ => {}
+        }
+
+        // Same sign: a larger exponent means a larger magnitude
+        let magnitude = self.exponent.cmp(&other.exponent);
+        match magnitude {
+            Ordering::Equal => self.mantissa.partial_cmp(&other.mantissa),
+            _ if self.is_sign_negative() => Some(magnitude.reverse()),
+            _ => Some(magnitude),
+        }
+    }
+}
+
+// Values are canonical, so hashing the parts matches `==` apart from the
+// zeros and the NaNs, which have several representations
+impl Hash for BigFloatBase2 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.is_zero() {
+            0.0_f64.to_bits().hash(state);
+        } else if self.is_nan() {
+            f64::NAN.to_bits().hash(state);
+        } else {
+            self.mantissa.to_bits().hash(state);
+        }
+        self.exponent.hash(state);
+    }
+}
+
+// Comparisons against primitives convert the primitive, on either side
+macro_rules! impl_primitive_cmp {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq<$t> for BigFloatBase2 {
+                fn eq(&self, other: &$t) -> bool {
+                    self.partial_cmp(other) == Some(Ordering::Equal)
+                }
+            }
+
+            impl PartialEq<BigFloatBase2> for $t {
+                fn eq(&self, other: &BigFloatBase2) -> bool {
+                    other.partial_cmp(self) == Some(Ordering::Equal)
+                }
+            }
+
+            impl PartialOrd<$t> for BigFloatBase2 {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    self.partial_cmp(&BigFloatBase2::from(*other))
+                }
+            }
+
+            impl PartialOrd<BigFloatBase2> for $t {
+                fn partial_cmp(&self, other: &BigFloatBase2) -> Option<Ordering> {
+                    BigFloatBase2::from(*self).partial_cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_cmp!(f64, i32, i64);
+
+// Neumaier summation: each addition rounds once, like f64, so its error is
+// exactly recoverable and small terms are not lost against large ones
+impl Sum for BigFloatBase2 {
+    fn sum<I: Iterator<Item = BigFloatBase2>>(iter: I) -> BigFloatBase2 {
+        let mut sum = BigFloatBase2::from_f64(0.0);
+        let mut compensation = BigFloatBase2::from_f64(0.0);
+        for value in iter {
+            let next = &sum + &value;
+            if next.is_finite() {
+                compensation += if sum.abs() >= value.abs() {
+                    (&sum - &next) + value
+                } else {
+                    (&value - &next) + sum
+                };
+            }
+            sum = next;
+        }
+        if sum.is_finite() { sum + compensation } else { sum }
+    }
+}
+
+impl<'a> Sum<&'a BigFloatBase2> for BigFloatBase2 {
+    fn sum<I: Iterator<Item = &'a BigFloatBase2>>(iter: I) -> BigFloatBase2 {
+        iter.cloned().sum()
+    }
+}
+
+impl Product for BigFloatBase2 {
+    fn product<I: Iterator<Item = BigFloatBase2>>(iter: I) -> BigFloatBase2 {
+        iter.fold(BigFloatBase2::from_f64(1.0), |product, value| product * value)
+    }
+}
+
+impl<'a> Product<&'a BigFloatBase2> for BigFloatBase2 {
+    fn product<I: Iterator<Item = &'a BigFloatBase2>>(iter: I) -> BigFloatBase2 {
+        iter.fold(BigFloatBase2::from_f64(1.0), |product, value| product * value)
+    }
+}
+
+impl Default for BigFloatBase2 {
+    fn default() -> Self {
+        BigFloatBase2::from_f64(0.0)
+    }
+}
+
+impl From<BigFloat> for BigFloatBase2 {
+    /// Exact whenever the decimal value is an f64 number times a power of
+    /// two; otherwise rounded once from a double-double intermediate.
+    fn from(value: BigFloat) -> Self {
+        if value.is_zero() || !value.is_finite() {
+            return special(value.mantissa());
+        }
+        let (mantissa, exponent) = value.into_parts();
+        match exponent.as_i128() {
+            Some(exponent) if exponent.abs() <= SCALE_DIGITS => {
+                let scaled = DoubleDouble::from_f64(mantissa).scale10(exponent as i32);
+                normalize(scaled.to_f64(), Exponent::Long(0))
+            }
+            Some(exponent) => match split_product(exponent, &LOG2_10_FIXED) {
+                // 10^exponent = 2^integer · 2^fraction
+                Some((integer, fraction)) => {
+                    let scaled = DoubleDouble::from_f64(mantissa) * (fraction * LN_2).exp();
+                    normalize(scaled.to_f64(), Exponent::from(integer))
+                }
+                None => normalize(mantissa, Exponent::from_bigfloat(scale_by(BigFloat::from(exponent), LOG2_10))),
+            },
+            None => normalize(mantissa, Exponent::from_bigfloat(scale_by(exponent.to_bigfloat(), LOG2_10))),
+        }
+    }
+}
+
+impl From<BigFloatBase2> for BigFloat {
+    fn from(value: BigFloatBase2) -> Self {
+        value.to_bigfloat()
+    }
+}
+
+impl From<f64> for BigFloatBase2 {
+    fn from(value: f64) -> Self {
+        BigFloatBase2::from_f64(value)
+    }
+}
+
+impl From<i32> for BigFloatBase2 {
+    fn from(value: i32) -> Self {
+        BigFloatBase2::from_f64(value as f64)
+    }
+}
+
+impl From<i64> for BigFloatBase2 {
+    fn from(value: i64) -> Self {
+        BigFloatBase2::from_f64(value as f64)
+    }
+}
+
+impl fmt::Display for BigFloatBase2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Inside the f64 range print the f64's shortest round-trip digits,
+        // with the same plain/scientific thresholds as `BigFloat`
+        match self.to_f64() {
+            Some(value) if value.is_normal() && (1e-6..1e7).contains(&value.abs()) => write!(f, "{}", value),
+            Some(value) if value.is_normal() => write!(f, "{:e}", value),
+            _ => write!(f, "{}", self.to_bigfloat()),
+        }
+    }
+}
+
+impl FromStr for BigFloatBase2 {
+    type Err = BigFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The `BigFloat` parser validates and covers the full range; numbers
+        // that are normal f64 values are rounded once by the f64 parser
+        let value: BigFloat = s.parse()?;
+        match s.trim().parse::<f64>() {
+            Ok(parsed) if parsed.is_normal() => Ok(BigFloatBase2::from_f64(parsed)),
+            _ => Ok(BigFloatBase2::from(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization_is_exact() {
+        let value = BigFloatBase2::new(12.0, 10);
+        assert_eq!(value.mantissa(), 1.5);
+        assert_eq!(value.exponent(), 13);
+
+        for x in [0.1, -1e300, 5e-324, f64::MAX, f64::MIN_POSITIVE, -3.0] {
+            let value = BigFloatBase2::from_f64(x);
+            assert!((1.0..2.0).contains(&value.mantissa().abs()), "{:?}", value);
+            assert_eq!(value.to_f64(), Some(x));
+        }
+        assert_eq!(BigFloatBase2::from_f64(5e-324).exponent(), -1074);
+        assert_eq!(BigFloatBase2::from_f64(-0.0).exponent(), 0);
+        assert!(BigFloatBase2::from_f64(-0.0).is_sign_negative());
+    }
+
+    #[test]
+    fn test_ldexp_frexp() {
+        let x = BigFloatBase2::from_f64(0.1);
+        let scaled = x.ldexp(10_000);
+        assert_eq!(scaled.mantissa(), x.mantissa());
+        assert_eq!(scaled.ldexp(-10_000), x);
+        assert_eq!(BigFloatBase2::from_f64(8.0).frexp(), (0.5, Exponent::Long(4)));
+        assert_eq!(BigFloatBase2::from_f64(-0.75).frexp(), (-0.75, Exponent::Long(0)));
+
+        let huge = BigFloatBase2::new(1.0, i128::MAX).ldexp(1);
+        assert_eq!(huge.exponent().depth(), 1);
+        assert_eq!(huge.ldexp(-1), BigFloatBase2::new(1.0, i128::MAX));
+    }
+
+    #[test]
+    fn test_arithmetic_rounds_like_f64() {
+        let pairs = [(0.1, 0.2), (1e200, 1e-100), (3.0, -2.9999999999999996), (1.0, 1e-17)];
+        for (a, b) in pairs {
+            let (x, y) = (BigFloatBase2::from_f64(a), BigFloatBase2::from_f64(b));
+            assert_eq!((&x + &y).to_f64(), Some(a + b));
+            assert_eq!((&x - &y).to_f64(), Some(a - b));
+            assert_eq!((&x * &y).to_f64(), Some(a * b));
+            assert_eq!((&x / &y).to_f64(), Some(a / b));
+        }
+        assert_eq!(BigFloatBase2::from_f64(2.0).sqrt().to_f64(), Some(2.0_f64.sqrt()));
+
+        // Past the f64 range the exponent simply keeps growing
+        let big = BigFloatBase2::new(1.5, 5000);
+        assert_eq!(&big * &big, BigFloatBase2::new(1.125, 10_001));
+        assert_eq!(&big / 3.0, BigFloatBase2::new(1.0, 4999));
+        assert_eq!(BigFloatBase2::new(1.0, 4001).sqrt(), BigFloatBase2::new(2.0_f64.sqrt(), 2000));
+
+        let mut value = BigFloatBase2::from(10);
+        value *= 3;
+        value -= 0.5;
+        assert_eq!(value, BigFloatBase2::from_f64(29.5));
+    }
+
+    #[test]
+    fn test_special_values_and_ordering() {
+        let zero = BigFloatBase2::from(0);
+        assert!((BigFloatBase2::from(1) / &zero).is_infinite());
+        assert!((&zero / &zero).is_nan());
+        assert!((-zero.clone() + -zero).is_sign_negative());
+        assert!(BigFloatBase2::from(-1).sqrt().is_nan());
+        assert!(BigFloatBase2::from(-8).pow(&BigFloatBase2::from_f64(0.5)).is_nan());
+
+        let values = [
+            BigFloatBase2::new(-1.0, 5000),
+            BigFloatBase2::from(-1),
+            BigFloatBase2::new(1.0, -5000),
+            BigFloatBase2::from(1),
+            BigFloatBase2::new(1.0, 5000),
+            BigFloatBase2::from_f64(f64::INFINITY),
+        ];
+        for pair in values.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_transcendental() {
+        let two = BigFloatBase2::from(2);
+        assert_eq!(two.ln().to_f64(), Some(std::f64::consts::LN_2));
+        assert_eq!(BigFloatBase2::new(1.0, 1000).log10().to_f64(), Some(1000.0 * std::f64::consts::LOG10_2));
+        assert_eq!(BigFloatBase2::from(1).exp().to_f64(), Some(std::f64::consts::E));
+        assert_eq!(two.pow(&BigFloatBase2::from(10)), BigFloatBase2::from(1024));
+        assert_eq!(two.powi(-3), BigFloatBase2::from_f64(0.125));
+
+        // e^1000 = 1.9700711140170469938888793522433e434
+        let exp_1000 = BigFloatBase2::from(1000).exp().to_bigfloat();
+        let ratio: BigFloat = exp_1000 / BigFloat::new(1.970071114017047, 434);
+        assert!((ratio - BigFloat::from_f64(1.0)).abs() < BigFloat::new(1.0, -14));
+        let root = BigFloatBase2::new(1.0, 10_000).pow(&BigFloatBase2::from_f64(0.5));
+        assert_eq!(root, BigFloatBase2::new(1.0, 5000));
+    }
+
+    #[test]
+    fn test_bigfloat_conversions() {
+        // Decimal values that are binary fractions convert exactly, and
+        // f64-range values round-trip
+        assert_eq!(BigFloatBase2::from(BigFloat::new(1.5, 3)), BigFloatBase2::from_f64(1500.0));
+        assert_eq!(BigFloatBase2::from(BigFloat::new(6.103515625, -5)), BigFloatBase2::new(1.0, -14));
+        for x in [0.1, 1.5e300, -7.0, 2.5e-300] {
+            let decimal = BigFloat::from_f64(x);
+            let binary = BigFloatBase2::from(decimal.clone());
+            let error = (BigFloat::from(binary) - decimal.clone()).abs();
+            assert!(error <= decimal.abs() * BigFloat::from_f64(f64::EPSILON), "{}", x);
+        }
+        assert_eq!(BigFloatBase2::from_f64(1.5e300).to_string(), "1.5e300");
+
+        // Past the f64 range the conversion rounds once
+        let decimal = BigFloat::new(1.5, 1000);
+        let binary = BigFloatBase2::from(decimal.clone());
+        assert_eq!(binary.exponent(), 3322);
+        assert_eq!(binary.to_bigfloat(), decimal);
+
+        let nested = BigFloat::new(1.0, BigFloat::new(1.0, 100));
+        let binary = BigFloatBase2::from(nested.clone());
+        assert_eq!(binary.exponent().depth(), 1);
+        assert!(binary.to_bigfloat() > BigFloat::new(1.0, 100));
+        assert!(BigFloatBase2::from(BigFloat::from_f64(f64::NAN)).is_nan());
+    }
+
+    #[test]
+    fn test_large_exponent_conversions() {
+        // 2^(10^36) = 5.8639449083257281912e301029995663981195213738894724493026
+        let value = BigFloatBase2::new(1.0, 10_i128.pow(36)).to_bigfloat();
+        assert_eq!(value.exponent(), 301029995663981195213738894724493026_i128);
+        assert!((value.mantissa() - 5.863944908325728).abs() < 1e-14, "{}", value);
+        let value = BigFloatBase2::new(1.0, -(10_i128.pow(20))).to_bigfloat();
+        // 2^-(10^20) = 1 / 2.3653176496878716463e30102999566398119521
+        let expected = BigFloat::from_f64(1.0) / BigFloat::new(2.365317649687872, 30102999566398119521_i128);
+        assert_eq!(value.exponent(), expected.exponent());
+        assert!((value.mantissa() - expected.mantissa()).abs() < 1e-14, "{}", value);
+
+        for power in [20, 30, 36] {
+            for exponent in [10_i128.pow(power), 5 * 10_i128.pow(power), -(10_i128.pow(power))] {
+                let decimal = BigFloat::new(1.5, exponent);
+                let back = BigFloatBase2::from(decimal.clone()).to_bigfloat();
+                assert_eq!(back.exponent(), decimal.exponent(), "1.5e{}", exponent);
+                assert!((back.mantissa() - 1.5).abs() < 1e-14, "1.5e{} came back as {}", exponent, back);
+            }
+        }
+    }
+
+    #[test]
+    fn test_api_matches_bigfloat() {
+        let (one, two) = (BigFloatBase2::from_f64(1.0), BigFloatBase2::from_f64(2.0));
+        assert_eq!(BigFloatBase2::from_f64(10.0).exp2(), BigFloatBase2::new(1.0, 10));
+        assert_eq!(BigFloatBase2::new(1.25, 40).exp2().exponent(), 1_374_389_534_720_i128);
+        assert_eq!(BigFloatBase2::from_f64(3.0).exp10(), BigFloatBase2::from_f64(1000.0));
+        assert_eq!(BigFloatBase2::from_f64(-2.0).exp10(), BigFloatBase2::from_f64(0.01));
+
+        assert_eq!(BigFloatBase2::new(-1.5, 100).signum(), -one.clone());
+        assert!(BigFloatBase2::from_f64(-0.0).signum().is_sign_negative());
+        assert_eq!(BigFloatBase2::from_f64(f64::NAN).classify(), Category::Nan);
+        assert_eq!(BigFloatBase2::new(1.0, -5000).classify(), Category::Normal);
+        assert_eq!(one.clone().min(two.clone()), one);
+        assert_eq!(BigFloatBase2::from_f64(f64::NAN).max(two.clone()), two);
+        assert_eq!(BigFloatBase2::from_f64(-0.0).total_cmp(&BigFloatBase2::from_f64(0.0)), Ordering::Less);
+        assert_eq!(BigFloatBase2::new(1.0, 100).total_cmp(&BigFloatBase2::from_f64(f64::NAN)), Ordering::Less);
+
+        // A power of the deepest tower needs one more exponent level than is allowed
+        let mut tower = BigFloat::new(1.0, 100);
+        for _ in 0..crate::exponent::MAX_EXPONENT_DEPTH - 1 {
+            tower = BigFloat::new(1.0, tower);
+        }
+        let tower = BigFloatBase2::new(1.0, tower);
+        assert!(tower.is_finite() && tower.checked_mul(&tower).is_some());
+        assert_eq!(tower.checked_pow(&tower), None);
+        assert!(tower.saturating_pow(&tower).is_infinite());
+        assert_eq!(one.checked_div(&BigFloatBase2::from_f64(0.0)), None);
+        assert_eq!(one.checked_add(&two), Some(BigFloatBase2::from_f64(3.0)));
+        assert_eq!(BigFloatBase2::from_f64(-8.0).checked_pow(&BigFloatBase2::from_f64(0.5)), None);
+
+        let hash_of = |value: &BigFloatBase2| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash_of(&BigFloatBase2::from_f64(0.0)), hash_of(&BigFloatBase2::from_f64(-0.0)));
+        assert_eq!(hash_of(&BigFloatBase2::new(3.0, 1)), hash_of(&BigFloatBase2::new(1.5, 2)));
+        assert!(BigFloatBase2::new(1.0, 60) > 1e18 && 3 < BigFloatBase2::from_f64(3.5));
+        assert!(BigFloatBase2::from_f64(2.0) == 2_i64 && 0.5 == BigFloatBase2::new(1.0, -1));
+
+        // Every 1.0 registers against 2^60 through the compensation
+        let terms = std::iter::once(BigFloatBase2::new(1.0, 60)).chain((0..1000).map(|_| one.clone()));
+        assert_eq!(terms.sum::<BigFloatBase2>(), BigFloatBase2::from_f64(2.0_f64.powi(60) + 1000.0));
+        let factors = [two.clone(), BigFloatBase2::new(1.5, 1000), BigFloatBase2::from_f64(0.25)];
+        assert_eq!(factors.iter().product::<BigFloatBase2>(), BigFloatBase2::new(1.5, 999));
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        assert_eq!(BigFloatBase2::from_f64(0.1).to_string(), "0.1");
+        assert_eq!("0.1".parse::<BigFloatBase2>().unwrap(), BigFloatBase2::from_f64(0.1));
+        assert_eq!("1.5e1000".parse::<BigFloatBase2>().unwrap().to_string(), "1.5e1000");
+        assert_eq!("-inf".parse::<BigFloatBase2>().unwrap().to_string(), "-∞");
+        assert_eq!("x".parse::<BigFloatBase2>(), Err(BigFloatError::InvalidMantissa { offset: 0 }));
+    }
+}
@@ -0,0 +1,698 @@
+use crate::bigfloat::BigFloat;
+use crate::convert::write_digits;
+use crate::double_double::{split_product, DoubleDouble, LN_10, LOG10_E, LOG10_E_FIXED};
+use crate::error::BigFloatError;
+use crate::exponent::Exponent;
+use crate::powers_of_ten::{decade_estimate, integer_value};
+use std::cmp::Ordering;
+use std::fmt;
+use crate::operators::{convert, impl_forwarded_ops};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+const TEN: DoubleDouble = DoubleDouble { hi: 10.0, lo: 0.0 };
+
+// Significant digits printed by `Display`; a double-double carries about 32
+const DISPLAY_DIGITS: usize = 31;
+
+// Digits past this many do not change a double-double mantissa
+const PARSE_DIGITS: usize = 34;
+
+// An operand this many decades below the other cannot affect the sum
+const ADD_DIGITS: i128 = 34;
+
+// `exp` and `pow` take their arguments as plain double-doubles up to this
+// decimal exponent; past it the integer part of the power overflows an i128
+const ARGUMENT_DIGITS: i128 = 37;
+
+// A power that is itself a rounded double-double leaves fewer than 16
+// digits for the fraction from here on, so `pow` hands it to `BigFloat`
+const SPLIT_LIMIT: f64 = 1e16;
+
+/// A floating-point number mantissa × 10^exponent whose mantissa is a
+/// double-double, carrying about 32 significant digits instead of the ~16 of
+/// `BigFloat`.
+///
+/// The mantissa is the unevaluated sum `hi + lo` of two f64 values with
+/// 1 <= |hi| < 10. The exponent works exactly as in `BigFloat`, so both types
+/// have the same range. Zeros, infinities and NaN have `lo = 0` and a
+/// `Long(0)` exponent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BigFloatDD {
+    mantissa: DoubleDouble,
+    exponent: Exponent,
+}
+
+impl BigFloatDD {
+    pub fn new(mantissa: f64, exponent: impl Into<Exponent>) -> Self {
+        BigFloatDD::from_parts(mantissa, 0.0, exponent)
+    }
+
+    /// Builds (hi + lo) × 10^exponent; `hi` and `lo` need not be normalized.
+    pub fn from_parts(hi: f64, lo: f64, exponent: impl Into<Exponent>) -> Self {
+        normalize(DoubleDouble::new(hi, lo), exponent.into())
+    }
+
+    pub fn from_f64(value: f64) -> Self {
+        BigFloatDD::new(value, 0)
+    }
+
+    /// The mantissa as its high and low parts.
+    pub fn mantissa(&self) -> (f64, f64) {
+        (self.mantissa.hi, self.mantissa.lo)
+    }
+
+    pub fn exponent(&self) -> Exponent {
+        self.exponent.clone()
+    }
+
+    pub fn exponent_ref(&self) -> &Exponent {
+        &self.exponent
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa.hi == 0.0
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.mantissa.hi.is_finite()
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.mantissa.hi.is_nan()
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.mantissa.hi.is_infinite()
+    }
+
+    pub fn is_sign_negative(&self) -> bool {
+        self.mantissa.hi.is_sign_negative()
+    }
+
+    pub fn abs(&self) -> BigFloatDD {
+        if self.is_sign_negative() { -self.clone() } else { self.clone() }
+    }
+
+    /// Rounds the mantissa to f64, giving the nearest `BigFloat`.
+    pub fn to_bigfloat(&self) -> BigFloat {
+        BigFloat::new(self.mantissa.to_f64(), self.exponent())
+    }
+
+    /// The nearest f64, rounding once from the full mantissa, or `None`
+    /// beyond the f64 range.
+    pub fn to_f64(&self) -> Option<f64> {
+        match self.exponent.as_i128() {
+            Some(exponent) if self.is_finite() && (-400..=308).contains(&exponent) => {
+                let value = self.mantissa.scale10(exponent as i32).to_f64();
+                value.is_finite().then_some(value)
+            }
+            _ => self.to_bigfloat().to_f64(),
+        }
+    }
+
+    /// Natural logarithm. Special values and nested exponents go through
+    /// `BigFloat`.
+    pub fn ln(&self) -> BigFloatDD {
+        match self.ln_double_double() {
+            Some(ln) => BigFloatDD::from_double_double(ln),
+            None => BigFloatDD::from(self.to_bigfloat().ln()),
+        }
+    }
+
+    /// The exponent plus log10 of the mantissa, with the exponent kept exact
+    /// up to 2^53 and the fraction in the low part, so that `exp10` gives the
+    /// value back. Special values and nested exponents go through `BigFloat`.
+    pub fn log10(&self) -> BigFloatDD {
+        match self.exponent.as_i128() {
+            Some(exponent) if self.is_finite() && self.mantissa.hi > 0.0 => {
+                let fraction = self.mantissa.ln() * LOG10_E;
+                BigFloatDD::from_double_double(DoubleDouble::from_i128(exponent) + fraction)
+            }
+            _ => BigFloatDD::from(self.to_bigfloat().log10()),
+        }
+    }
+
+    /// e^self, computed as 10^(self·log10 e). The integer part of `self`
+    /// is multiplied by log10 e in exact integer arithmetic, so the fraction
+    /// of the power keeps its digits however large the exponent gets.
+    pub fn exp(&self) -> BigFloatDD {
+        self.split_power()
+            .and_then(|(integer, fraction)| {
+                let (power, power_fraction) = split_product(integer, &LOG10_E_FIXED)?;
+                let (carry, fraction) = (power_fraction + fraction * LOG10_E).floor_split();
+                Some(pow10_parts(power.checked_add(carry)?, fraction))
+            })
+            .unwrap_or_else(|| BigFloatDD::from(self.to_bigfloat().exp()))
+    }
+
+    /// 10^self, with the integer part of `self` as the exponent, exactly,
+    /// and the fraction as the mantissa.
+    pub fn exp10(&self) -> BigFloatDD {
+        self.split_power()
+            .map(|(integer, fraction)| pow10_parts(integer, fraction))
+            .unwrap_or_else(|| BigFloatDD::from(self.to_bigfloat().exp10()))
+    }
+
+    /// self^exponent. Integer exponents use exact repeated squaring; others
+    /// go through the log10 domain, and a negative base needs an integer
+    /// exponent (otherwise the result is NaN).
+    pub fn pow(&self, exponent: &BigFloatDD) -> BigFloatDD {
+        let y = exponent.to_double_double().filter(|y| y.is_finite());
+        let integer = y.and_then(|y| match y.floor_split() {
+            (integer, fraction) if fraction.hi == 0.0 => Some(integer),
+            _ => None,
+        });
+        if let Some(n) = integer.and_then(|n| i64::try_from(n).ok()) {
+            return self.powi(n);
+        }
+
+        if self.is_finite() && !self.is_zero() {
+            if let (Some(y), Some(ln)) = (y, self.abs().ln_double_double()) {
+                if self.is_sign_negative() && integer.is_none() {
+                    return BigFloatDD::from_f64(f64::NAN);
+                }
+                if let Some(magnitude) = exp10_double_double(y * ln * LOG10_E) {
+                    let odd = integer.is_some_and(|n| n % 2 != 0);
+                    return if self.is_sign_negative() && odd { -magnitude } else { magnitude };
+                }
+            }
+        }
+        BigFloatDD::from(self.to_bigfloat().pow(&exponent.to_bigfloat()))
+    }
+
+    /// self^n by binary exponentiation.
+    pub fn powi(&self, n: i64) -> BigFloatDD {
+        let mut base = self.clone();
+        let mut remaining = n.unsigned_abs();
+        let mut result = BigFloatDD::from_f64(1.0);
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = &result * &base;
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                base = &base * &base;
+            }
+        }
+        if n < 0 { BigFloatDD::from_f64(1.0) / result } else { result }
+    }
+
+    pub fn sqrt(&self) -> BigFloatDD {
+        match self.exponent.as_i128() {
+            Some(exponent) if self.is_finite() && self.mantissa.hi > 0.0 => {
+                // Halve an even exponent; an odd one moves a decade into the mantissa
+                let odd = exponent.rem_euclid(2);
+                let mantissa = self.mantissa.scale10(odd as i32).sqrt();
+                normalize(mantissa, Exponent::from((exponent - odd) / 2))
+            }
+            _ => BigFloatDD::from(self.to_bigfloat().sqrt()),
+        }
+    }
+
+    fn from_double_double(value: DoubleDouble) -> BigFloatDD {
+        normalize(value, Exponent::Long(0))
+    }
+
+    /// The value as a plain double-double, if its exponent is small enough.
+    fn to_double_double(&self) -> Option<DoubleDouble> {
+        match self.exponent.as_i128() {
+            _ if !self.is_finite() || self.is_zero() => Some(self.mantissa),
+            Some(exponent) if exponent <= ARGUMENT_DIGITS => {
+                // Past -330 the value underflows a double-double
+                let exponent = exponent.max(-330) as i32;
+                Some(self.mantissa.scale10(exponent))
+            }
+            _ => None,
+        }
+    }
+
+    /// The value split into its floor and its fraction in [0, 1), or `None`
+    /// if it is not finite or the floor overflows an `i128`. An integer is
+    /// split exactly even where scaling it into a double-double would round.
+    fn split_power(&self) -> Option<(i128, DoubleDouble)> {
+        let exponent = self.exponent.as_i128()?;
+        let parts = (integer_value(self.mantissa.hi, exponent), integer_value(self.mantissa.lo, exponent));
+        if let (Some(hi), Some(lo)) = parts {
+            return Some((hi.checked_add(lo)?, DoubleDouble::from_f64(0.0)));
+        }
+        let value = self.to_double_double().filter(|value| value.is_finite() && value.hi.abs() < 1e38)?;
+        Some(value.floor_split())
+    }
+
+    /// ln(self) for a finite positive value with an `i128` exponent.
+    fn ln_double_double(&self) -> Option<DoubleDouble> {
+        match self.exponent.as_i128() {
+            Some(exponent) if self.is_finite() && self.mantissa.hi > 0.0 => {
+                Some(self.mantissa.ln() + DoubleDouble::from_i128(exponent) * LN_10)
+            }
+            _ => None,
+        }
+    }
+}
+
+// Scales the mantissa into [1, 10) and canonicalizes the exponent of every
+// `BigFloatDD` result
+fn normalize(mantissa: DoubleDouble, exponent: Exponent) -> BigFloatDD {
+    if mantissa.hi == 0.0 || !mantissa.is_finite() {
+        return special(mantissa.hi);
+    }
+
+    let mut decade = decade_estimate(mantissa.hi.abs());
+    let mut mantissa = mantissa.scale10(-decade);
+    // The estimate can be one decade low, and hi can round onto a power of ten
+    while mantissa.hi.abs() >= 10.0 {
+        mantissa = mantissa / TEN;
+        decade += 1;
+    }
+    while mantissa.hi.abs() < 1.0 {
+        mantissa = mantissa * TEN;
+        decade -= 1;
+    }
+    match (exponent + Exponent::Long(decade as i128)).saturating_canonical(mantissa.hi < 0.0) {
+        Ok(exponent) => BigFloatDD { mantissa, exponent },
+        Err(value) => special(value),
+    }
+}
+
+fn special(value: f64) -> BigFloatDD {
+    BigFloatDD {
+        mantissa: DoubleDouble::from_f64(value),
+        exponent: Exponent::Long(0),
+    }
+}
+
+/// 10^`power` with the integer part of the power as the exponent, or `None`
+/// from `SPLIT_LIMIT` on.
+fn exp10_double_double(power: DoubleDouble) -> Option<BigFloatDD> {
+    if !power.is_finite() || power.hi.abs() >= SPLIT_LIMIT {
+        return None;
+    }
+    let (integer, fraction) = power.floor_split();
+    Some(pow10_parts(integer, fraction))
+}
+
+/// 10^(`integer` + `fraction`) for a fraction in [0, 1).
+fn pow10_parts(integer: i128, fraction: DoubleDouble) -> BigFloatDD {
+    normalize((fraction * LN_10).exp(), Exponent::from(integer))
+}
+
+impl Add for BigFloatDD {
+    type Output = BigFloatDD;
+
+    fn add(self, other: BigFloatDD) -> BigFloatDD {
+        // Special values follow f64, as in `BigFloat`
+        if self.is_zero() && other.is_zero() || !self.is_finite() || !other.is_finite() {
+            return BigFloatDD::from_f64(self.mantissa.hi + other.mantissa.hi);
+        }
+        if self.is_zero() {
+            return other;
+        }
+        if other.is_zero() {
+            return self;
+        }
+
+        let (big, small) = if self.exponent >= other.exponent { (self, other) } else { (other, self) };
+        let difference = (big.exponent() - small.exponent()).as_i128().unwrap_or(i128::MAX);
+        if difference > ADD_DIGITS {
+            return big;
+        }
+        let mantissa = big.mantissa + small.mantissa.scale10(-(difference as i32));
+        normalize(mantissa, big.exponent)
+    }
+}
+
+impl Sub for BigFloatDD {
+    type Output = BigFloatDD;
+
+    fn sub(self, other: BigFloatDD) -> BigFloatDD {
+        self.add(-other)
+    }
+}
+
+impl Mul for BigFloatDD {
+    type Output = BigFloatDD;
+
+    fn mul(self, other: BigFloatDD) -> BigFloatDD {
+        if self.is_zero() || other.is_zero() || !self.is_finite() || !other.is_finite() {
+            return BigFloatDD::from_f64(self.mantissa.hi * other.mantissa.hi);
+        }
+        normalize(self.mantissa * other.mantissa, self.exponent + other.exponent)
+    }
+}
+
+impl Div for BigFloatDD {
+    type Output = BigFloatDD;
+
+    fn div(self, other: BigFloatDD) -> BigFloatDD {
+        if self.is_zero() || other.is_zero() || !self.is_finite() || !other.is_finite() {
+            return BigFloatDD::from_f64(self.mantissa.hi / other.mantissa.hi);
+        }
+        normalize(self.mantissa / other.mantissa, self.exponent - other.exponent)
+    }
+}
+
+impl Neg for BigFloatDD {
+    type Output = BigFloatDD;
+
+    fn neg(self) -> BigFloatDD {
+        BigFloatDD {
+            mantissa: -self.mantissa,
+            exponent: self.exponent,
+        }
+    }
+}
+
+impl Neg for &BigFloatDD {
+    type Output = BigFloatDD;
+
+    fn neg(self) -> BigFloatDD {
+        -self.clone()
+    }
+}
+
+impl_forwarded_ops!(BigFloatDD, convert);
+
+impl PartialOrd for BigFloatDD {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        if !self.is_finite() || !other.is_finite() || self.is_zero() || other.is_zero() {
+            return self.mantissa.hi.partial_cmp(&other.mantissa.hi);
+        }
+        match (self.is_sign_negative(), other.is_sign_negative()) {
+            (false, true) => return Some(Ordering::Greater),
+            (true, false) => return Some(Ordering::Less),
+            _ => {}
+        }
+
+        // Same sign: a larger exponent means a larger magnitude
+        let magnitude = self.exponent.cmp(&other.exponent);
+        match magnitude {
+            Ordering::Equal => self.mantissa.partial_cmp(&other.mantissa),
+            _ if self.is_sign_negative() => Some(magnitude.reverse()),
+            _ => Some(magnitude),
+        }
+    }
+}
+
+impl Default for BigFloatDD {
+    fn default() -> Self {
+        BigFloatDD::from_f64(0.0)
+    }
+}
+
+impl From<BigFloat> for BigFloatDD {
+    fn from(value: BigFloat) -> Self {
+        let (mantissa, exponent) = value.into_parts();
+        normalize(DoubleDouble::from_f64(mantissa), exponent)
+    }
+}
+
+impl From<BigFloatDD> for BigFloat {
+    fn from(value: BigFloatDD) -> Self {
+        value.to_bigfloat()
+    }
+}
+
+impl From<f64> for BigFloatDD {
+    fn from(value: f64) -> Self {
+        BigFloatDD::from_f64(value)
+    }
+}
+
+impl From<i32> for BigFloatDD {
+    fn from(value: i32) -> Self {
+        BigFloatDD::from(value as i64)
+    }
+}
+
+impl From<i64> for BigFloatDD {
+    fn from(value: i64) -> Self {
+        // Exact: a double-double holds 106 bits
+        BigFloatDD::from_double_double(DoubleDouble::from_i128(value as i128))
+    }
+}
+
+impl fmt::Display for BigFloatDD {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() || !self.is_finite() {
+            return write!(f, "{}", self.to_bigfloat());
+        }
+
+        let (digits, carried) = round_digits(self.mantissa.abs(), DISPLAY_DIGITS);
+        let exponent = if carried { self.exponent() + Exponent::Long(1) } else { self.exponent() };
+        write_digits(f, self.is_sign_negative(), &digits, &exponent)
+    }
+}
+
+/// The first `count` digits of a mantissa in [1, 10), rounded half up with
+/// trailing zeros removed, and whether rounding carried into a new decade.
+fn round_digits(mantissa: DoubleDouble, count: usize) -> (String, bool) {
+    let mut digits = Vec::with_capacity(count + 1);
+    let mut rest = mantissa;
+    for _ in 0..=count {
+        let (digit, fraction) = rest.floor_split();
+        digits.push(digit.clamp(0, 9) as u8);
+        rest = fraction * TEN;
+    }
+
+    let mut carried = false;
+    if digits.pop() >= Some(5) {
+        match digits.iter().rposition(|&digit| digit != 9) {
+            Some(position) => {
+                digits[position] += 1;
+                digits[position + 1..].fill(0);
+            }
+            None => {
+                digits.fill(0);
+                digits[0] = 1;
+                carried = true;
+            }
+        }
+    }
+
+    while digits.len() > 1 && digits.last() == Some(&0) {
+        digits.pop();
+    }
+    (digits.iter().map(|digit| char::from(b'0' + digit)).collect(), carried)
+}
+
+impl FromStr for BigFloatDD {
+    type Err = BigFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Plain decimals keep every digit; special values, nested exponents
+        // and errors are left to the `BigFloat` parser
+        match parse_decimal(s.trim()) {
+            Some(value) => Ok(value),
+            None => s.parse::<BigFloat>().map(BigFloatDD::from),
+        }
+    }
+}
+
+/// Parses `[+-]digits[.digits][e[+-]digits]` with an `i128` exponent.
+fn parse_decimal(s: &str) -> Option<BigFloatDD> {
+    let (negative, s) = match s.as_bytes().first()? {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (digits, mut exponent) = match s.find(['e', 'E']) {
+        Some(position) => (&s[..position], s[position + 1..].parse::<i128>().ok()?),
+        None => (s, 0),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let mut mantissa = DoubleDouble::default();
+    let mut significant = 0;
+    for (index, byte) in integer.bytes().chain(fraction.bytes()).enumerate() {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        let in_fraction = index >= integer.len();
+        if significant < PARSE_DIGITS {
+            mantissa = mantissa * TEN + DoubleDouble::from_f64((byte - b'0') as f64);
+            if mantissa.hi != 0.0 {
+                significant += 1;
+            }
+            if in_fraction {
+                exponent = exponent.checked_sub(1)?;
+            }
+        } else if !in_fraction {
+            exponent = exponent.checked_add(1)?;
+        }
+    }
+
+    let value = normalize(mantissa, Exponent::from(exponent));
+    Some(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_normalization() {
+        let value = BigFloatDD::new(1234.5, 10);
+        assert_eq!(value.mantissa().0, 1.2345);
+        assert_eq!(value.exponent(), 13);
+        assert_eq!(value.to_string(), "1.2345e13");
+
+        let tiny = BigFloatDD::from_parts(1.0, 1e-20, -400);
+        assert_eq!(tiny.mantissa(), (1.0, 1e-20));
+        assert_eq!(tiny.exponent(), -400);
+    }
+
+    #[test]
+    fn test_display_and_parse_round_trip() {
+        let third = BigFloatDD::from(1) / BigFloatDD::from(3);
+        assert_eq!(third.to_string(), "0.3333333333333333333333333333333");
+
+        let pi = "3.141592653589793238462643383279";
+        let parsed: BigFloatDD = pi.parse().unwrap();
+        assert_eq!(parsed.to_string(), pi);
+
+        let big: BigFloatDD = "-1.234567890123456789012345678901e1000".parse().unwrap();
+        assert_eq!(big.to_string(), "-1.234567890123456789012345678901e1000");
+        assert_eq!("123456.5".parse::<BigFloatDD>().unwrap().to_string(), "123456.5");
+        assert_eq!("1e6".parse::<BigFloatDD>().unwrap().to_string(), "1000000");
+        assert_eq!("0.000125".parse::<BigFloatDD>().unwrap().to_string(), "0.000125");
+        assert_eq!("-0".parse::<BigFloatDD>().unwrap().to_string(), "-0");
+    }
+
+    #[test]
+    fn test_parse_falls_back_to_bigfloat() {
+        assert!("inf".parse::<BigFloatDD>().unwrap().is_infinite());
+        let nested: BigFloatDD = "1e1e100".parse().unwrap();
+        assert_eq!(nested.to_string(), "1e1e100");
+        assert_eq!("1.2.3".parse::<BigFloatDD>(), Err(BigFloatError::InvalidMantissa { offset: 0 }));
+        assert_eq!("".parse::<BigFloatDD>(), Err(BigFloatError::Empty));
+    }
+
+    #[test]
+    fn test_arithmetic_keeps_low_digits() {
+        let one = BigFloatDD::from(1);
+        let tiny = BigFloatDD::new(1.0, -25);
+        let difference = (&one + &tiny) - &one;
+        assert_eq!(difference.to_bigfloat(), tiny.to_bigfloat());
+        assert!((BigFloat::from(1) + BigFloat::new(1.0, -25) - BigFloat::from(1)).is_zero());
+
+        // 1e20 + 1 is not representable in f64
+        let sum = BigFloatDD::new(1.0, 20) + 1.0;
+        assert_eq!(sum.to_string(), "1.00000000000000000001e20");
+
+        let mut value = BigFloatDD::from(10);
+        value *= 3.0;
+        value /= BigFloatDD::from(4);
+        value -= 0.5;
+        assert_eq!(value, BigFloatDD::from(7));
+    }
+
+    #[test]
+    fn test_special_values() {
+        let zero = BigFloatDD::from(0);
+        assert!((BigFloatDD::from(1) / &zero).is_infinite());
+        assert!((&zero / &zero).is_nan());
+        assert!((-zero.clone() + -zero).is_sign_negative());
+        assert!(BigFloatDD::from(-1).sqrt().is_nan());
+        assert!(BigFloatDD::from(-8).pow(&BigFloatDD::from(0.5)).is_nan());
+        assert!(BigFloatDD::from(1) < BigFloatDD::from_f64(f64::INFINITY));
+        assert!(BigFloatDD::from_f64(f64::NAN).partial_cmp(&BigFloatDD::from(1)).is_none());
+    }
+
+    #[test]
+    fn test_ordering() {
+        let values = [
+            BigFloatDD::new(-2.0, 100),
+            BigFloatDD::new(-1.0, 0),
+            BigFloatDD::from(0),
+            BigFloatDD::from_parts(1.0, 1e-30, 0),
+            BigFloatDD::from_parts(1.0, 2e-30, 0),
+            BigFloatDD::new(1.0, 100),
+        ];
+        for pair in values.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_ln_exp() {
+        let two = BigFloatDD::from(2);
+        let root = two.sqrt();
+        assert_eq!(root.to_string(), "1.41421356237309504880168872421");
+        assert_eq!((&root * &root).to_string(), "2");
+        assert_eq!(BigFloatDD::new(4.0, 100).sqrt().to_string(), "2e50");
+        assert_eq!(BigFloatDD::new(4.0, 101).sqrt().to_string(), "6.324555320336758663997787088865e50");
+
+        assert_eq!(two.ln().to_string(), "0.6931471805599453094172321214582");
+        assert_eq!(BigFloatDD::from(1).exp().to_string(), "2.718281828459045235360287471353");
+        assert_eq!(BigFloatDD::new(1.0, 1000).log10().to_string(), "1000");
+
+        // e^1000 = 1.970071114017046993888879352243e434
+        assert_eq!(BigFloatDD::from(1000).exp().to_string(), "1.970071114017046993888879352243e434");
+        // e^(10^30) = 4.027933523471206587446775252474e434294481903251827651128918916:
+        // the power is split exactly, so the mantissa keeps every digit
+        let exp_huge = BigFloatDD::new(1.0, 30).exp();
+        assert_eq!(exp_huge.to_string(), "4.027933523471206587446775252474e434294481903251827651128918916");
+        let exp_huge = BigFloatDD::new(1.0, 35).exp();
+        assert_eq!(exp_huge.exponent(), 43429448190325182765112891891660508_i128);
+        assert!(exp_huge.to_string().starts_with("1.69605409805208459208454761605"), "{}", exp_huge);
+        let x = BigFloatDD::new(1.5, 20);
+        let round_trip = x.exp().ln();
+        assert!((round_trip - &x).abs() < BigFloatDD::new(1.0, -8));
+    }
+
+    #[test]
+    fn test_log10_exp10_round_trip() {
+        assert_eq!(BigFloatDD::from(3).exp10().to_string(), "1000");
+        assert_eq!(BigFloatDD::from(-2).exp10().to_string(), "0.01");
+        // An integral power is the exponent, exactly
+        let power = BigFloatDD::new(1.0, 35).exp10();
+        assert_eq!(power.exponent(), 10_i128.pow(35));
+        assert_eq!(power.mantissa(), (1.0, 0.0));
+        let half = BigFloatDD::from_parts(1.0, 5e-17, 16).exp10();
+        assert_eq!(half.to_string(), "3.162277660168379331998893544433e10000000000000000");
+
+        // Past 2^53 a plain f64 log10 has no room left for the mantissa
+        for exponent in [1_000_000_000_000_002_i128, (1 << 53) + 5, 1 << 56, -(1 << 55) - 3] {
+            let x = BigFloat::new(1.23456, exponent);
+            let round_trip = BigFloatDD::from(x.clone()).log10().exp10().to_bigfloat();
+            assert_eq!(round_trip.exponent(), x.exponent());
+            assert!((round_trip.mantissa() - 1.23456).abs() < 1e-13, "{}", round_trip);
+        }
+        let x = BigFloat::new(1.23456, 1_000_000_000_000_002_i128);
+        assert_eq!(BigFloat::exp10_dd(&x.log10_dd()), x);
+    }
+
+    #[test]
+    fn test_pow() {
+        let three = BigFloatDD::from(3);
+        assert_eq!(three.pow(&BigFloatDD::from(2)), BigFloatDD::from(9));
+        assert_eq!(BigFloatDD::from(-2).pow(&BigFloatDD::from(3)), BigFloatDD::from(-8));
+        assert_eq!(BigFloatDD::from(2).powi(-2), BigFloatDD::new(2.5, -1));
+        assert_eq!(BigFloatDD::from(2).pow(&BigFloatDD::from(0.5)).to_string(), "1.41421356237309504880168872421");
+        // 2^100 = 1267650600228229401496703205376 exactly
+        assert_eq!(BigFloatDD::from(2).powi(100).to_string(), "1.267650600228229401496703205376e30");
+    }
+
+    #[test]
+    fn test_bigfloat_conversions() {
+        let plain = BigFloat::new(1.5, 1000);
+        let extended = BigFloatDD::from(plain.clone());
+        assert_eq!(extended.mantissa(), (1.5, 0.0));
+        assert_eq!(BigFloat::from(extended), plain);
+
+        let third = BigFloatDD::from(1) / BigFloatDD::from(3);
+        assert_eq!(third.to_bigfloat(), BigFloat::new(10.0 / 3.0, -1));
+        assert_eq!(third.to_f64(), Some(1.0 / 3.0));
+
+        let nested = BigFloat::new(1.0, BigFloat::new(1.0, 100));
+        assert_eq!(BigFloatDD::from(nested.clone()).to_bigfloat(), nested);
+        assert!(BigFloatDD::from(BigFloat::from_f64(f64::NAN)).is_nan());
+    }
+}
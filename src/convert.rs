@@ -1,84 +1,138 @@
 use crate::bigfloat::BigFloat;
+use crate::error::BigFloatError;
+use crate::exponent::Exponent;
+use crate::mantissa::Mantissa;
+use crate::math::pow10;
+use crate::powers_of_ten::scale_pow10;
 use std::fmt;
 use std::str::FromStr;
 
-impl fmt::Display for BigFloat {
+impl<M: Mantissa> fmt::Display for BigFloat<M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.mantissa.is_nan() {
+        let sign_negative = self.mantissa().to_f64().is_sign_negative();
+        if self.is_nan() {
             return write!(f, "NaN");
         }
         
-        if self.mantissa.is_infinite() {
-            return write!(f, "{}", if self.mantissa.is_sign_positive() { "∞" } else { "-∞" });
+        if self.is_infinite() {
+            return write!(f, "{}", if sign_negative { "-∞" } else { "∞" });
         }
         
         if self.is_zero() {
-            return write!(f, "0");
+            return write!(f, "{}", if sign_negative { "-0" } else { "0" });
         }
 
-        if self.exponent == 0 {
-            // For exponent 0, just show the mantissa
-            write!(f, "{}", self.mantissa)
-        } else if self.exponent <= 6 {
-            // Use standard notation for small exponents
-            let value = self.mantissa * 10.0_f64.powi(self.exponent as i32);
-            write!(f, "{}", value)
-        } else {
-            // Use scientific notation for large exponents
-            write!(f, "{}e{}", self.mantissa, self.exponent)
+        match self.exponent_cow().as_i128() {
+            Some(0) => {
+                // For exponent 0, just show the mantissa
+                self.mantissa().format(f)
+            }
+            Some(exponent @ -6..=6) => {
+                // Use standard notation for small exponents
+                self.mantissa().scale10(exponent as i32).format(f)
+            }
+            _ => {
+                // Use scientific notation for large exponents; a BigFloat
+                // exponent prints recursively, e.g. "1e1e100"
+                self.mantissa().format(f)?;
+                write!(f, "e{}", self.exponent_cow())
+            }
         }
     }
 }
 
-impl FromStr for BigFloat {
-    type Err = String;
+/// Writes the significant `digits` (the first has place value 10^exponent)
+/// with the same plain/scientific thresholds as `BigFloat`, for the types
+/// whose mantissa carries more digits than an f64.
+pub(crate) fn write_digits(
+    f: &mut fmt::Formatter<'_>,
+    negative: bool,
+    digits: &str,
+    exponent: &Exponent,
+) -> fmt::Result {
+    let sign = if negative { "-" } else { "" };
+    match exponent.as_i128() {
+        Some(exponent @ 0..=6) => {
+            let point = exponent as usize + 1;
+            if digits.len() <= point {
+                write!(f, "{}{}{}", sign, digits, "0".repeat(point - digits.len()))
+            } else {
+                write!(f, "{}{}.{}", sign, &digits[..point], &digits[point..])
+            }
+        }
+        Some(exponent @ -6..=-1) => {
+            let zeros = "0".repeat((-exponent - 1) as usize);
+            write!(f, "{}0.{}{}", sign, zeros, digits)
+        }
+        _ if digits.len() == 1 => write!(f, "{}{}e{}", sign, digits, exponent),
+        _ => write!(f, "{}{}.{}e{}", sign, &digits[..1], &digits[1..], exponent),
+    }
+}
+
+impl<M: Mantissa> FromStr for BigFloat<M> {
+    type Err = BigFloatError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let offset = s.len() - s.trim_start().len();
         let s = s.trim();
         
         if s.is_empty() {
-            return Err("Empty string".to_string());
+            return Err(BigFloatError::Empty);
         }
 
-        // Handle special cases
-        match s.to_lowercase().as_str() {
-            "nan" => return Ok(BigFloat::from_f64(f64::NAN)),
-            "inf" | "infinity" | "∞" => return Ok(BigFloat::from_f64(f64::INFINITY)),
-            "-inf" | "-infinity" | "-∞" => return Ok(BigFloat::from_f64(f64::NEG_INFINITY)),
-            "0" | "0.0" => return Ok(BigFloat::from_f64(0.0)),
-            _ => {}
-        }
+        parse_at(s, offset)
+    }
+}
 
-        // Try to parse as standard f64 first
-        if let Ok(val) = s.parse::<f64>() {
-            return Ok(BigFloat::from_f64(val));
-        }
+/// Parses a trimmed string that starts at byte `offset` of the original input.
+/// The mantissa is rounded once, straight from its digits, to `M`.
+fn parse_at<M: Mantissa>(s: &str, offset: usize) -> Result<BigFloat<M>, BigFloatError> {
+    // Handle special cases
+    match s.to_lowercase().as_str() {
+        "nan" => return Ok(BigFloat::from_float(f64::NAN)),
+        "inf" | "infinity" | "∞" => return Ok(BigFloat::from_float(f64::INFINITY)),
+        "-inf" | "-infinity" | "-∞" => return Ok(BigFloat::from_float(f64::NEG_INFINITY)),
+        "0" | "0.0" => return Ok(BigFloat::from_float(0.0)),
+        _ => {}
+    }
 
-        // Handle scientific notation with potentially large exponents
-        if let Some(e_pos) = s.to_lowercase().find('e') {
-            let (mantissa_str, exp_str) = s.split_at(e_pos);
-            let exp_str = &exp_str[1..]; // Remove 'e'
-            
-            let mantissa: f64 = mantissa_str.parse()
-                .map_err(|_| format!("Invalid mantissa: {}", mantissa_str))?;
-            
-            // Try to parse exponent as u128
-            if let Ok(exp) = exp_str.parse::<u128>() {
-                return Ok(BigFloat::new(mantissa, exp));
-            }
-            
-            // If that fails, try as i64 and convert using the helper method
-            if let Ok(exp) = exp_str.parse::<i64>() {
-                return Ok(BigFloat::new_from_i64_exponent(mantissa, exp));
-            }
-            
-            
-            return Err(format!("Invalid exponent: {}", exp_str));
+    // Handle scientific notation with potentially large exponents
+    if let Some(e_pos) = s.find(['e', 'E']) {
+        let (mantissa_str, exp_str) = s.split_at(e_pos);
+        let exp_str = &exp_str[1..]; // Remove 'e'
+        let exp_offset = offset + e_pos + 1;
+        
+        let mantissa = M::parse(mantissa_str)
+            .ok_or(BigFloatError::InvalidMantissa { offset })?;
+        
+        if let Ok(exp) = exp_str.parse::<i128>() {
+            return Ok(BigFloat::from_mantissa(mantissa, exp));
         }
+        
+        // Nested exponents such as "1e1e100" parse recursively; an error in
+        // the nested mantissa is an error in this exponent
+        let exp = parse_at::<f64>(exp_str, exp_offset).map_err(|error| match error {
+            BigFloatError::InvalidMantissa { offset } => BigFloatError::InvalidExponent { offset },
+            BigFloatError::Empty => BigFloatError::InvalidExponent { offset: exp_offset },
+            error => error,
+        })?;
+        let value = BigFloat::from_mantissa(mantissa, 0) * pow10(&exp).cast();
 
+        // A finite mantissa only saturates when the exponent nests too deeply
+        let mantissa = mantissa.to_f64();
+        if mantissa.is_finite() && mantissa != 0.0 && (value.is_infinite() || value.is_zero()) {
+            return Err(BigFloatError::ExponentOverflow);
+        }
+        return Ok(value);
+    }
 
-        Err(format!("Unable to parse: {}", s))
+    if s.is_empty() {
+        return Err(BigFloatError::Empty);
     }
+
+    M::parse(s)
+        .map(|mantissa| BigFloat::from_mantissa(mantissa, 0))
+        .ok_or(BigFloatError::InvalidMantissa { offset })
 }
 
 impl From<f64> for BigFloat {
@@ -105,20 +159,34 @@ impl From<i64> for BigFloat {
     }
 }
 
+impl From<u64> for BigFloat {
+    fn from(value: u64) -> Self {
+        BigFloat::from_f64(value as f64)
+    }
+}
+
+impl From<i128> for BigFloat {
+    fn from(value: i128) -> Self {
+        BigFloat::from_f64(value as f64)
+    }
+}
+
 impl BigFloat {
     pub fn to_f64(&self) -> Option<f64> {
-        if self.exponent > 308 {
+        let exponent = self.exponent_ref().to_i128_saturating();
+        if exponent > 308 {
             None // Out of f64 range
         } else {
-            Some(self.mantissa * 10.0_f64.powi(self.exponent as i32))
+            Some(scale_pow10(self.mantissa(), exponent))
         }
     }
 
     pub fn to_f64_saturating(&self) -> f64 {
-        if self.exponent > 308 {
-            if self.mantissa >= 0.0 { f64::INFINITY } else { f64::NEG_INFINITY }
+        let exponent = self.exponent_ref().to_i128_saturating();
+        if exponent > 308 {
+            if self.mantissa() >= 0.0 { f64::INFINITY } else { f64::NEG_INFINITY }
         } else {
-            self.mantissa * 10.0_f64.powi(self.exponent as i32)
+            scale_pow10(self.mantissa(), exponent)
         }
     }
 }
@@ -126,6 +194,7 @@ impl BigFloat {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exponent::Exponent;
 
     #[test]
     fn test_display_basic() {
@@ -140,6 +209,42 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_signed_zero_round_trip() {
+        let bf: BigFloat = "-0".parse().unwrap();
+        assert!(bf.is_zero() && bf.is_sign_negative());
+        assert_eq!(format!("{}", bf), "-0");
+        assert_eq!(bf.to_f64().map(f64::is_sign_negative), Some(true));
+
+        let bf: BigFloat = "-0.0e5".parse().unwrap();
+        assert!(bf.is_sign_negative());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<BigFloat>(), Err(BigFloatError::Empty));
+        assert_eq!("   ".parse::<BigFloat>(), Err(BigFloatError::Empty));
+        assert_eq!("abc".parse::<BigFloat>(), Err(BigFloatError::InvalidMantissa { offset: 0 }));
+        assert_eq!("  1.2.3e5".parse::<BigFloat>(), Err(BigFloatError::InvalidMantissa { offset: 2 }));
+        assert_eq!("1.5ex".parse::<BigFloat>(), Err(BigFloatError::InvalidExponent { offset: 4 }));
+        assert_eq!("1e".parse::<BigFloat>(), Err(BigFloatError::InvalidExponent { offset: 2 }));
+        assert_eq!("1e1e1x".parse::<BigFloat>(), Err(BigFloatError::InvalidExponent { offset: 4 }));
+
+        let error = "1.5ex".parse::<BigFloat>().unwrap_err();
+        assert_eq!(error.to_string(), "invalid exponent at byte 4");
+    }
+
+    #[test]
+    fn test_parse_exponent_overflow() {
+        let deep = format!("1{}100", "e1".repeat(40));
+        assert_eq!(deep.parse::<BigFloat>(), Err(BigFloatError::ExponentOverflow));
+
+        let deep_negative = format!("1e-1{}100", "e1".repeat(40));
+        assert_eq!(deep_negative.parse::<BigFloat>(), Err(BigFloatError::ExponentOverflow));
+
+        assert!("0e1e1e1e100".parse::<BigFloat>().unwrap().is_zero());
+    }
+
     #[test]
     fn test_parse_basic() {
         let bf: BigFloat = "123.45".parse().unwrap();
@@ -193,4 +298,84 @@ mod tests {
         let bf = BigFloat::new(1.23, 15);
         assert_eq!(format!("{}", bf), "1.23e15");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_small_exponent_display() {
+        let bf = BigFloat::from_f64(0.00123);
+        assert_eq!(format!("{}", bf), "0.00123");
+    }
+
+    #[test]
+    fn test_negative_exponent_display() {
+        let bf = BigFloat::new(1.5, -5000);
+        assert_eq!(format!("{}", bf), "1.5e-5000");
+    }
+
+    #[test]
+    fn test_parse_negative_exponent() {
+        let bf: BigFloat = "1e-5000".parse().unwrap();
+        assert!((bf.mantissa() - 1.0).abs() < 1e-10);
+        assert_eq!(bf.exponent(), -5000);
+
+        let bf: BigFloat = "-2.5E-400".parse().unwrap();
+        assert!((bf.mantissa() + 2.5).abs() < 1e-10);
+        assert_eq!(bf.exponent(), -400);
+    }
+
+    #[test]
+    fn test_parse_beyond_f64_range() {
+        let bf: BigFloat = "1.23e456".parse().unwrap();
+        assert!((bf.mantissa() - 1.23).abs() < 1e-10);
+        assert_eq!(bf.exponent(), 456);
+    }
+
+    #[test]
+    fn test_to_f64_small() {
+        let bf = BigFloat::new(1.5, -3);
+        assert_eq!(bf.to_f64().unwrap(), 0.0015);
+
+        let tiny = BigFloat::new(1.0, -1000);
+        assert_eq!(tiny.to_f64().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_parse_nested_exponent() {
+        let bf: BigFloat = "1e1e100".parse().unwrap();
+        assert_eq!(bf.mantissa(), 1.0);
+        assert_eq!(bf.exponent(), Exponent::BigFloat(Box::new(BigFloat::new(1.0, 100))));
+
+        let bf: BigFloat = "1.23e4.56e78".parse().unwrap();
+        assert!((bf.mantissa() - 1.23).abs() < 1e-10);
+        assert_eq!(bf.exponent().to_bigfloat(), BigFloat::new(4.56, 78));
+
+        let bf: BigFloat = "1e-1e100".parse().unwrap();
+        assert!(bf.exponent().is_negative());
+    }
+
+    #[test]
+    fn test_parse_fractional_exponent() {
+        let bf: BigFloat = "1e2.5".parse().unwrap();
+        assert!((bf.mantissa() - 10.0_f64.sqrt()).abs() < 1e-10);
+        assert_eq!(bf.exponent(), 2);
+    }
+
+    #[test]
+    fn test_display_nested_exponent() {
+        let bf: BigFloat = "2e1e100".parse().unwrap();
+        assert_eq!(format!("{}", bf), "2e1e100");
+
+        let bf: BigFloat = "1e-1e100".parse().unwrap();
+        assert_eq!(format!("{}", bf), "1e-1e100");
+
+        let bf: BigFloat = "1e1e1e100".parse().unwrap();
+        assert_eq!(format!("{}", bf), "1e1e1e100");
+    }
+
+    #[test]
+    fn test_to_f64_nested_exponent() {
+        let huge: BigFloat = "1e1e100".parse().unwrap();
+        assert!(huge.to_f64().is_none());
+        let tiny: BigFloat = "1e-1e100".parse().unwrap();
+        assert_eq!(tiny.to_f64(), Some(0.0));
+    }
+}
@@ -0,0 +1,376 @@
+//! Double-double arithmetic: an unevaluated sum `hi + lo` of two f64 values
+//! with |lo| <= ulp(hi) / 2, giving about 106 bits (~32 decimal digits).
+//!
+//! The algorithms follow Hida, Li and Bailey's QD library.
+
+use crate::powers_of_ten::{MAX_EXACT, POWERS};
+use std::cmp::Ordering;
+use std::f64::consts;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct DoubleDouble {
+    pub hi: f64,
+    pub lo: f64,
+}
+
+pub(crate) const LN_10: DoubleDouble = DoubleDouble { hi: consts::LN_10, lo: -2.1707562233822494e-16 };
+pub(crate) const LN_2: DoubleDouble = DoubleDouble { hi: consts::LN_2, lo: 2.3190468138462996e-17 };
+pub(crate) const LOG10_E: DoubleDouble = DoubleDouble { hi: consts::LOG10_E, lo: 1.098319650216765e-17 };
+pub(crate) const LOG10_2: DoubleDouble = DoubleDouble { hi: consts::LOG10_2, lo: -2.8037281277851704e-18 };
+pub(crate) const LOG2_10: DoubleDouble = DoubleDouble { hi: consts::LOG2_10, lo: 1.661617516973592e-16 };
+
+/// A constant as a 256-bit binary fraction followed by its integer part,
+/// least significant word first.
+pub(crate) type Fixed = [u64; 5];
+
+pub(crate) const LOG10_2_FIXED: Fixed = [0xc52f37935be631e5, 0x13569862a1e8f9a4, 0x47c4acd605be48bc, 0x4d104d427de7fbcc, 0];
+pub(crate) const LOG2_10_FIXED: Fixed = [0x46dab2020b9e1674, 0x65b157f8deceb53a, 0x24afdbfd36bf6d33, 0x5269e12f346e2bf9, 3];
+pub(crate) const LOG10_E_FIXED: Fixed = [0x1d1f96a27bc7529e, 0x1f71a30122e4d101, 0x9aadd557d699ee19, 0x6f2dec549b9438ca, 0];
+
+/// `n` × `constant` split into its floor and its fraction in [0, 1), or
+/// `None` if the floor overflows an `i128`.
+///
+/// The product is taken in integer arithmetic, so the fraction is known to
+/// 2^-129 for every `i128`, where a double-double product would have lost
+/// it past about 1e16.
+pub(crate) fn split_product(n: i128, constant: &Fixed) -> Option<(i128, DoubleDouble)> {
+    let magnitude = n.unsigned_abs();
+    let factors = [magnitude as u64, (magnitude >> 64) as u64];
+    let mut product = [0u64; 7];
+    for (i, &factor) in factors.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &word) in constant.iter().enumerate() {
+            let sum = factor as u128 * word as u128 + product[i + j] as u128 + carry;
+            product[i + j] = sum as u64;
+            carry = sum >> 64;
+        }
+        product[i + constant.len()] = carry as u64;
+    }
+
+    if product[6] != 0 {
+        return None;
+    }
+    let mut integer = i128::try_from((product[5] as u128) << 64 | product[4] as u128).ok()?;
+    let mut fraction = [product[0], product[1], product[2], product[3]];
+    if n < 0 && fraction != [0; 4] {
+        // -(i + f) = -(i + 1) + (1 - f), with 1 - f as the 256-bit two's complement
+        integer += 1;
+        let mut carry = true;
+        for word in fraction.iter_mut() {
+            (*word, carry) = (!*word).overflowing_add(carry as u64);
+        }
+    }
+    let integer = if n < 0 { -integer } else { integer };
+
+    // The top 127 bits of the fraction, exact in a double-double up to 2^-106
+    let top = DoubleDouble::from_i128(((fraction[3] as u128) << 63 | (fraction[2] >> 1) as u128) as i128);
+    let scale = 2.0_f64.powi(-127);
+    Some((integer, DoubleDouble::new(top.hi * scale, top.lo * scale)))
+}
+
+/// a + b as a rounded sum and its exact rounding error.
+pub(crate) fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let error = (a - (sum - b_virtual)) + (b - b_virtual);
+    (sum, error)
+}
+
+/// a * b as a rounded product and its exact rounding error.
+pub(crate) fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+// two_sum for |a| >= |b|
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    (sum, b - (sum - a))
+}
+
+impl DoubleDouble {
+    pub fn new(hi: f64, lo: f64) -> Self {
+        if lo == 0.0 {
+            // two_sum would turn -0 into +0
+            return DoubleDouble::from_f64(hi);
+        }
+        let (hi, lo) = two_sum(hi, lo);
+        DoubleDouble { hi, lo }
+    }
+
+    pub fn from_f64(value: f64) -> Self {
+        DoubleDouble { hi: value, lo: 0.0 }
+    }
+
+    /// An `i128` as hi + lo, exact up to 2^106.
+    pub fn from_i128(value: i128) -> Self {
+        let hi = value as f64;
+        let lo = value.wrapping_sub(hi as i128) as f64;
+        DoubleDouble::new(hi, lo)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+
+    pub fn is_finite(self) -> bool {
+        self.hi.is_finite()
+    }
+
+    pub fn abs(self) -> Self {
+        if self.is_sign_negative() { -self } else { self }
+    }
+
+    pub fn is_sign_negative(self) -> bool {
+        self.hi < 0.0 || self.hi == 0.0 && self.lo < 0.0
+    }
+
+    /// Largest integer not above `self`, as the integer and the fraction in [0, 1).
+    pub fn floor_split(self) -> (i128, DoubleDouble) {
+        let hi_floor = self.hi.floor();
+        let integer = if hi_floor == self.hi {
+            // hi is integral, so the fraction lives in lo
+            hi_floor as i128 + self.lo.floor() as i128
+        } else {
+            hi_floor as i128
+        };
+        let mut fraction = self - DoubleDouble::from_i128(integer);
+        let mut integer = integer;
+        if fraction.is_sign_negative() {
+            fraction = fraction + DoubleDouble::from_f64(1.0);
+            integer -= 1;
+        }
+        (integer, fraction)
+    }
+
+    /// `self` × 10^`power`, in steps of at most 10^22 so that every factor is exact.
+    pub fn scale10(self, power: i32) -> Self {
+        const STEP: i32 = MAX_EXACT as i32;
+        let mut value = self;
+        let mut power = power;
+        while power > 0 {
+            let step = power.min(STEP);
+            value = value * DoubleDouble::from_f64(POWERS[step as usize].0);
+            power -= step;
+        }
+        while power < 0 {
+            let step = (-power).min(STEP);
+            value = value / DoubleDouble::from_f64(POWERS[step as usize].0);
+            power += step;
+        }
+        value
+    }
+
+    pub fn sqrt(self) -> Self {
+        if self.hi <= 0.0 || !self.is_finite() {
+            return DoubleDouble::from_f64(self.hi.sqrt());
+        }
+        // One Newton step from the f64 root doubles the precision
+        let root = self.hi.sqrt();
+        let (square, square_error) = two_product(root, root);
+        let residual = (self - DoubleDouble::new(square, square_error)).to_f64();
+        DoubleDouble::new(root, residual / (2.0 * root))
+    }
+
+    /// e^`self`, for arguments well inside the f64 exp range.
+    pub fn exp(self) -> Self {
+        const SQUARINGS: i32 = 10;
+
+        if !self.is_finite() {
+            return DoubleDouble::from_f64(self.hi.exp());
+        }
+
+        // e^x = 2^k · e^r with |r| <= ln 2 / 2, and e^r = (e^(r/1024))^1024
+        let k = (self.hi / LN_2.hi).round();
+        let r = (self - LN_2 * DoubleDouble::from_f64(k)) / DoubleDouble::from_f64(2.0_f64.powi(SQUARINGS));
+
+        // Taylor series for e^r - 1
+        let mut term = r;
+        let mut sum = r;
+        for n in 2..=12 {
+            term = term * r / DoubleDouble::from_f64(n as f64);
+            sum = sum + term;
+            if term.hi.abs() < 1e-34 {
+                break;
+            }
+        }
+
+        // (1 + s)^2 - 1 = 2s + s^2 keeps the small part accurate
+        for _ in 0..SQUARINGS {
+            sum = sum * DoubleDouble::from_f64(2.0) + sum * sum;
+        }
+        let result = sum + DoubleDouble::from_f64(1.0);
+        let scale = 2.0_f64.powi(k as i32);
+        DoubleDouble { hi: result.hi * scale, lo: result.lo * scale }
+    }
+
+    /// Natural logarithm of a positive value.
+    pub fn ln(self) -> Self {
+        if self.hi <= 0.0 || !self.is_finite() {
+            return DoubleDouble::from_f64(self.hi.ln());
+        }
+        if (self.hi - 1.0).abs() < 0.125 {
+            // Near 1 the Newton step's `- 1` cancels; ln x = 2·atanh((x-1)/(x+1))
+            // keeps full relative precision instead
+            let u = (self - DoubleDouble::from_f64(1.0)) / (self + DoubleDouble::from_f64(1.0));
+            let u_squared = u * u;
+            let mut power = u;
+            let mut sum = u;
+            for n in (3..=41).step_by(2) {
+                power = power * u_squared;
+                let term = power / DoubleDouble::from_f64(n as f64);
+                sum = sum + term;
+                if term.hi.abs() < 1e-33 * sum.hi.abs() {
+                    break;
+                }
+            }
+            return sum * DoubleDouble::from_f64(2.0);
+        }
+        // Newton step on e^x = self: x + self·e^-x - 1
+        let x = DoubleDouble::from_f64(self.hi.ln());
+        x + self * (-x).exp() - DoubleDouble::from_f64(1.0)
+    }
+}
+
+impl Add for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn add(self, other: DoubleDouble) -> DoubleDouble {
+        let (sum, error) = two_sum(self.hi, other.hi);
+        let (low_sum, low_error) = two_sum(self.lo, other.lo);
+        let (sum, error) = quick_two_sum(sum, error + low_sum);
+        let (hi, lo) = quick_two_sum(sum, error + low_error);
+        if !hi.is_finite() {
+            return DoubleDouble::from_f64(self.hi + other.hi);
+        }
+        DoubleDouble { hi, lo }
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn sub(self, other: DoubleDouble) -> DoubleDouble {
+        self + -other
+    }
+}
+
+impl Mul for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn mul(self, other: DoubleDouble) -> DoubleDouble {
+        let (product, error) = two_product(self.hi, other.hi);
+        if !product.is_finite() {
+            return DoubleDouble::from_f64(product);
+        }
+        let error = error + (self.hi * other.lo + self.lo * other.hi);
+        let (hi, lo) = quick_two_sum(product, error);
+        DoubleDouble { hi, lo }
+    }
+}
+
+impl Div for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn div(self, other: DoubleDouble) -> DoubleDouble {
+        let first = self.hi / other.hi;
+        if !first.is_finite() || first == 0.0 {
+            return DoubleDouble::from_f64(first);
+        }
+        // Long division, one f64 quotient digit at a time
+        let remainder = self - other * DoubleDouble::from_f64(first);
+        let second = remainder.hi / other.hi;
+        let remainder = remainder - other * DoubleDouble::from_f64(second);
+        let third = remainder.hi / other.hi;
+        let (hi, lo) = quick_two_sum(first, second);
+        DoubleDouble { hi, lo } + DoubleDouble::from_f64(third)
+    }
+}
+
+impl Neg for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn neg(self) -> DoubleDouble {
+        DoubleDouble { hi: -self.hi, lo: -self.lo }
+    }
+}
+
+impl PartialOrd for DoubleDouble {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.hi.partial_cmp(&other.hi)? {
+            Ordering::Equal => self.lo.partial_cmp(&other.lo),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: DoubleDouble, expected: DoubleDouble, tolerance: f64) {
+        let error = (actual - expected).to_f64().abs();
+        assert!(error <= tolerance * expected.to_f64().abs(), "{:?} vs {:?}", actual, expected);
+    }
+
+    #[test]
+    fn test_add_keeps_low_bits() {
+        let one = DoubleDouble::from_f64(1.0);
+        let tiny = DoubleDouble::from_f64(1e-25);
+        let sum = one + tiny;
+        assert_eq!(sum.hi, 1.0);
+        assert_eq!(sum.lo, 1e-25);
+        assert_eq!((sum - one).to_f64(), 1e-25);
+    }
+
+    #[test]
+    fn test_div_and_mul_round_trip() {
+        let third = DoubleDouble::from_f64(1.0) / DoubleDouble::from_f64(3.0);
+        let one = third * DoubleDouble::from_f64(3.0);
+        assert!((one - DoubleDouble::from_f64(1.0)).to_f64().abs() < 1e-31);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let root = DoubleDouble::from_f64(2.0).sqrt();
+        assert!((root * root - DoubleDouble::from_f64(2.0)).to_f64().abs() < 1e-31);
+    }
+
+    #[test]
+    fn test_exp_ln() {
+        let e = DoubleDouble::from_f64(1.0).exp();
+        // e = 2.71828182845904523536028747135266...
+        assert_close(e, DoubleDouble::new(consts::E, 1.4456468917292502e-16), 1e-31);
+        assert_close(LN_10.exp(), DoubleDouble::from_f64(10.0), 1e-31);
+        assert_close(DoubleDouble::from_f64(10.0).ln(), LN_10, 1e-31);
+        assert_close(DoubleDouble::from_f64(-0.5).exp().ln(), DoubleDouble::from_f64(-0.5), 1e-31);
+
+        // ln(1 + 2^-30) keeps its relative precision despite the cancellation
+        let near_one = DoubleDouble::from_f64(1.0 + 2.0_f64.powi(-30)).ln();
+        assert_close(near_one, DoubleDouble::new(9.313225741817976e-10, 2.692645221273596e-28), 1e-31);
+    }
+
+    #[test]
+    fn test_floor_split() {
+        let (integer, fraction) = DoubleDouble::new(3.0, -1e-20).floor_split();
+        assert_eq!(integer, 2);
+        assert!(fraction < DoubleDouble::from_f64(1.0) && fraction.hi == 1.0);
+
+        let (integer, fraction) = DoubleDouble::from_f64(-2.25).floor_split();
+        assert_eq!(integer, -3);
+        assert_eq!(fraction.to_f64(), 0.75);
+
+        let (integer, fraction) = DoubleDouble::new(1e20, 0.5).floor_split();
+        assert_eq!(integer, 100_000_000_000_000_000_000);
+        assert_eq!(fraction.to_f64(), 0.5);
+    }
+
+    #[test]
+    fn test_scale10() {
+        let value = DoubleDouble::from_f64(1.0).scale10(30);
+        assert_close(value, DoubleDouble::new(1e30, -1.9884624838656e13), 1e-31);
+        let back = value.scale10(-30);
+        assert!((back - DoubleDouble::from_f64(1.0)).to_f64().abs() < 1e-31);
+    }
+}
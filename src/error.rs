@@ -0,0 +1,34 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors reported by parsing and by the strict `try_*` operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BigFloatError {
+    /// The input string was empty or only whitespace.
+    Empty,
+    /// The mantissa starting at byte `offset` of the input is not a number.
+    InvalidMantissa { offset: usize },
+    /// The exponent starting at byte `offset` of the input is not a number.
+    InvalidExponent { offset: usize },
+    /// The exponent grew or shrank past what can be represented.
+    ExponentOverflow,
+    /// The operation is undefined for its operands, e.g. ln of a negative.
+    Domain { operation: &'static str },
+    /// A division (or a pole such as 0^-1) by zero.
+    DivisionByZero,
+}
+
+impl fmt::Display for BigFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BigFloatError::Empty => write!(f, "empty string"),
+            BigFloatError::InvalidMantissa { offset } => write!(f, "invalid mantissa at byte {}", offset),
+            BigFloatError::InvalidExponent { offset } => write!(f, "invalid exponent at byte {}", offset),
+            BigFloatError::ExponentOverflow => write!(f, "exponent out of range"),
+            BigFloatError::Domain { operation } => write!(f, "domain error: {}", operation),
+            BigFloatError::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl Error for BigFloatError {}
@@ -0,0 +1,462 @@
+use crate::bigfloat::BigFloat;
+use crate::error::BigFloatError;
+use crate::powers_of_ten::{integer_value, scale_pow10};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+
+/// Maximum nesting depth of `Exponent::BigFloat`.
+///
+/// A value whose exponent would need more levels saturates to ±∞ (or to ±0
+/// for huge negative exponents) instead of growing without bound.
+pub const MAX_EXPONENT_DEPTH: usize = 32;
+
+// Exponents of magnitude 1e38 or more are kept as a BigFloat. The bound sits
+// just inside the i128 range so that promotion and demotion agree exactly.
+const LONG_LIMIT: f64 = 1e38;
+
+/// Decimal exponent of a `BigFloat`.
+///
+/// Exponents that fit in an `i128` are stored inline. Anything larger is
+/// promoted to a boxed `BigFloat`, which is how values such as 10^(10^100)
+/// are represented.
+///
+/// Each exponent has exactly one form, which comparisons and hashing rely
+/// on, so outside this crate the variants are only built through `From`,
+/// `from_i128`, `from_bigfloat` or `try_from_bigfloat`. They are read with
+/// `as_i128`/`to_bigfloat`, or matched as `Exponent::Long { 0: n, .. }`.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum Exponent {
+    #[non_exhaustive]
+    Long(i128),
+    #[non_exhaustive]
+    BigFloat(Box<BigFloat>),
+}
+
+impl Exponent {
+    /// Builds an exponent from a `BigFloat`, demoting it to `Long` when it
+    /// fits. A fractional value is floored (rounded toward -∞), so 2.5 and
+    /// -2.5 give 2 and -3; `try_from_bigfloat` rejects one instead.
+    pub fn from_bigfloat(value: BigFloat) -> Exponent {
+        match floor_to_i128(&value) {
+            Some(n) => Exponent::Long(n),
+            None => Exponent::BigFloat(Box::new(value)),
+        }
+    }
+
+    /// Builds an exponent from an integral, finite `BigFloat`, or reports a
+    /// `Domain` error for a fractional value or NaN and `ExponentOverflow`
+    /// for an infinity.
+    pub fn try_from_bigfloat(value: BigFloat) -> Result<Exponent, BigFloatError> {
+        if value.is_nan() {
+            return Err(BigFloatError::Domain { operation: "exponent from NaN" });
+        }
+        if value.is_infinite() {
+            return Err(BigFloatError::ExponentOverflow);
+        }
+        if !is_integral(&value) {
+            return Err(BigFloatError::Domain { operation: "exponent from a fractional value" });
+        }
+        Ok(Exponent::from_bigfloat(value))
+    }
+
+    /// Builds an exponent from an `i128`, promoting it to a `BigFloat` when
+    /// its magnitude reaches 1e38.
+    pub fn from_i128(n: i128) -> Exponent {
+        if (n as f64).abs() < LONG_LIMIT {
+            Exponent::Long(n)
+        } else {
+            Exponent::BigFloat(Box::new(BigFloat::from(n)))
+        }
+    }
+
+    pub fn to_bigfloat(&self) -> BigFloat {
+        match self {
+            Exponent::Long(n) => BigFloat::from(*n),
+            Exponent::BigFloat(value) => (**value).clone(),
+        }
+    }
+
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Exponent::Long(n) => Some(*n),
+            Exponent::BigFloat(_) => None,
+        }
+    }
+
+    /// The exponent as an `i128`, clamped to `i128::MIN`/`i128::MAX` when it
+    /// has been promoted to a `BigFloat`.
+    pub fn to_i128_saturating(&self) -> i128 {
+        match self {
+            Exponent::Long(n) => *n,
+            Exponent::BigFloat(value) => {
+                if value.is_sign_negative() { i128::MIN } else { i128::MAX }
+            }
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Exponent::Long(n) => *n as f64,
+            Exponent::BigFloat(value) => {
+                if value.is_sign_negative() { f64::NEG_INFINITY } else { f64::INFINITY }
+            }
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        match self {
+            Exponent::Long(n) => *n < 0,
+            Exponent::BigFloat(value) => value.is_sign_negative(),
+        }
+    }
+
+    /// Number of nested `BigFloat` exponents below this one (0 for `Long`).
+    pub fn depth(&self) -> usize {
+        match self {
+            Exponent::Long(_) => 0,
+            Exponent::BigFloat(value) => 1 + value.exponent_ref().depth(),
+        }
+    }
+
+    /// The canonical exponent of a finite non-zero value with the given sign,
+    /// or `Err` with the signed zero, infinity or NaN that the value
+    /// saturates to, exactly as `BigFloat` treats its own exponents. The
+    /// other number types canonicalize their exponents through this.
+    pub(crate) fn saturating_canonical(self, negative: bool) -> Result<Exponent, f64> {
+        let anchor = BigFloat::new(if negative { -1.0 } else { 1.0 }, self);
+        if anchor.is_zero() || !anchor.is_finite() {
+            return Err(anchor.mantissa());
+        }
+        Ok(anchor.into_parts().1)
+    }
+
+    /// Demotes a `BigFloat` exponent that fits in an `i128`, and promotes a
+    /// `Long` one that is too large.
+    pub(crate) fn canonical(self) -> Exponent {
+        match self {
+            Exponent::Long(n) => Exponent::from_i128(n),
+            Exponent::BigFloat(value) if value.is_finite() => Exponent::from_bigfloat(*value),
+            Exponent::BigFloat(_) => self,
+        }
+    }
+}
+
+fn floor_to_i128(value: &BigFloat) -> Option<i128> {
+    if !value.is_finite() {
+        return None;
+    }
+    match value.exponent_ref() {
+        Exponent::Long(exponent) if *exponent <= 38 => {
+            // An integral value such as 1e30 keeps every digit, where the
+            // f64 product below would round it to the nearest double
+            if let Some(integer) = integer_value(value.mantissa(), *exponent) {
+                return Some(integer).filter(|integer| (*integer as f64).abs() < LONG_LIMIT);
+            }
+            let floored = scale_pow10(value.mantissa(), *exponent).floor();
+            if floored.abs() < LONG_LIMIT {
+                Some(floored as i128)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether a finite `value` is a whole number. A mantissa in [1, 10) has at
+/// most 52 binary fraction digits, and as 5^e is odd, m × 10^e is whole
+/// exactly when m × 2^e is.
+fn is_integral(value: &BigFloat) -> bool {
+    if value.is_zero() {
+        return true;
+    }
+    match value.exponent_ref() {
+        Exponent::Long(exponent) if *exponent < 0 => false,
+        Exponent::Long(exponent) if *exponent < 52 => (value.mantissa() * 2.0_f64.powi(*exponent as i32)).fract() == 0.0,
+        exponent => !exponent.is_negative(),
+    }
+}
+
+impl Add for Exponent {
+    type Output = Exponent;
+
+    fn add(self, other: Exponent) -> Exponent {
+        if let (Exponent::Long(a), Exponent::Long(b)) = (&self, &other) {
+            if let Some(sum) = a.checked_add(*b) {
+                return Exponent::from_i128(sum);
+            }
+        }
+        // Promote on overflow
+        Exponent::from_bigfloat(self.to_bigfloat() + other.to_bigfloat())
+    }
+}
+
+impl Sub for Exponent {
+    type Output = Exponent;
+
+    fn sub(self, other: Exponent) -> Exponent {
+        if let (Exponent::Long(a), Exponent::Long(b)) = (&self, &other) {
+            if let Some(difference) = a.checked_sub(*b) {
+                return Exponent::from_i128(difference);
+            }
+        }
+        Exponent::from_bigfloat(self.to_bigfloat() - other.to_bigfloat())
+    }
+}
+
+impl Neg for Exponent {
+    type Output = Exponent;
+
+    fn neg(self) -> Exponent {
+        match self {
+            Exponent::Long(n) => match n.checked_neg() {
+                Some(negated) => Exponent::from_i128(negated),
+                None => Exponent::from_bigfloat(-BigFloat::from(n)),
+            },
+            Exponent::BigFloat(value) => Exponent::BigFloat(Box::new(-*value)),
+        }
+    }
+}
+
+impl PartialOrd for Exponent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for Exponent {}
+
+impl Ord for Exponent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Exponent::Long(a), Exponent::Long(b)) => a.cmp(b),
+            // A promoted exponent is always beyond the i128 range
+            (Exponent::Long(_), Exponent::BigFloat(b)) => {
+                if b.is_sign_negative() { Ordering::Greater } else { Ordering::Less }
+            }
+            (Exponent::BigFloat(a), Exponent::Long(_)) => {
+                if a.is_sign_negative() { Ordering::Less } else { Ordering::Greater }
+            }
+            // Nested exponents are finite, so they always compare
+            (Exponent::BigFloat(a), Exponent::BigFloat(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+impl PartialEq<i128> for Exponent {
+    fn eq(&self, other: &i128) -> bool {
+        self.as_i128() == Some(*other)
+    }
+}
+
+impl fmt::Display for Exponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exponent::Long(n) => write!(f, "{}", n),
+            Exponent::BigFloat(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl Default for Exponent {
+    fn default() -> Self {
+        Exponent::Long(0)
+    }
+}
+
+macro_rules! impl_exponent_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Exponent {
+                fn from(value: $t) -> Self {
+                    Exponent::from_i128(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_exponent_from_int!(i32, i64, i128, u32, u64);
+
+impl From<u128> for Exponent {
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(n) => Exponent::from_i128(n),
+            Err(_) => Exponent::from_bigfloat(BigFloat::from_f64(value as f64)),
+        }
+    }
+}
+
+impl From<BigFloat> for Exponent {
+    fn from(value: BigFloat) -> Self {
+        Exponent::from_bigfloat(value)
+    }
+}
+
+/// How a `BigFloat` keeps its exponent in memory; the mantissa type picks
+/// the storage through `Mantissa::StoredExponent`.
+pub trait ExponentStorage: Clone + PartialEq + fmt::Debug {
+    /// Stores a canonical exponent.
+    fn store(exponent: Exponent) -> Self;
+
+    /// The stored exponent, borrowed when it is kept as an `Exponent`.
+    fn load(&self) -> Cow<'_, Exponent>;
+
+    fn into_exponent(self) -> Exponent;
+}
+
+impl ExponentStorage for Exponent {
+    fn store(exponent: Exponent) -> Self {
+        exponent
+    }
+
+    fn load(&self) -> Cow<'_, Exponent> {
+        Cow::Borrowed(self)
+    }
+
+    fn into_exponent(self) -> Exponent {
+        self
+    }
+}
+
+/// Exponent storage of `BigFloat<f32>`: an `i64` inline and anything wider
+/// boxed. The `i128` of `Exponent` forces 16-byte alignment and 32 bytes of
+/// storage; this takes 16, so a `BigFloat<f32>` is half the size of a
+/// `BigFloat<f64>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactExponent(Compact);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Compact {
+    Short(i64),
+    Wide(Box<Exponent>),
+}
+
+impl ExponentStorage for CompactExponent {
+    fn store(exponent: Exponent) -> Self {
+        match exponent.as_i128().map(i64::try_from) {
+            Some(Ok(n)) => CompactExponent(Compact::Short(n)),
+            _ => CompactExponent(Compact::Wide(Box::new(exponent))),
+        }
+    }
+
+    fn load(&self) -> Cow<'_, Exponent> {
+        match &self.0 {
+            Compact::Short(n) => Cow::Owned(Exponent::Long(*n as i128)),
+            Compact::Wide(exponent) => Cow::Borrowed(exponent),
+        }
+    }
+
+    fn into_exponent(self) -> Exponent {
+        match self.0 {
+            Compact::Short(n) => Exponent::Long(n as i128),
+            Compact::Wide(exponent) => *exponent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bigfloat_demotes() {
+        let exponent = Exponent::from_bigfloat(BigFloat::new(1.0, 20));
+        assert_eq!(exponent, Exponent::Long(100_000_000_000_000_000_000));
+
+        let exponent = Exponent::from_bigfloat(BigFloat::new(1.0, 30));
+        assert_eq!(exponent, Exponent::Long(10_i128.pow(30)));
+        let exponent = Exponent::from_bigfloat(BigFloat::new(1.5, 37));
+        assert_eq!(exponent, Exponent::Long(15 * 10_i128.pow(36)));
+
+        let exponent = Exponent::from_bigfloat(BigFloat::new(1.0, 100));
+        assert_eq!(exponent.depth(), 1);
+        assert!(exponent.as_i128().is_none());
+    }
+
+    #[test]
+    fn test_from_bigfloat_floors_and_try_rejects_fractions() {
+        assert_eq!(Exponent::from_bigfloat(BigFloat::from_f64(2.5)), Exponent::from(2));
+        assert_eq!(Exponent::from_bigfloat(BigFloat::from_f64(-2.5)), Exponent::from(-3));
+
+        assert_eq!(Exponent::try_from_bigfloat(BigFloat::from_f64(-3.0)), Ok(Exponent::from(-3)));
+        assert_eq!(Exponent::try_from_bigfloat(BigFloat::new(1.5, 37)), Ok(Exponent::from(15 * 10_i128.pow(36))));
+        assert_eq!(Exponent::try_from_bigfloat(BigFloat::new(1.0, 100)).unwrap().depth(), 1);
+        assert_eq!(Exponent::try_from_bigfloat(BigFloat::from_f64(0.0)), Ok(Exponent::from(0)));
+        // 1 + 2^-52 needs all 52 fraction bits, so × 10^51 is still fractional
+        let fine = 1.0 + f64::EPSILON;
+        assert!(Exponent::try_from_bigfloat(BigFloat::new(fine, 51)).is_err());
+        assert!(Exponent::try_from_bigfloat(BigFloat::new(fine, 52)).is_ok());
+
+        for value in [BigFloat::from_f64(2.5), BigFloat::new(1.0, -100), BigFloat::from_f64(f64::NAN)] {
+            assert!(matches!(Exponent::try_from_bigfloat(value), Err(BigFloatError::Domain { .. })));
+        }
+        assert_eq!(
+            Exponent::try_from_bigfloat(BigFloat::from_f64(f64::INFINITY)),
+            Err(BigFloatError::ExponentOverflow)
+        );
+    }
+
+    #[test]
+    fn test_constructors_are_canonical() {
+        // Equal exponents built either way compare and hash alike
+        let large = Exponent::from_i128(10_i128.pow(38));
+        assert_eq!(large.depth(), 1);
+        assert_eq!(large, Exponent::from_bigfloat(BigFloat::new(1.0, 38)));
+        assert_eq!(large.cmp(&Exponent::from_i128(10_i128.pow(37))), Ordering::Greater);
+        assert_eq!(Exponent::from(u128::MAX).cmp(&Exponent::from(i128::MAX)), Ordering::Greater);
+        assert_eq!(Exponent::from_i128(-(10_i128.pow(38))).cmp(&Exponent::from(i64::MIN)), Ordering::Less);
+    }
+
+    #[test]
+    fn test_add_promotes_on_overflow() {
+        let sum = Exponent::Long(i128::MAX) + Exponent::Long(i128::MAX);
+        match sum {
+            Exponent::BigFloat(value) => {
+                assert!((value.mantissa() - 3.402823669209385).abs() < 1e-10);
+                assert_eq!(value.exponent(), 38);
+            }
+            Exponent::Long(_) => panic!("expected promotion"),
+        }
+    }
+
+    #[test]
+    fn test_sub_demotes() {
+        let big = Exponent::from_bigfloat(BigFloat::new(2.0, 40));
+        let difference = big.clone() - big;
+        assert_eq!(difference, Exponent::Long(0));
+    }
+
+    #[test]
+    fn test_ordering_across_boundary() {
+        let long = Exponent::Long(i128::MAX);
+        let big = Exponent::from_bigfloat(BigFloat::new(1.0, 100));
+        let negative_big = -big.clone();
+        assert!(long < big);
+        assert!(negative_big < Exponent::Long(i128::MIN));
+        assert!(negative_big < big);
+    }
+
+    #[test]
+    fn test_compact_storage() {
+        assert_eq!(std::mem::size_of::<CompactExponent>(), 16);
+        let exponents = [
+            Exponent::Long(-5),
+            Exponent::Long(i64::MAX as i128 + 1),
+            Exponent::from_bigfloat(BigFloat::new(1.0, 100)),
+        ];
+        for exponent in exponents {
+            let stored = CompactExponent::store(exponent.clone());
+            assert_eq!(*stored.load(), exponent);
+            assert_eq!(stored.into_exponent(), exponent);
+        }
+    }
+
+    #[test]
+    fn test_neg_min() {
+        let negated = -Exponent::Long(i128::MIN);
+        assert!(negated > Exponent::Long(i128::MAX));
+    }
+}
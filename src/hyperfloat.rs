@@ -0,0 +1,1115 @@
+use crate::bigfloat::BigFloat;
+use crate::error::BigFloatError;
+use crate::exponent::{Exponent, MAX_EXPONENT_DEPTH};
+use crate::math::{lambert_w, pow10};
+use std::cmp::Ordering;
+use std::f64::consts::{LN_10, LOG10_E};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+// Magnitudes at or above this move up a layer
+const EXP_LIMIT: f64 = 9e15;
+// log10(EXP_LIMIT); magnitudes of a layer above 0 below this move down a layer
+const LAYER_DOWN: f64 = 15.954242509439325;
+// 1 / EXP_LIMIT; layer 0 magnitudes below this move up to a negative layer 1
+const FIRST_NEG_LAYER: f64 = 1.0 / 9e15;
+// e^(1/e), the largest base whose infinite tower converges
+const CONVERGENT_BASE: f64 = 1.444667861009766;
+
+/// A number stored as a tower of powers of ten, for values far beyond what
+/// `BigFloat` can reach (e.g. 10^^1000000).
+///
+/// The value is `sign * 10^10^...^mag` with `layer` applications of `10^`.
+/// A negative `mag` above layer 0 puts the sign on the outermost exponent, so
+/// layer 2 with mag -20 is 10^-(10^20). Normalization keeps `mag` below 9e15
+/// and moves down a layer whenever that loses nothing.
+///
+/// A value converted from a `BigFloat` keeps that `BigFloat` as well, so
+/// converting back gives it unchanged and comparisons between two such
+/// values are exact; results of arithmetic carry only the tower.
+#[derive(Debug, Clone)]
+pub struct HyperFloat {
+    sign: i8,
+    layer: u64,
+    mag: f64,
+    exact: Option<BigFloat>,
+}
+
+impl HyperFloat {
+    pub fn new(sign: i8, layer: u64, mag: f64) -> Self {
+        let mut hf = HyperFloat {
+            sign: sign.signum(),
+            layer,
+            mag,
+            exact: None,
+        };
+        hf.normalize();
+        hf
+    }
+
+    pub fn from_f64(value: f64) -> Self {
+        if value.is_nan() {
+            return HyperFloat::nan();
+        }
+        HyperFloat::new(if value.is_sign_negative() { -1 } else { 1 }, 0, value.abs())
+    }
+
+    /// 10↑↑`height` for a non-negative integer height.
+    pub fn tower(height: u64) -> Self {
+        if height == 0 {
+            return HyperFloat::from_f64(1.0);
+        }
+        HyperFloat::new(1, height, 1.0)
+    }
+
+    fn zero() -> Self {
+        HyperFloat { sign: 0, layer: 0, mag: 0.0, exact: None }
+    }
+
+    fn nan() -> Self {
+        HyperFloat { sign: 0, layer: 0, mag: f64::NAN, exact: None }
+    }
+
+    fn infinity(sign: i8) -> Self {
+        HyperFloat { sign, layer: 0, mag: f64::INFINITY, exact: None }
+    }
+
+    fn normalize(&mut self) {
+        if self.mag.is_nan() {
+            *self = HyperFloat::nan();
+            return;
+        }
+
+        if self.mag.is_infinite() {
+            *self = if self.mag > 0.0 || self.layer == 0 {
+                HyperFloat::infinity(if self.mag > 0.0 { self.sign } else { -self.sign })
+            } else {
+                // 10^-∞
+                HyperFloat::zero()
+            };
+            return;
+        }
+
+        if self.sign == 0 || (self.layer == 0 && self.mag == 0.0) {
+            *self = HyperFloat::zero();
+            return;
+        }
+
+        if self.layer == 0 && self.mag < 0.0 {
+            self.mag = -self.mag;
+            self.sign = -self.sign;
+        }
+
+        if self.layer == 0 && self.mag < FIRST_NEG_LAYER {
+            self.layer = 1;
+            self.mag = self.mag.log10();
+            return;
+        }
+
+        let mut abs_mag = self.mag.abs();
+        let mut sign_mag = self.mag.signum();
+
+        if abs_mag >= EXP_LIMIT {
+            self.layer = self.layer.saturating_add(1);
+            self.mag = sign_mag * abs_mag.log10();
+            return;
+        }
+
+        while abs_mag < LAYER_DOWN && self.layer > 0 {
+            self.layer -= 1;
+            if self.layer == 0 {
+                self.mag = 10.0_f64.powf(self.mag);
+            } else {
+                self.mag = sign_mag * 10.0_f64.powf(abs_mag);
+                abs_mag = self.mag.abs();
+                sign_mag = self.mag.signum();
+            }
+        }
+    }
+
+    pub fn sign(&self) -> i8 {
+        self.sign
+    }
+
+    pub fn layer(&self) -> u64 {
+        self.layer
+    }
+
+    pub fn mag(&self) -> f64 {
+        self.mag
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.sign == 0 && self.mag == 0.0
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.mag.is_nan()
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.mag.is_infinite()
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.mag.is_finite()
+    }
+
+    pub fn abs(&self) -> HyperFloat {
+        HyperFloat {
+            sign: self.sign.abs(),
+            exact: self.exact.as_ref().map(BigFloat::abs),
+            ..*self
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let sign = self.sign as f64;
+        match self.layer {
+            0 => sign * self.mag,
+            1 => sign * 10.0_f64.powf(self.mag),
+            _ => {
+                if self.mag > 0.0 { sign * f64::INFINITY } else { sign * 0.0 }
+            }
+        }
+    }
+
+    /// The value as a `BigFloat`, or `None` when it needs more nested
+    /// exponents than `BigFloat` allows.
+    pub fn to_bigfloat(&self) -> Option<BigFloat> {
+        if self.is_nan() {
+            return Some(BigFloat::from_f64(f64::NAN));
+        }
+        if let Some(exact) = &self.exact {
+            return Some(exact.clone());
+        }
+        if self.layer as usize > MAX_EXPONENT_DEPTH + 2 {
+            return None;
+        }
+
+        let magnitude = if self.layer == 0 {
+            BigFloat::from_f64(self.mag)
+        } else if self.layer == 1 {
+            layer_one_to_bigfloat(self.mag)
+        } else {
+            // 10^log10|self|, where the log is one layer down
+            let below = self.abs().log10().to_bigfloat()?;
+            if !below.is_finite() {
+                return None;
+            }
+            pow10(&below)
+        };
+
+        if self.is_finite() && !magnitude.is_finite() {
+            return None;
+        }
+        Some(if self.sign < 0 { -magnitude } else { magnitude })
+    }
+
+    /// Compares absolute values.
+    pub fn cmp_abs(&self, other: &HyperFloat) -> Ordering {
+        if let (Some(a), Some(b)) = (&self.exact, &other.exact) {
+            return a.abs().partial_cmp(&b.abs()).unwrap_or(Ordering::Equal);
+        }
+        match (self.is_zero(), other.is_zero()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            _ => {}
+        }
+        match (self.is_infinite(), other.is_infinite()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Greater,
+            (false, true) => return Ordering::Less,
+            _ => {}
+        }
+
+        // Negative magnitudes are tiny values, which get smaller as the layer grows
+        let self_layer = if self.mag >= 0.0 { self.layer as i128 } else { -(self.layer as i128) };
+        let other_layer = if other.mag >= 0.0 { other.layer as i128 } else { -(other.layer as i128) };
+        self_layer
+            .cmp(&other_layer)
+            .then(self.mag.partial_cmp(&other.mag).unwrap_or(Ordering::Equal))
+    }
+
+    /// log10 of a non-negative value as a plain f64; only called on layers 0 and 1.
+    fn log10_f64(&self) -> f64 {
+        if self.layer == 0 { self.mag.log10() } else { self.mag }
+    }
+
+    pub fn log10(&self) -> HyperFloat {
+        if self.is_nan() || self.sign < 0 {
+            return HyperFloat::nan();
+        }
+        if self.is_zero() {
+            return HyperFloat::from_f64(f64::NEG_INFINITY);
+        }
+        if self.is_infinite() {
+            return self.clone();
+        }
+
+        if self.layer == 0 {
+            HyperFloat::from_f64(self.mag.log10())
+        } else {
+            let sign = if self.mag < 0.0 { -1 } else { 1 };
+            HyperFloat::new(sign, self.layer - 1, self.mag.abs())
+        }
+    }
+
+    pub fn ln(&self) -> HyperFloat {
+        self.log10() * HyperFloat::from_f64(LN_10)
+    }
+
+    /// 10^self
+    pub fn exp10(&self) -> HyperFloat {
+        if self.is_nan() {
+            return HyperFloat::nan();
+        }
+        if self.is_zero() {
+            return HyperFloat::from_f64(1.0);
+        }
+        if self.is_infinite() {
+            return if self.sign > 0 { self.clone() } else { HyperFloat::zero() };
+        }
+
+        if self.layer == 0 {
+            HyperFloat::new(1, 1, self.sign as f64 * self.mag)
+        } else if self.mag < 0.0 {
+            // |self| is far below f64 precision around 1
+            HyperFloat::from_f64(1.0)
+        } else {
+            HyperFloat::new(1, self.layer + 1, self.sign as f64 * self.mag)
+        }
+    }
+
+    pub fn exp(&self) -> HyperFloat {
+        (self.clone() * HyperFloat::from_f64(LOG10_E)).exp10()
+    }
+
+    pub fn pow(&self, exponent: &HyperFloat) -> HyperFloat {
+        if self.is_nan() || exponent.is_nan() {
+            return HyperFloat::nan();
+        }
+        if exponent.is_zero() {
+            return HyperFloat::from_f64(1.0);
+        }
+        if self.is_zero() {
+            return if exponent.sign > 0 { HyperFloat::zero() } else { HyperFloat::infinity(1) };
+        }
+
+        let magnitude = (self.abs().log10() * exponent.clone()).exp10();
+        if self.sign > 0 {
+            return magnitude;
+        }
+
+        // Negative bases only have real powers for integer exponents; anything
+        // beyond 2^53 is an even integer
+        let sign = match exponent.layer {
+            0 if exponent.mag.fract() != 0.0 => return HyperFloat::nan(),
+            0 if exponent.mag < 9007199254740992.0 && exponent.mag % 2.0 == 1.0 => -1,
+            1 if exponent.mag < 0.0 => return HyperFloat::nan(),
+            _ => 1,
+        };
+        HyperFloat { sign, ..magnitude }
+    }
+
+    pub fn sqrt(&self) -> HyperFloat {
+        self.pow(&HyperFloat::from_f64(0.5))
+    }
+
+    /// Tetration: `self`↑↑`height`, a tower of `height` copies of `self`.
+    ///
+    /// Real heights use the linear approximation, where `b↑↑h = 1 + h` for
+    /// `-1 < h <= 0` and `b↑↑(h + 1) = b^(b↑↑h)`; heights in (-2, -1] follow
+    /// from the same recurrence run backwards. The base must be positive or
+    /// zero; a zero tower alternates 1, 0, 1, … as in `up_arrow`, with
+    /// `0↑↑h = 0^(0↑↑(h - 1))` taking the parity of `ceil(h)` for positive
+    /// heights.
+    ///
+    /// The tower is built one step at a time until it converges, starts to
+    /// only add layers or reaches `MAX_ITERATIONS` steps. Bases up to
+    /// e^(1/e) then give the limit b↑↑∞ = -W(-ln b)/ln b; others give NaN,
+    /// which only happens just above e^(1/e), where a tower crawls past e.
+    pub fn tetrate(&self, height: f64) -> HyperFloat {
+        const MAX_ITERATIONS: u64 = 100_000;
+
+        if self.is_nan() || height.is_nan() || self.sign < 0 || height < -2.0 {
+            return HyperFloat::nan();
+        }
+        if self.is_zero() && height > 0.0 {
+            // An infinite tower of zeros never settles on 1 or 0
+            if height.is_infinite() {
+                return HyperFloat::nan();
+            }
+            return HyperFloat::from_f64(if height.ceil() % 2.0 == 0.0 { 1.0 } else { 0.0 });
+        }
+        if height <= -1.0 {
+            if self.is_zero() {
+                // log_0 has no value
+                return HyperFloat::nan();
+            }
+            // b↑↑h = log_b(b↑↑(h + 1)) = log_b(h + 2)
+            return HyperFloat::from_f64(height + 2.0).log10() / self.log10();
+        }
+        if height <= 0.0 {
+            return HyperFloat::from_f64(1.0 + height);
+        }
+        if height.is_infinite() {
+            // Converges for bases up to e^(1/e); diverges otherwise
+            return self.tetrate(f64::MAX);
+        }
+
+        let whole = height.floor();
+        let steps = if whole >= u64::MAX as f64 { u64::MAX } else { whole as u64 };
+        let grows = self.cmp_abs(&HyperFloat::from_f64(1.0)) == Ordering::Greater;
+
+        // b↑↑f = b^f for the fractional part f
+        let mut value = self.pow(&HyperFloat::from_f64(height - whole));
+        let mut previous = value.clone();
+        for step in 0..steps {
+            if value.is_infinite() {
+                return value;
+            }
+            if grows && value.layer >= 3 && value.mag > 0.0 {
+                // From here on each b^ only adds a layer
+                return HyperFloat::new(1, value.layer.saturating_add(steps - step), value.mag);
+            }
+            if step == MAX_ITERATIONS {
+                return self.infinite_tower();
+            }
+
+            let next = self.pow(&value);
+            if next.converged_to(&value) {
+                // Converged to the tower's fixed point
+                return next;
+            }
+            if next.converged_to(&previous) {
+                // Oscillating between two values; the parity of the remaining steps decides
+                return if (steps - step) % 2 == 1 { next } else { value };
+            }
+            previous = std::mem::replace(&mut value, next);
+        }
+        value
+    }
+
+    /// b↑↑∞ for a base up to e^(1/e); NaN for larger bases, whose towers diverge.
+    fn infinite_tower(&self) -> HyperFloat {
+        let base = self.to_f64();
+        if !(base > 0.0 && base <= CONVERGENT_BASE) {
+            return HyperFloat::nan();
+        }
+        let ln = base.ln();
+        HyperFloat::from_f64(-lambert_w(-ln) / ln)
+    }
+
+    /// Whether two steps of a tower agree to within a few ulps.
+    fn converged_to(&self, other: &HyperFloat) -> bool {
+        const TOLERANCE: f64 = 4.0 * f64::EPSILON;
+
+        *self == *other
+            || self.layer == 0
+                && other.layer == 0
+                && self.sign == other.sign
+                && (self.mag - other.mag).abs() <= TOLERANCE * self.mag.abs()
+    }
+
+    /// Super-logarithm: the real height `h` with `base`↑↑`h` = `self`, using
+    /// the same linear approximation as `tetrate`.
+    ///
+    /// Returns NaN for bases at or below e^(1/e), where towers stop growing.
+    pub fn slog(&self, base: &HyperFloat) -> f64 {
+        const MAX_ITERATIONS: usize = 10_000;
+
+        if self.is_nan() || base.is_nan() || base.sign < 0 || base.cmp_abs(&HyperFloat::from_f64(CONVERGENT_BASE)) != Ordering::Greater {
+            return f64::NAN;
+        }
+        if self.is_infinite() {
+            return if self.sign > 0 { f64::INFINITY } else { f64::NAN };
+        }
+        if self.sign <= 0 {
+            // h in (-2, -1]: b↑↑h = log_b(h + 2)
+            return base.pow(self).to_f64() - 2.0;
+        }
+
+        let log_base = base.log10();
+        let one = HyperFloat::from_f64(1.0);
+        let mut height = 0.0;
+        let mut value = self.clone();
+        for _ in 0..MAX_ITERATIONS {
+            if value <= one {
+                return height + value.to_f64() - 1.0;
+            }
+            if value.layer >= 3 && value.mag > 0.0 {
+                // Dividing by log10(base) no longer changes the magnitude this high up
+                height += (value.layer - 2) as f64;
+                value = HyperFloat::new(1, 2, value.mag);
+                continue;
+            }
+
+            let next = value.log10() / log_base.clone();
+            if next == value {
+                return f64::NAN;
+            }
+            value = next;
+            height += 1.0;
+        }
+        f64::NAN
+    }
+
+    /// Super square root: the `y` with `y^y = self`, via `y = e^W(ln self)`.
+    ///
+    /// Defined for `self >= e^(-1/e)`; below 1 this returns the larger root.
+    pub fn ssqrt(&self) -> HyperFloat {
+        if self.is_nan() || self.sign <= 0 {
+            return HyperFloat::nan();
+        }
+        if self.is_infinite() {
+            return self.clone();
+        }
+
+        let ln = self.ln();
+        if ln.layer == 0 {
+            let w = lambert_w(ln.sign as f64 * ln.mag);
+            return HyperFloat::from_f64(w.exp());
+        }
+        if ln.sign < 0 {
+            // ln(self) is below -1/e
+            return HyperFloat::nan();
+        }
+
+        // y = z / W(z) with z = ln(self), where W(z) solves w + ln w = ln z
+        let ln_ln = ln.ln();
+        let w = if ln_ln.layer == 0 {
+            let target = ln_ln.mag;
+            let mut w = target - target.ln();
+            for _ in 0..8 {
+                w -= (w + w.ln() - target) / (1.0 + 1.0 / w);
+            }
+            HyperFloat::from_f64(w)
+        } else {
+            // ln ln z / ln z is far below the precision of ln z here
+            ln_ln.clone() - ln_ln.ln()
+        };
+        ln / w
+    }
+
+    /// The value as a `BigFloat`, saturating to ±∞ (or to ±0 for tiny values)
+    /// when it needs more nested exponents than `BigFloat` allows.
+    pub fn to_bigfloat_saturating(&self) -> BigFloat {
+        self.to_bigfloat().unwrap_or_else(|| {
+            let magnitude = if self.mag > 0.0 { f64::INFINITY } else { 0.0 };
+            BigFloat::from_f64(self.sign as f64 * magnitude)
+        })
+    }
+}
+
+/// 10^`mag` as a `BigFloat`, splitting `mag` directly so that its fractional
+/// part is not disturbed by a round trip through a decimal mantissa.
+fn layer_one_to_bigfloat(mag: f64) -> BigFloat {
+    let exponent = mag.floor();
+    BigFloat::new(10.0_f64.powf(mag - exponent), exponent as i128)
+}
+
+impl Add for HyperFloat {
+    type Output = HyperFloat;
+
+    fn add(self, other: HyperFloat) -> HyperFloat {
+        if self.is_nan() || other.is_nan() {
+            return HyperFloat::nan();
+        }
+        if self.is_zero() {
+            return other;
+        }
+        if other.is_zero() {
+            return self;
+        }
+        if self.is_infinite() || other.is_infinite() {
+            return HyperFloat::from_f64(self.to_f64() + other.to_f64());
+        }
+
+        let (big, small) = if self.cmp_abs(&other) == Ordering::Less {
+            (other, self)
+        } else {
+            (self, other)
+        };
+
+        if big.layer == 0 && small.layer == 0 {
+            return HyperFloat::new(1, 0, big.sign as f64 * big.mag + small.sign as f64 * small.mag);
+        }
+
+        if big.layer >= 2 || small.layer >= 2 {
+            // The smaller operand is far below the precision of the larger one
+            if big.cmp_abs(&small) == Ordering::Equal && big.sign != small.sign {
+                return HyperFloat::zero();
+            }
+            return big;
+        }
+
+        // Both operands are within layer 1: add in the log10 domain
+        let big_log = big.log10_f64();
+        let small_log = small.log10_f64();
+        let log_diff = small_log - big_log;
+        if log_diff < -17.0 {
+            return big;
+        }
+
+        let ratio = 10.0_f64.powf(log_diff);
+        let factor = if big.sign == small.sign { 1.0 + ratio } else { 1.0 - ratio };
+        if factor <= 0.0 {
+            return HyperFloat::zero();
+        }
+        HyperFloat::new(big.sign, 1, big_log + factor.log10())
+    }
+}
+
+impl Sub for HyperFloat {
+    type Output = HyperFloat;
+
+    fn sub(self, other: HyperFloat) -> HyperFloat {
+        self + (-other)
+    }
+}
+
+impl Mul for HyperFloat {
+    type Output = HyperFloat;
+
+    fn mul(self, other: HyperFloat) -> HyperFloat {
+        if self.is_nan() || other.is_nan() {
+            return HyperFloat::nan();
+        }
+        if self.is_infinite() || other.is_infinite() {
+            return HyperFloat::from_f64(self.to_f64() * other.to_f64());
+        }
+        let sign = self.sign * other.sign;
+        if sign == 0 {
+            return HyperFloat::zero();
+        }
+
+        if self.layer == 0 && other.layer == 0 {
+            return HyperFloat::new(sign, 0, self.mag * other.mag);
+        }
+
+        // log10(|a * b|) = log10|a| + log10|b|
+        let magnitude = (self.abs().log10() + other.abs().log10()).exp10();
+        HyperFloat { sign, ..magnitude }
+    }
+}
+
+impl Div for HyperFloat {
+    type Output = HyperFloat;
+
+    fn div(self, other: HyperFloat) -> HyperFloat {
+        if self.is_nan() || other.is_nan() {
+            return HyperFloat::nan();
+        }
+        if other.is_zero() {
+            return if self.is_zero() {
+                HyperFloat::nan()
+            } else {
+                HyperFloat::infinity(self.sign)
+            };
+        }
+        if self.is_infinite() || other.is_infinite() {
+            return HyperFloat::from_f64(self.to_f64() / other.to_f64());
+        }
+        let sign = self.sign * other.sign;
+        if sign == 0 {
+            return HyperFloat::zero();
+        }
+
+        if self.layer == 0 && other.layer == 0 {
+            return HyperFloat::new(sign, 0, self.mag / other.mag);
+        }
+
+        let magnitude = (self.abs().log10() - other.abs().log10()).exp10();
+        HyperFloat { sign, ..magnitude }
+    }
+}
+
+impl Neg for HyperFloat {
+    type Output = HyperFloat;
+
+    fn neg(self) -> HyperFloat {
+        HyperFloat {
+            sign: -self.sign,
+            exact: self.exact.map(|exact| -exact),
+            ..self
+        }
+    }
+}
+
+// Through the ordering, so that a value equals itself with or without its `BigFloat`
+impl PartialEq for HyperFloat {
+    fn eq(&self, other: &HyperFloat) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for HyperFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+
+        match self.sign.cmp(&other.sign) {
+            Ordering::Equal => {}
+            unequal => return Some(unequal),
+        }
+        Some(match self.sign {
+            1 => self.cmp_abs(other),
+            -1 => other.cmp_abs(self),
+            _ => Ordering::Equal,
+        })
+    }
+}
+
+impl Default for HyperFloat {
+    fn default() -> Self {
+        HyperFloat::zero()
+    }
+}
+
+impl From<f64> for HyperFloat {
+    fn from(value: f64) -> Self {
+        HyperFloat::from_f64(value)
+    }
+}
+
+impl From<BigFloat> for HyperFloat {
+    fn from(value: BigFloat) -> Self {
+        if !value.is_finite() {
+            return HyperFloat::from_f64(value.mantissa());
+        }
+        if value.is_zero() {
+            return HyperFloat::zero();
+        }
+
+        let sign = if value.is_sign_negative() { -1 } else { 1 };
+        let tower = match value.exponent_ref() {
+            Exponent::Long(exponent) if exponent.abs() < 300 => {
+                HyperFloat::new(sign, 0, value.to_f64_saturating().abs())
+            }
+            Exponent::Long(exponent) => {
+                HyperFloat::new(sign, 1, *exponent as f64 + value.mantissa().abs().log10())
+            }
+            Exponent::BigFloat(_) => {
+                let log = HyperFloat::from(value.abs().log10());
+                HyperFloat { sign, ..log.exp10() }
+            }
+        };
+        HyperFloat { exact: Some(value), ..tower }
+    }
+}
+
+impl fmt::Display for HyperFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_nan() {
+            return write!(f, "NaN");
+        }
+        if self.is_infinite() {
+            return write!(f, "{}", if self.sign > 0 { "∞" } else { "-∞" });
+        }
+        if let (Some(exact), 0 | 1) = (&self.exact, self.layer) {
+            return write!(f, "{}", exact);
+        }
+        if self.sign < 0 {
+            write!(f, "-")?;
+        }
+
+        match self.layer {
+            0 => write!(f, "{}", BigFloat::from_f64(self.mag)),
+            1 => write!(f, "{}", layer_one_to_bigfloat(self.mag)),
+            layer if self.mag == 1e10 && layer >= 3 => write!(f, "10^^{}", layer + 2),
+            layer if layer < 10 => {
+                // Each leading "e" is one application of 10^
+                let prefix = if self.mag < 0.0 { "e-" } else { "e" };
+                let mag = BigFloat::from_f64(self.mag.abs());
+                write!(f, "{}{}{}", prefix, "e".repeat(layer as usize - 1), mag)
+            }
+            // 10^-(10^^…|mag|) as "e-" in front of the tower of |mag|, as below 10 layers
+            layer if self.mag < 0.0 => write!(f, "e-(e^{}){}", layer - 1, BigFloat::from_f64(-self.mag)),
+            layer => write!(f, "(e^{}){}", layer, BigFloat::from_f64(self.mag)),
+        }
+    }
+}
+
+impl FromStr for HyperFloat {
+    type Err = BigFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let offset = s.len() - s.trim_start().len();
+        let s = s.trim();
+
+        if s.is_empty() {
+            return Err(BigFloatError::Empty);
+        }
+
+        parse_at(s, offset)
+    }
+}
+
+/// Parses a trimmed string that starts at byte `offset` of the original input.
+fn parse_at(s: &str, offset: usize) -> Result<HyperFloat, BigFloatError> {
+    match s.to_lowercase().as_str() {
+        "nan" => return Ok(HyperFloat::nan()),
+        "inf" | "infinity" | "∞" => return Ok(HyperFloat::infinity(1)),
+        "-inf" | "-infinity" | "-∞" => return Ok(HyperFloat::infinity(-1)),
+        _ => {}
+    }
+
+    if let Some(rest) = s.strip_prefix('-') {
+        return Ok(-parse_at(rest, offset + 1)?);
+    }
+
+    // 10^^height
+    if let Some(height) = s.strip_prefix("10^^") {
+        let height: u64 = height.parse().map_err(|_| BigFloatError::InvalidExponent { offset: offset + 4 })?;
+        return Ok(HyperFloat::tower(height));
+    }
+
+    // (e^layers)value
+    if let Some(rest) = s.strip_prefix("(e^") {
+        let layers_offset = offset + 3;
+        let close = rest.find(')').ok_or(BigFloatError::InvalidExponent { offset: layers_offset })?;
+        let layers: u64 = rest[..close].parse().map_err(|_| BigFloatError::InvalidExponent { offset: layers_offset })?;
+        let mut value = parse_at(&rest[close + 1..], layers_offset + close + 1)?;
+        // 10^x > x, so within a few steps the value either stops changing
+        // (NaN, ∞) or reaches a positive layer, where each step adds a layer
+        for applied in 0..layers {
+            if value.is_nan() {
+                return Ok(value);
+            }
+            if value.layer > 0 && value.mag >= 0.0 && value.sign > 0 {
+                let layer = value.layer.checked_add(layers - applied).ok_or(BigFloatError::ExponentOverflow)?;
+                return Ok(HyperFloat::new(1, layer, value.mag));
+            }
+            let next = value.exp10();
+            if next == value {
+                return Ok(value);
+            }
+            value = next;
+        }
+        return Ok(value);
+    }
+
+    // Leading "e" means 10^rest
+    if let Some(rest) = s.strip_prefix(['e', 'E']) {
+        if rest.is_empty() {
+            return Err(BigFloatError::InvalidExponent { offset: offset + 1 });
+        }
+        return Ok(parse_at(rest, offset + 1)?.exp10());
+    }
+
+    if let Some(e_pos) = s.find(['e', 'E']) {
+        let (mantissa_str, exp_str) = s.split_at(e_pos);
+        let exp_str = &exp_str[1..];
+        let exp_offset = offset + e_pos + 1;
+
+        let mantissa: f64 = mantissa_str.parse().map_err(|_| BigFloatError::InvalidMantissa { offset })?;
+        // An error in the nested mantissa is an error in this exponent
+        let exp = parse_at(exp_str, exp_offset).map_err(|error| match error {
+            BigFloatError::InvalidMantissa { offset } => BigFloatError::InvalidExponent { offset },
+            error => error,
+        })?;
+        return Ok(HyperFloat::from_f64(mantissa) * exp.exp10());
+    }
+
+    s.parse::<f64>()
+        .map(HyperFloat::from_f64)
+        .map_err(|_| BigFloatError::InvalidMantissa { offset })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= 1e-9 * expected.abs().max(1.0),
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_normalize_layers() {
+        let hf = HyperFloat::from_f64(123.0);
+        assert_eq!((hf.sign(), hf.layer()), (1, 0));
+        assert_eq!(hf.mag(), 123.0);
+
+        let hf = HyperFloat::new(1, 0, 1e20);
+        assert_eq!(hf.layer(), 1);
+        assert_close(hf.mag(), 20.0);
+
+        let hf = HyperFloat::new(1, 2, 1.0);
+        assert_eq!(hf.layer(), 0);
+        assert_close(hf.mag(), 1e10);
+
+        let hf = HyperFloat::from_f64(-1e-20);
+        assert_eq!((hf.sign(), hf.layer()), (-1, 1));
+        assert_close(hf.mag(), -20.0);
+    }
+
+    #[test]
+    fn test_tower() {
+        assert_eq!(HyperFloat::tower(0), HyperFloat::from_f64(1.0));
+        assert_eq!(HyperFloat::tower(2), HyperFloat::from_f64(1e10));
+
+        let hf = HyperFloat::tower(1_000_000);
+        assert_eq!(hf.layer(), 999_998);
+        assert_eq!(hf.mag(), 1e10);
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let sum = HyperFloat::from_f64(1.5) + HyperFloat::from_f64(2.5);
+        assert_eq!(sum, HyperFloat::from_f64(4.0));
+
+        let a: HyperFloat = "1e100".parse().unwrap();
+        let b: HyperFloat = "3e100".parse().unwrap();
+        assert_close((a.clone() + b.clone()).mag(), 4e100_f64.log10());
+        assert_close((b - a.clone()).mag(), 2e100_f64.log10());
+        assert!((a.clone() - a.clone()).is_zero());
+
+        // Negligible operands at higher layers
+        let huge: HyperFloat = "ee20".parse().unwrap();
+        assert_eq!(huge.clone() + a, huge);
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let a = HyperFloat::from_f64(6.0) * HyperFloat::from_f64(-7.0);
+        assert_eq!(a, HyperFloat::from_f64(-42.0));
+
+        let b: HyperFloat = "1e5000".parse().unwrap();
+        let c: HyperFloat = "2e3000".parse().unwrap();
+        let product = b.clone() * c.clone();
+        assert_eq!(product.layer(), 1);
+        assert_close(product.mag(), 8000.0 + 2.0_f64.log10());
+
+        let quotient = c / b;
+        assert_eq!(quotient.layer(), 1);
+        assert_close(quotient.mag(), -2000.0 + 2.0_f64.log10());
+
+        // 10^10^20 squared is 10^(2 * 10^20)
+        let tower: HyperFloat = "ee20".parse().unwrap();
+        let squared = tower.clone() * tower;
+        assert_eq!(squared.layer(), 2);
+        assert_close(squared.mag(), 20.0 + 2.0_f64.log10());
+    }
+
+    #[test]
+    fn test_log_exp() {
+        let hf: HyperFloat = "eee1e10".parse().unwrap();
+        assert_eq!(hf.layer(), 3);
+        assert_eq!(hf.log10().layer(), 2);
+        assert_eq!(hf.log10().exp10(), hf);
+
+        assert_close(HyperFloat::from_f64(1.0).exp().to_f64(), std::f64::consts::E);
+        assert_close(HyperFloat::from_f64(1000.0).exp().mag(), 1000.0 * LOG10_E);
+        assert_close(HyperFloat::from_f64(100.0).ln().to_f64(), 100.0_f64.ln());
+        assert!(HyperFloat::from_f64(-1.0).log10().is_nan());
+    }
+
+    #[test]
+    fn test_pow() {
+        let two = HyperFloat::from_f64(2.0);
+        assert_close(two.pow(&HyperFloat::from_f64(10.0)).to_f64(), 1024.0);
+        assert_close(HyperFloat::from_f64(-2.0).pow(&HyperFloat::from_f64(3.0)).to_f64(), -8.0);
+        assert!(HyperFloat::from_f64(-2.0).pow(&HyperFloat::from_f64(0.5)).is_nan());
+
+        // (10^^5)^2 stays a layer-3 value
+        let tower = HyperFloat::tower(5);
+        assert_eq!(tower.pow(&two).layer(), tower.layer());
+        assert_close(HyperFloat::from_f64(16.0).sqrt().to_f64(), 4.0);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let tiny: HyperFloat = "1e-1e20".parse().unwrap();
+        let small = HyperFloat::from_f64(1e-10);
+        let one = HyperFloat::from_f64(1.0);
+        let big: HyperFloat = "1e1e20".parse().unwrap();
+        let bigger = HyperFloat::tower(1_000_000);
+
+        assert!(HyperFloat::default() < tiny);
+        assert!(tiny < small && small < one && one < big && big < bigger);
+        assert!(-bigger < -big.clone() && -big < -one.clone() && -tiny < HyperFloat::default());
+        assert!(HyperFloat::from_f64(f64::NAN).partial_cmp(&one).is_none());
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(HyperFloat::from_f64(1.5).to_string(), "1.5");
+        assert_eq!("1e456".parse::<HyperFloat>().unwrap().to_string(), "1e456");
+        assert_eq!("1e-456".parse::<HyperFloat>().unwrap().to_string(), "1e-456");
+        assert_eq!("eee1e10".parse::<HyperFloat>().unwrap().to_string(), "10^^5");
+        assert_eq!("ee20".parse::<HyperFloat>().unwrap().to_string(), "ee20");
+        assert_eq!("1e-1e20".parse::<HyperFloat>().unwrap().to_string(), "e-e20");
+        assert_eq!("10^^1000000".parse::<HyperFloat>().unwrap().to_string(), "10^^1000000");
+        assert_eq!("-ee20".parse::<HyperFloat>().unwrap().to_string(), "-ee20");
+
+        let hf = HyperFloat::new(1, 20, 123.0);
+        assert_eq!(hf.to_string(), "(e^20)123");
+        assert_eq!(hf.to_string().parse::<HyperFloat>().unwrap(), hf);
+        assert_eq!("e".parse::<HyperFloat>(), Err(BigFloatError::InvalidExponent { offset: 1 }));
+        assert_eq!(" ".parse::<HyperFloat>(), Err(BigFloatError::Empty));
+        assert_eq!("1.2.3".parse::<HyperFloat>(), Err(BigFloatError::InvalidMantissa { offset: 0 }));
+        assert_eq!("1e1ex".parse::<HyperFloat>(), Err(BigFloatError::InvalidExponent { offset: 4 }));
+        assert_eq!("(e^x)5".parse::<HyperFloat>(), Err(BigFloatError::InvalidExponent { offset: 3 }));
+    }
+
+    #[test]
+    fn test_display_negative_mag_round_trip() {
+        // 10^-(10^^…123) at a high layer is tiny, not a huge tower
+        for layer in [9, 10, 12, 1_000] {
+            let hf = HyperFloat::new(1, layer, -123.0);
+            let back = hf.to_string().parse::<HyperFloat>().unwrap();
+            assert_eq!(back, hf, "{}", hf);
+            assert!(back < HyperFloat::from_f64(1.0));
+        }
+        assert_eq!(HyperFloat::new(-1, 12, -123.0).to_string(), "-e-(e^11)123");
+    }
+
+    #[test]
+    fn test_parse_layer_count_terminates() {
+        let inf = HyperFloat::from_f64(f64::INFINITY);
+        assert_eq!("(e^3000000000)inf".parse::<HyperFloat>().unwrap(), inf);
+        assert!("(e^18446744073709551615)nan".parse::<HyperFloat>().unwrap().is_nan());
+        assert_eq!("(e^18446744073709551615)inf".parse::<HyperFloat>().unwrap(), inf);
+        assert!("(e^18446744073709551615)-inf".parse::<HyperFloat>().unwrap().layer() > u64::MAX - 8);
+        assert_eq!(
+            "(e^18446744073709551615)(e^5)1".parse::<HyperFloat>(),
+            Err(BigFloatError::ExponentOverflow)
+        );
+    }
+
+    #[test]
+    fn test_bigfloat_round_trip() {
+        let texts = [
+            "0",
+            "-42.5",
+            "1.5e-300",
+            "6.02e23",
+            "1.23e456",
+            "-7e-5000",
+            "1e1e100",
+            "1e-1e50",
+            "1.2345678901234e1000000000000",
+            "1.5e1e100",
+            "3.3e123456789012345678901234567890",
+            "-2.5e-1.5e1e20",
+        ];
+        for text in texts {
+            let bf: BigFloat = text.parse().unwrap();
+            let hf = HyperFloat::from(bf.clone());
+            assert_eq!(hf.to_bigfloat(), Some(bf.clone()), "{}", text);
+            assert_eq!(HyperFloat::from(-bf.clone()).to_bigfloat(), Some(-bf.clone()), "{}", text);
+            assert_eq!((-hf).abs().to_bigfloat(), Some(bf.abs()), "{}", text);
+        }
+
+        // Values that only differ below the tower's precision still compare exactly
+        let a = HyperFloat::from("1.5e1e100".parse::<BigFloat>().unwrap());
+        let b = HyperFloat::from("1.6e1e100".parse::<BigFloat>().unwrap());
+        assert!(a < b && a != b);
+        assert_eq!(HyperFloat::from(BigFloat::new(1.2345678901234, 1_000_000_000_000_i128)).to_string(), "1.2345678901234e1000000000000");
+    }
+
+    #[test]
+    fn test_to_bigfloat_out_of_range() {
+        assert!(HyperFloat::tower(1_000_000).to_bigfloat().is_none());
+        let hf: HyperFloat = "eee1e10".parse().unwrap();
+        let bf = hf.to_bigfloat().unwrap();
+        assert_eq!(bf.exponent().depth(), 2);
+    }
+
+    #[test]
+    fn test_tetrate_integer_heights() {
+        let two = HyperFloat::from_f64(2.0);
+        assert_close(two.tetrate(3.0).to_f64(), 16.0);
+        assert_close(two.tetrate(4.0).to_f64(), 65536.0);
+        assert_close(two.tetrate(5.0).mag(), 65536.0 * 2.0_f64.log10());
+
+        let ten = HyperFloat::from_f64(10.0);
+        assert_eq!(ten.tetrate(1_000_000.0), HyperFloat::tower(1_000_000));
+        assert_eq!(ten.tetrate(0.0), HyperFloat::from_f64(1.0));
+    }
+
+    #[test]
+    fn test_tetrate_zero_base() {
+        let zero = HyperFloat::from_f64(0.0);
+        let one = HyperFloat::from_f64(1.0);
+        assert_eq!(zero.tetrate(0.0), one);
+        assert_eq!(zero.tetrate(1.0), zero);
+        assert_eq!(zero.tetrate(2.0), one);
+        assert_eq!(zero.tetrate(3.0), zero);
+        assert_eq!(zero.tetrate(0.5), zero);
+        assert_eq!(zero.tetrate(1.5), one);
+        assert_eq!(zero.tetrate(1e300), one);
+        assert_close(zero.tetrate(-0.25).to_f64(), 0.75);
+        assert!(zero.tetrate(-1.5).is_nan());
+        assert!(zero.tetrate(f64::INFINITY).is_nan());
+        assert!(HyperFloat::from_f64(-2.0).tetrate(2.0).is_nan());
+    }
+
+    #[test]
+    fn test_tetrate_real_heights() {
+        let ten = HyperFloat::from_f64(10.0);
+        assert_close(ten.tetrate(0.5).to_f64(), 10.0_f64.sqrt());
+        assert_close(ten.tetrate(1.5).to_f64(), 10.0_f64.powf(10.0_f64.sqrt()));
+        assert_close(ten.tetrate(-0.25).to_f64(), 0.75);
+        assert_close(ten.tetrate(-1.5).to_f64(), 0.5_f64.log10());
+        assert!(ten.tetrate(-3.0).is_nan());
+    }
+
+    #[test]
+    fn test_tetrate_convergent_bases() {
+        // sqrt(2)↑↑∞ = 2
+        let root_two = HyperFloat::from_f64(2.0_f64.sqrt());
+        assert_close(root_two.tetrate(f64::INFINITY).to_f64(), 2.0);
+        assert_close(root_two.tetrate(1e18).to_f64(), 2.0);
+
+        // Towers of bases near e^(1/e) creep towards e; capped at the limit,
+        // which W only resolves to about half the digits at its branch point
+        let base = HyperFloat::from_f64(CONVERGENT_BASE);
+        for height in [1e18, f64::INFINITY] {
+            assert!((base.tetrate(height).to_f64() - std::f64::consts::E).abs() < 1e-6);
+        }
+        let below = HyperFloat::from_f64(1.4);
+        assert_close(below.tetrate(1e18).to_f64(), below.infinite_tower().to_f64());
+        assert!(HyperFloat::from_f64(1.4446678610098).tetrate(1e18).is_nan());
+    }
+
+    #[test]
+    fn test_slog() {
+        let ten = HyperFloat::from_f64(10.0);
+        assert_close(HyperFloat::from_f64(1e10).slog(&ten), 2.0);
+        assert_close(HyperFloat::tower(1_000_000).slog(&ten), 1_000_000.0);
+        for height in [-1.5, -0.5, 0.3, 1.7, 2.5, 4.25] {
+            assert_close(ten.tetrate(height).slog(&ten), height);
+        }
+
+        let two = HyperFloat::from_f64(2.0);
+        assert_close(HyperFloat::from_f64(65536.0).slog(&two), 4.0);
+        assert!(HyperFloat::from_f64(5.0).slog(&HyperFloat::from_f64(1.2)).is_nan());
+        assert!(HyperFloat::from_f64(2.0).slog(&HyperFloat::from_f64(1.4)).is_nan());
+        assert!(!HyperFloat::from_f64(2.0).slog(&HyperFloat::from_f64(1.45)).is_nan());
+    }
+
+    #[test]
+    fn test_ssqrt() {
+        assert_close(HyperFloat::from_f64(27.0).ssqrt().to_f64(), 3.0);
+        assert_close(HyperFloat::from_f64(4.0).ssqrt().to_f64(), 2.0);
+        assert_close(HyperFloat::from_f64(1.0).ssqrt().to_f64(), 1.0);
+        assert!(HyperFloat::from_f64(0.5).ssqrt().is_nan());
+
+        // y^y = 10^10^20 needs the asymptotic branch
+        let huge: HyperFloat = "ee20".parse().unwrap();
+        let root = huge.ssqrt();
+        let back = root.pow(&root);
+        assert_eq!(back.layer(), huge.layer());
+        assert_close(back.mag(), huge.mag());
+    }
+}
@@ -1,7 +1,36 @@
 pub mod bigfloat;
+pub mod error;
+pub mod exponent;
+pub mod hyperfloat;
 pub mod arithmetic;
 pub mod math;
 pub mod convert;
 pub mod traits;
+pub mod ordered;
+pub mod accumulator;
+pub mod bigfloat_dd;
+pub mod bigfloat_base2;
+pub mod big_decimal;
+pub mod big_interval;
+pub mod rounding;
+pub mod mantissa;
+mod double_double;
+mod operators;
+mod powers_of_ten;
+#[cfg(test)]
+mod test_util;
 
-pub use bigfloat::BigFloat;
\ No newline at end of file
+pub use accumulator::Accumulator;
+pub use bigfloat::BigFloat;
+pub use bigfloat_dd::BigFloatDD;
+pub use bigfloat_base2::BigFloatBase2;
+pub use big_decimal::{BigDecimal, DecimalContext};
+pub use big_interval::BigInterval;
+pub use error::BigFloatError;
+pub use exponent::Exponent;
+pub use mantissa::Mantissa;
+pub use hyperfloat::HyperFloat;
+pub use math::HyperResult;
+pub use traits::Category;
+pub use ordered::OrderedBigFloat;
+pub use rounding::RoundingMode;
@@ -0,0 +1,151 @@
+use crate::exponent::{CompactExponent, Exponent, ExponentStorage};
+use crate::powers_of_ten::{scale_pow10, split_decade};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// The floating-point type that holds the mantissa of a `BigFloat`.
+///
+/// `BigFloat<M>` keeps a mantissa in [1, 10) and an `Exponent`; everything
+/// the core type needs from the mantissa goes through this trait. `f64` is
+/// the default and backs the full API, including the transcendental
+/// functions that build on the core. `f32` is the compact one: together with
+/// its `CompactExponent` a `BigFloat<f32>` takes 24 bytes against the 48 of
+/// a `BigFloat<f64>`.
+pub trait Mantissa:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + fmt::Debug
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// How a `BigFloat` with this mantissa stores its exponent.
+    type StoredExponent: ExponentStorage;
+
+    /// Decimal digits the type always resolves, as in `f64::DIGITS`.
+    const DIGITS: i128;
+
+    /// Significand bits, counting the implicit leading one.
+    const BITS: u32;
+
+    fn from_f64(value: f64) -> Self;
+
+    fn to_f64(self) -> f64;
+
+    /// Splits a finite non-zero value into a magnitude in [1, 10) and its
+    /// decimal exponent.
+    fn normalize(self) -> (Self, i128);
+
+    /// `self` × 10^`power`, dividing for negative powers.
+    fn scale10(self, power: i32) -> Self;
+
+    fn ln(self) -> f64 {
+        self.to_f64().ln()
+    }
+
+    fn log10(self) -> f64 {
+        self.to_f64().log10()
+    }
+
+    /// Parses a plain decimal number, rounding it once to this type.
+    fn parse(s: &str) -> Option<Self>;
+
+    /// Writes the value with the type's own shortest round-trip digits.
+    fn format(self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl Mantissa for f64 {
+    type StoredExponent = Exponent;
+
+    const DIGITS: i128 = 15;
+    const BITS: u32 = f64::MANTISSA_DIGITS;
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn normalize(self) -> (Self, i128) {
+        split_decade(self.abs())
+    }
+
+    fn scale10(self, power: i32) -> Self {
+        scale_pow10(self, power as i128)
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+
+    fn format(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Mantissa for f32 {
+    type StoredExponent = CompactExponent;
+
+    const DIGITS: i128 = 7;
+    const BITS: u32 = f32::MANTISSA_DIGITS;
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn normalize(self) -> (Self, i128) {
+        let (mantissa, exponent) = (self as f64).normalize();
+        // Rounding to f32 can carry 9.99999999 up to 10
+        let mantissa = mantissa as f32;
+        if mantissa >= 10.0 {
+            (mantissa / 10.0, exponent + 1)
+        } else {
+            (mantissa, exponent)
+        }
+    }
+
+    fn scale10(self, power: i32) -> Self {
+        (self as f64).scale10(power) as f32
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+
+    fn format(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(1234.5_f64.normalize(), (1.2345, 3));
+        assert_eq!((-0.05_f64).normalize(), (5.0, -2));
+        assert_eq!(1234.5_f32.normalize(), (1.2345, 3));
+        assert_eq!(9.9999999_f32.normalize(), (1.0, 1));
+        assert_eq!(f32::MIN_POSITIVE.normalize().1, -38);
+    }
+
+    #[test]
+    fn test_scale_and_parse() {
+        assert_eq!(1.5_f64.scale10(2), 150.0);
+        assert_eq!(1.5_f32.scale10(-1), 0.15);
+        assert_eq!(<f32 as Mantissa>::parse("0.1"), Some(0.1_f32));
+        assert_eq!(<f64 as Mantissa>::parse("x"), None);
+        assert_eq!(Mantissa::log10(100.0_f32), 2.0);
+    }
+}
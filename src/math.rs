@@ -1,96 +1,257 @@
 use crate::bigfloat::BigFloat;
+use crate::bigfloat_dd::BigFloatDD;
+use crate::double_double::{self, two_product, two_sum, DoubleDouble};
+use crate::error::BigFloatError;
+use crate::exponent::Exponent;
+use crate::hyperfloat::HyperFloat;
+use crate::mantissa::Mantissa;
+use crate::powers_of_ten::scale_pow10;
+use crate::rounding::RoundingMode;
+use std::cmp::Ordering;
+use std::f64::consts::{LN_10, LOG10_E};
+use std::fmt;
 
-impl BigFloat {
-    pub fn ln(&self) -> BigFloat {
+impl<M: Mantissa> BigFloat<M> {
+    pub fn ln(&self) -> BigFloat<M> {
         if self.is_zero() {
-            return BigFloat::from_f64(f64::NEG_INFINITY);
+            return BigFloat::from_float(f64::NEG_INFINITY);
         }
         
         if !self.is_finite() {
-            return BigFloat::from_f64(self.mantissa.ln());
+            return BigFloat::from_float(self.mantissa().ln());
         }
 
-        if self.mantissa < 0.0 {
-            return BigFloat::from_f64(f64::NAN);
+        if self.mantissa().to_f64() < 0.0 {
+            return BigFloat::from_float(f64::NAN);
         }
 
-        let mantissa_ln = self.mantissa.ln();
-        
-        // ln(mantissa * 10^exp) = ln(mantissa) + exp * ln(10)
-        let exp_term = (self.exponent as f64) * 10.0_f64.ln();
-        BigFloat::from_f64(mantissa_ln + exp_term)
+        if let Exponent::BigFloat(_) = &*self.exponent_cow() {
+            // The result itself needs a BigFloat exponent's worth of range
+            return self.log10() * BigFloat::from_float(LN_10);
+        }
+
+        // ln(mantissa * 10^exp) = (exp + log10(mantissa)) * ln(10), with the
+        // product taken in double-double so that it is rounded only once
+        let (hi, lo) = self.log10_double_double();
+        let (product_hi, product_lo) = two_product(hi, LN_10);
+        BigFloat::from_float(product_hi + (product_lo + lo * LN_10 + hi * LN_10_LO))
     }
 
-    pub fn log10(&self) -> BigFloat {
+    /// log10 rounded once to a `BigFloat`. The result's own f64 mantissa has
+    /// to hold the exponent as well, so `log10().exp10()` only gives the value
+    /// back to about |log10| × 2^-53 relative error, and from an exponent of
+    /// 2^53 on the mantissa's digits are lost entirely. Use `log10_dd` with
+    /// `BigFloat::exp10_dd`, or `log10_split`, where they matter.
+    pub fn log10(&self) -> BigFloat<M> {
         if self.is_zero() {
-            return BigFloat::from_f64(f64::NEG_INFINITY);
+            return BigFloat::from_float(f64::NEG_INFINITY);
         }
         
         if !self.is_finite() {
-            return BigFloat::from_f64(self.mantissa.log10());
+            return BigFloat::from_float(self.mantissa().log10());
         }
 
-        if self.mantissa < 0.0 {
-            return BigFloat::from_f64(f64::NAN);
+        if self.mantissa().to_f64() < 0.0 {
+            return BigFloat::from_float(f64::NAN);
         }
 
-        let mantissa_log10 = self.mantissa.log10();
-        
         // log10(mantissa * 10^exp) = log10(mantissa) + exp
-        BigFloat::from_f64(mantissa_log10 + (self.exponent as f64))
+        match &*self.exponent_cow() {
+            Exponent::Long(_) => {
+                let (hi, lo) = self.log10_double_double();
+                BigFloat::from_float(hi + lo)
+            }
+            Exponent::BigFloat(exponent) => {
+                ((**exponent).clone() + BigFloat::from_f64(self.mantissa().to_f64().abs().log10())).cast()
+            }
+        }
+    }
+
+    /// `log10` as a `BigFloatDD`, whose low part keeps the mantissa's digits
+    /// for exponents past 2^53, where a `BigFloat` has no room left for them;
+    /// `BigFloat::exp10_dd` undoes it.
+    pub fn log10_dd(&self) -> BigFloatDD {
+        BigFloatDD::from(self.cast::<f64>()).log10()
+    }
+
+    /// log10|self| split into its integer part (the decimal exponent) and its
+    /// fractional part in [0, 1), which carries all of the mantissa's digits.
+    ///
+    /// `log10` has to round the sum into a single f64, so for exponents past
+    /// 2^53 the fraction is lost there; `BigFloat::new(10^fraction, integer)`
+    /// rebuilds the value exactly from this split. An exponent stored as a
+    /// `BigFloat` saturates to `i128::MIN`/`i128::MAX`.
+    pub fn log10_split(&self) -> (i128, f64) {
+        (self.exponent_cow().to_i128_saturating(), self.mantissa().to_f64().abs().log10())
     }
 
+    /// log10|self| as an unevaluated sum hi + lo, for an `i128` exponent.
+    fn log10_double_double(&self) -> (f64, f64) {
+        let (integer_part, fraction) = self.log10_split();
+        let integer_hi = integer_part as f64;
+        let integer_lo = integer_part.wrapping_sub(integer_hi as i128) as f64;
+        let (hi, lo) = two_sum(integer_hi, fraction);
+        two_sum(hi, lo + integer_lo)
+    }
+}
+
+impl BigFloat {
     pub fn exp(&self) -> BigFloat {
         if !self.is_finite() {
-            return BigFloat::from_f64(self.mantissa.exp());
+            return BigFloat::from_f64(self.mantissa().exp());
         }
 
-        let exp_f64 = self.exponent as f64;
-        
-        if exp_f64 < 0.0 {
-            // Very small number, exp() will be close to 1
-            return BigFloat::from_f64(1.0);
+        // Within the f64 range the standard exp keeps full precision
+        let as_f64 = self.to_f64_lossy();
+        let result = as_f64.exp();
+        if result.is_normal() {
+            return BigFloat::from_f64(result);
         }
-        
-        if exp_f64 > 2.0 {
-            // Very large number, result will be infinity
-            return BigFloat::from_f64(f64::INFINITY);
+
+        // Otherwise go through the log10 domain: e^x = 10^(x * log10(e))
+        if as_f64.abs() < FRACTIONAL_LIMIT {
+            exp_via_log10(as_f64)
+        } else {
+            pow10(&(self.clone() * BigFloat::from_f64(LOG10_E)))
         }
-        
-        // For moderate exponents, convert to f64 and use standard exp
-        let as_f64 = self.to_f64_lossy();
-        if as_f64.is_finite() {
-            BigFloat::from_f64(as_f64.exp())
+    }
+
+    /// 10^`self`. The integer part of the power becomes the exponent and
+    /// the fractional part the mantissa, so no precision is lost to an
+    /// intermediate f64.
+    pub fn exp10(&self) -> BigFloat {
+        pow10(self)
+    }
+
+    /// 10^`power` for a double-double power, rounded to a `BigFloat`, so that
+    /// `BigFloat::exp10_dd(&x.log10_dd())` gives back `x` past 2^53 too.
+    pub fn exp10_dd(power: &BigFloatDD) -> BigFloat {
+        power.exp10().to_bigfloat()
+    }
+
+    /// 2^`self`, computed as 10^(self·log10 2) like `exp`.
+    pub fn exp2(&self) -> BigFloat {
+        if !self.is_finite() {
+            return BigFloat::from_f64(self.mantissa().exp2());
+        }
+
+        let x = self.to_f64_lossy();
+        if x.abs() < FRACTIONAL_LIMIT {
+            let (hi, lo) = two_product(x, LOG10_2);
+            pow10_split(hi, lo + x * LOG10_2_LO).unwrap_or_else(|| pow10(&BigFloat::from_f64(hi)))
         } else {
-            BigFloat::from_f64(f64::INFINITY)
+            pow10(&(self.clone() * BigFloat::from_f64(LOG10_2)))
         }
     }
 
-    pub fn pow(&self, exponent: &BigFloat) -> BigFloat {
-        if self.is_zero() {
-            if exponent.is_zero() {
-                return BigFloat::from_f64(f64::NAN);  // 0^0 is undefined
+    /// The value whose base-10 logarithm is `log10`, i.e. 10^`log10`.
+    pub fn from_log10(log10: f64) -> BigFloat {
+        if !log10.is_finite() {
+            return BigFloat::from_f64(10.0_f64.powf(log10));
+        }
+        pow10_split(log10, 0.0).unwrap_or_else(|| pow10(&BigFloat::from_f64(log10)))
+    }
+
+    /// `self` × 10^`n`, done on the exponent alone so the mantissa is kept
+    /// bit for bit.
+    pub fn scale10(&self, n: i128) -> BigFloat {
+        if self.is_zero() || !self.is_finite() {
+            return self.clone();
+        }
+        BigFloat::new(self.mantissa(), self.exponent() + Exponent::Long(n))
+    }
+
+    /// `self` × 2^`n`. Exact up to the final rounding into [1, 10) as long as
+    /// 2^`n` fits an f64; larger scales go through `exp2`.
+    pub fn ldexp(&self, n: i64) -> BigFloat {
+        if self.is_zero() || !self.is_finite() {
+            return self.clone();
+        }
+        match i32::try_from(n) {
+            Ok(n) if (-1022..=1023).contains(&n) => {
+                // m·2^n is exact whenever it is still a normal f64
+                let scaled = self.mantissa() * 2.0_f64.powi(n);
+                if scaled.is_normal() {
+                    return BigFloat::new(scaled, self.exponent());
+                }
+                self.clone() * BigFloat::from_f64(2.0_f64.powi(n))
             }
-            return BigFloat::from_f64(0.0);
+            _ => self.clone() * BigFloat::from(n).exp2(),
         }
+    }
 
+    /// `self` raised to `exponent`.
+    ///
+    /// Computed as 10^(exponent · log10|self|) with the integer and
+    /// fractional parts of the power carried separately, so results far
+    /// outside the f64 range keep a full-precision mantissa. A negative base
+    /// is only defined for integer exponents, where the sign follows the
+    /// parity; integers beyond f64 precision count as even.
+    pub fn pow(&self, exponent: &BigFloat) -> BigFloat {
+        // x^0 = 1 for every x, NaN included, as in IEEE 754 and `powi`
         if exponent.is_zero() {
             return BigFloat::from_f64(1.0);
         }
 
+        if self.is_zero() && !exponent.is_nan() {
+            // ±0 keeps its sign only under odd integer powers
+            let odd = integer_parity(exponent) == Some(true);
+            let magnitude = if exponent.mantissa() < 0.0 { f64::INFINITY } else { 0.0 };
+            let negative = odd && self.mantissa().is_sign_negative();
+            return BigFloat::from_f64(if negative { -magnitude } else { magnitude });
+        }
+
         if !self.is_finite() || !exponent.is_finite() {
             let self_f64 = self.to_f64_lossy();
             let exp_f64 = exponent.to_f64_lossy();
             return BigFloat::from_f64(self_f64.powf(exp_f64));
         }
 
-        // Use the identity: a^b = exp(b * ln(a))
-        let ln_self = self.ln();
-        let product = ln_self * exponent.clone();
-        product.exp()
+        let negate = if self.mantissa() < 0.0 {
+            match integer_parity(exponent) {
+                Some(odd) => odd,
+                None => return BigFloat::from_f64(f64::NAN),
+            }
+        } else {
+            false
+        };
+
+        // Integer powers take the exact path where they can
+        if let Some(n) = exponent.to_f64().filter(|n| n.fract() == 0.0 && n.abs() < 9.2e18) {
+            return self.powi_i64(n as i64);
+        }
+
+        let magnitude = match exponent.exponent_ref().as_i128() {
+            Some(e) if e <= 308 => self.abs().pow_log10(exponent.to_f64_lossy(), 0.0),
+            _ => None,
+        };
+        // Powers whose log10 does not fit an f64 go through BigFloat throughout
+        let magnitude = magnitude.unwrap_or_else(|| pow10(&(exponent.clone() * self.abs().log10())));
+        if negate { -magnitude } else { magnitude }
     }
 
     pub fn powi(&self, n: i32) -> BigFloat {
+        self.powi_i64(n as i64)
+    }
+
+    pub fn powi_i64(&self, n: i64) -> BigFloat {
+        if n < 0 {
+            let positive_result = self.powi_u128(n.unsigned_abs() as u128);
+            return BigFloat::from_f64(1.0) / positive_result;
+        }
+        self.powi_u128(n as u128)
+    }
+
+    /// `self` raised to a non-negative integer power.
+    ///
+    /// Small powers use binary exponentiation, straight in f64 when the
+    /// result fits so that values such as 2^10 are exact; larger ones go
+    /// through the log10 domain so that the error does not build up over
+    /// repeated multiplications.
+    pub fn powi_u128(&self, n: u128) -> BigFloat {
+        // Beyond this many squarings the log10 domain is at least as accurate
+        const SQUARING_LIMIT: u128 = 1 << 10;
+
         if n == 0 {
             return BigFloat::from_f64(1.0);
         }
@@ -99,19 +260,36 @@ impl BigFloat {
             return self.clone();
         }
 
-        if n < 0 {
-            let positive_result = self.powi(-n);
-            return BigFloat::from_f64(1.0) / positive_result;
+        if self.is_zero() || !self.is_finite() {
+            return BigFloat::from_f64(self.mantissa().powf(n as f64));
+        }
+
+        if n > SQUARING_LIMIT {
+            let n_hi = n as f64;
+            let n_lo = (n as i128).wrapping_sub(n_hi as i128) as f64;
+            let magnitude = self
+                .abs()
+                .pow_log10(n_hi, n_lo)
+                .unwrap_or_else(|| pow10(&(BigFloat::from_f64(n_hi) * self.abs().log10())));
+            let negate = self.mantissa() < 0.0 && n % 2 == 1;
+            return if negate { -magnitude } else { magnitude };
+        }
+
+        if let Some(value) = self.to_f64() {
+            let result = value.powi(n as i32);
+            if result.is_normal() {
+                return BigFloat::from_f64(result);
+            }
         }
 
         // Use binary exponentiation for efficiency
         let mut result = BigFloat::from_f64(1.0);
         let mut base = self.clone();
-        let mut exp = n as u32;
+        let mut exp = n;
 
         while exp > 0 {
             if exp & 1 == 1 {
-                result = result * base.clone();
+                result *= base.clone();
             }
             base = base.clone() * base;
             exp >>= 1;
@@ -120,35 +298,481 @@ impl BigFloat {
         result
     }
 
+    /// 10^((y_hi + y_lo) · log10(self)) for a positive `self`, or `None` when
+    /// the power does not fit an f64.
+    fn pow_log10(&self, y_hi: f64, y_lo: f64) -> Option<BigFloat> {
+        let exponent = self.exponent_ref().as_i128()?;
+        // log10 of the mantissa in double-double as well: a large `y`
+        // multiplies its error straight into the digits of the result
+        let log_mantissa = DoubleDouble::from_f64(self.mantissa()).ln() * double_double::LOG10_E;
+        let power = (DoubleDouble::from_i128(exponent) + log_mantissa) * DoubleDouble::new(y_hi, y_lo);
+        pow10_split(power.hi, power.lo)
+    }
+
+    /// The correctly rounded square root; see `sqrt_round` for other modes.
     pub fn sqrt(&self) -> BigFloat {
-        if self.mantissa < 0.0 {
-            return BigFloat::from_f64(f64::NAN);
+        self.sqrt_round(RoundingMode::NearestEven)
+    }
+
+    /// Natural logarithm that reports ln of a negative, of zero, or of NaN
+    /// as a domain error instead of returning NaN or -∞.
+    pub fn try_ln(&self) -> Result<BigFloat, BigFloatError> {
+        if self.is_nan() {
+            return Err(BigFloatError::Domain { operation: "ln of NaN" });
+        }
+        if self.is_zero() {
+            return Err(BigFloatError::Domain { operation: "ln of zero" });
+        }
+        if self.mantissa() < 0.0 {
+            return Err(BigFloatError::Domain { operation: "ln of a negative number" });
         }
+        Ok(self.ln())
+    }
 
-        if self.is_zero() || !self.is_finite() {
-            return BigFloat::from_f64(self.mantissa.sqrt());
+    /// Square root that reports a negative or NaN operand as a domain error.
+    /// -0 is allowed and gives -0, as in IEEE 754.
+    pub fn try_sqrt(&self) -> Result<BigFloat, BigFloatError> {
+        if self.is_nan() {
+            return Err(BigFloatError::Domain { operation: "square root of NaN" });
+        }
+        if self.mantissa() < 0.0 {
+            return Err(BigFloatError::Domain { operation: "square root of a negative number" });
+        }
+        Ok(self.sqrt())
+    }
+
+    /// `self.pow(exponent)` that reports undefined powers, 0 to a negative
+    /// power and exponent overflow as errors.
+    pub fn try_pow(&self, exponent: &BigFloat) -> Result<BigFloat, BigFloatError> {
+        if self.is_nan() || exponent.is_nan() {
+            return Err(BigFloatError::Domain { operation: "power with a NaN operand" });
         }
+        if self.is_zero() && exponent.mantissa() < 0.0 {
+            return Err(BigFloatError::DivisionByZero);
+        }
+        let (result, overflowed) = self.overflowing_pow(exponent);
+        if overflowed {
+            return Err(BigFloatError::ExponentOverflow);
+        }
+        if result.is_nan() {
+            return Err(BigFloatError::Domain { operation: "non-integer power of a negative number" });
+        }
+        Ok(result)
+    }
+
+    /// Tetration: `self`↑↑`height`, with real heights handled by the linear
+    /// approximation (see `HyperFloat::tetrate`).
+    ///
+    /// Saturates to +∞ when the tower outgrows `BigFloat`; use
+    /// `HyperFloat::tetrate` to keep going.
+    pub fn tetrate(&self, height: f64) -> BigFloat {
+        HyperFloat::from(self.clone()).tetrate(height).to_bigfloat_saturating()
+    }
+
+    /// Super-logarithm: the real height `h` with `base`↑↑`h` = `self`.
+    pub fn slog(&self, base: &BigFloat) -> f64 {
+        HyperFloat::from(self.clone()).slog(&HyperFloat::from(base.clone()))
+    }
 
-        let half = BigFloat::from_f64(0.5);
-        self.pow(&half)
+    /// Super square root: the `y` with `y^y = self`.
+    pub fn ssqrt(&self) -> BigFloat {
+        HyperFloat::from(self.clone()).ssqrt().to_bigfloat_saturating()
     }
 
     fn to_f64_lossy(&self) -> f64 {
-        if self.exponent > 308 {
-            if self.mantissa >= 0.0 { f64::INFINITY } else { f64::NEG_INFINITY }
+        let exponent = self.exponent_ref().to_i128_saturating();
+        if exponent > 308 {
+            if self.mantissa() >= 0.0 { f64::INFINITY } else { f64::NEG_INFINITY }
         } else {
-            self.mantissa * 10.0_f64.powi(self.exponent as i32)
+            scale_pow10(self.mantissa(), exponent)
+        }
+    }
+}
+
+/// Principal branch of the Lambert W function (w * e^w = x) for x >= -1/e.
+pub(crate) fn lambert_w(x: f64) -> f64 {
+    const INV_E: f64 = 1.0 / std::f64::consts::E;
+
+    if x.is_nan() || x < -INV_E {
+        return f64::NAN;
+    }
+    if x == 0.0 || x.is_infinite() {
+        return x;
+    }
+
+    let mut w = if x < 1.0 {
+        // Series around the branch point -1/e
+        let p = (2.0 * (std::f64::consts::E * x + 1.0)).sqrt();
+        -1.0 + p - p * p / 3.0
+    } else {
+        let ln = x.ln();
+        ln - ln.ln().max(0.0)
+    };
+
+    // Halley's iteration
+    for _ in 0..64 {
+        let ew = w.exp();
+        let f = w * ew - x;
+        if f == 0.0 || w == -1.0 {
+            break;
+        }
+        let next = w - f / (ew * (w + 1.0) - (w + 2.0) * f / (2.0 * w + 2.0));
+        if (next - w).abs() <= 4.0 * f64::EPSILON * next.abs() {
+            return next;
+        }
+        w = next;
+    }
+    w
+}
+
+/// 10^`power`: the integer part of `power` becomes the exponent and the
+/// fractional part the mantissa.
+pub(crate) fn pow10(power: &BigFloat) -> BigFloat {
+    if !power.is_finite() {
+        return BigFloat::from_f64(10.0_f64.powf(power.mantissa()));
+    }
+
+    let as_f64 = power.to_f64_lossy();
+    if as_f64.abs() < FRACTIONAL_LIMIT {
+        let integer_part = as_f64.floor();
+        return BigFloat::new(10.0_f64.powf(as_f64 - integer_part), integer_part as i128);
+    }
+    // From 2^53 on the power is a whole number of decades, kept exactly
+    // while it fits an i128
+    BigFloat::new(1.0, Exponent::from_bigfloat(power.clone()))
+}
+
+/// 2^53: powers below this can still have a fractional part in an f64.
+const FRACTIONAL_LIMIT: f64 = 9007199254740992.0;
+
+// Low-order parts of the double-double constants, so that e.g.
+// LOG10_E + LOG10_E_LO carries ~106 bits
+const LN_10_LO: f64 = double_double::LN_10.lo;
+const LOG10_2: f64 = double_double::LOG10_2.hi;
+const LOG10_2_LO: f64 = double_double::LOG10_2.lo;
+const LOG10_E_LO: f64 = double_double::LOG10_E.lo;
+
+/// e^x as 10^(x·log10 e), with the product kept in double-double precision so
+/// that the fractional digits that become the mantissa are not lost to the
+/// integer part.
+fn exp_via_log10(x: f64) -> BigFloat {
+    let (hi, lo) = two_product(x, LOG10_E);
+    pow10_split(hi, lo + x * LOG10_E_LO).unwrap_or_else(|| pow10(&BigFloat::from_f64(hi)))
+}
+
+/// 10^(hi + lo) for a double-double power, or `None` if `hi` is not finite.
+fn pow10_split(hi: f64, lo: f64) -> Option<BigFloat> {
+    if !hi.is_finite() {
+        return None;
+    }
+    if hi.abs() >= 1e38 {
+        return Some(pow10(&BigFloat::from_f64(hi)));
+    }
+    // Past 2^53 `lo` can itself carry whole decades
+    let integer_part = hi.floor();
+    let fraction = (hi - integer_part) + lo;
+    let carry = fraction.floor();
+    Some(BigFloat::new(
+        10.0_f64.powf(fraction - carry),
+        integer_part as i128 + carry as i128,
+    ))
+}
+
+/// Whether `value` is an odd (`Some(true)`) or even (`Some(false)`) integer,
+/// or `None` if it is not an integer at all.
+fn integer_parity(value: &BigFloat) -> Option<bool> {
+    match value.exponent_ref().as_i128() {
+        // Past 2^53 an f64 mantissa cannot express odd integers
+        Some(exponent) if exponent < 16 => {
+            let value = value.to_f64_lossy();
+            if value.fract() == 0.0 {
+                Some(value % 2.0 != 0.0)
+            } else {
+                None
+            }
+        }
+        _ if value.exponent_ref().is_negative() => None,
+        _ => Some(false),
+    }
+}
+
+/// Outcome of a hyperoperation such as a↑ⁿb.
+#[derive(Debug, Clone)]
+pub enum HyperResult {
+    /// The result fits in a `BigFloat`.
+    Value(BigFloat),
+    /// The result only fits in a `HyperFloat` tower.
+    Tower(HyperFloat),
+    /// The result is beyond even `HyperFloat`, so it is greater than every
+    /// finite `BigFloat`. `arrows` is the arrow count of the step that overflowed.
+    TooLarge { arrows: u32 },
+}
+
+impl HyperResult {
+    fn from_hyperfloat(value: HyperFloat) -> HyperResult {
+        match value.to_bigfloat() {
+            Some(value) => HyperResult::Value(value),
+            None => HyperResult::Tower(value),
+        }
+    }
+
+    pub fn is_too_large(&self) -> bool {
+        matches!(self, HyperResult::TooLarge { .. })
+    }
+
+    pub fn as_bigfloat(&self) -> Option<&BigFloat> {
+        match self {
+            HyperResult::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn to_hyperfloat(&self) -> Option<HyperFloat> {
+        match self {
+            HyperResult::Value(value) => Some(HyperFloat::from(value.clone())),
+            HyperResult::Tower(value) => Some(value.clone()),
+            HyperResult::TooLarge { .. } => None,
         }
     }
 }
 
+impl PartialEq<BigFloat> for HyperResult {
+    fn eq(&self, other: &BigFloat) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd<BigFloat> for HyperResult {
+    fn partial_cmp(&self, other: &BigFloat) -> Option<Ordering> {
+        match self {
+            HyperResult::Value(value) => value.partial_cmp(other),
+            HyperResult::Tower(value) => value.partial_cmp(&HyperFloat::from(other.clone())),
+            HyperResult::TooLarge { .. } => {
+                if other.mantissa().is_nan() {
+                    None
+                } else if other.mantissa() == f64::INFINITY {
+                    Some(Ordering::Less)
+                } else {
+                    Some(Ordering::Greater)
+                }
+            }
+        }
+    }
+}
+
+// Two results that are both too large cannot be told apart, so they are unordered
+impl PartialEq for HyperResult {
+    fn eq(&self, other: &HyperResult) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for HyperResult {
+    fn partial_cmp(&self, other: &HyperResult) -> Option<Ordering> {
+        match (self, other) {
+            (HyperResult::Value(a), HyperResult::Value(b)) => a.partial_cmp(b),
+            (HyperResult::TooLarge { .. }, HyperResult::TooLarge { .. }) => None,
+            (HyperResult::TooLarge { .. }, _) => Some(Ordering::Greater),
+            (_, HyperResult::TooLarge { .. }) => Some(Ordering::Less),
+            (a, b) => a.to_hyperfloat()?.partial_cmp(&b.to_hyperfloat()?),
+        }
+    }
+}
+
+impl fmt::Display for HyperResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HyperResult::Value(value) => write!(f, "{}", value),
+            HyperResult::Tower(value) => write!(f, "{}", value),
+            HyperResult::TooLarge { arrows } => write!(f, "too large to represent (↑^{})", arrows),
+        }
+    }
+}
+
+/// The `n`-th hyperoperation: successor (0), addition (1), multiplication (2),
+/// exponentiation (3), tetration (4) and so on, i.e. `a↑^(n-2)b` for n >= 3.
+pub fn hyperop(a: &BigFloat, n: u32, b: u64) -> HyperResult {
+    let b_value = BigFloat::from_f64(b as f64);
+    match n {
+        0 => HyperResult::Value(b_value + BigFloat::from_f64(1.0)),
+        1 => HyperResult::Value(a.clone() + b_value),
+        2 => HyperResult::Value(a.clone() * b_value),
+        _ => up_arrow(a, n - 2, b),
+    }
+}
+
+/// Knuth's up-arrow notation a↑ⁿb.
+///
+/// Non-negative integer bases are evaluated exactly while the result fits in
+/// a `u128`; larger results go through `HyperFloat`. Anything that outgrows
+/// `HyperFloat` as well comes back as `HyperResult::TooLarge`.
+pub fn up_arrow(a: &BigFloat, arrows: u32, b: u64) -> HyperResult {
+    if arrows == 0 {
+        return hyperop(a, 2, b);
+    }
+
+    if let Some(base) = as_u64(a) {
+        if let Some(exact) = exact_up_arrow(base as u128, arrows, b) {
+            return HyperResult::Value(BigFloat::from_f64(exact as f64));
+        }
+        if let Some(value) = last_step_up_arrow(base as u128, arrows, b) {
+            return HyperResult::Value(value);
+        }
+    }
+
+    match hyper_up_arrow(&HyperFloat::from(a.clone()), arrows, b) {
+        Some(value) => HyperResult::from_hyperfloat(value),
+        None => HyperResult::TooLarge { arrows },
+    }
+}
+
+/// Conway chained-arrow notation a→b→...→z for positive integers.
+///
+/// The first two links may also be zero, as in a↑ⁿ0 = 1 and 0↑ⁿb; the
+/// notation has no meaning for a zero further along, which gives NaN.
+pub fn conway_chain(chain: &[u64]) -> HyperResult {
+    if chain.iter().skip(2).any(|&link| link == 0) {
+        return HyperResult::Value(BigFloat::from_f64(f64::NAN));
+    }
+    match chain_exact(chain) {
+        Some(exact) => HyperResult::Value(BigFloat::from_f64(exact as f64)),
+        None => chain_approximate(chain),
+    }
+}
+
+fn as_u64(value: &BigFloat) -> Option<u64> {
+    let value = value.to_f64()?;
+    if value >= 0.0 && value < u64::MAX as f64 && value.fract() == 0.0 {
+        Some(value as u64)
+    } else {
+        None
+    }
+}
+
+fn exact_up_arrow(a: u128, arrows: u32, b: u64) -> Option<u128> {
+    if arrows == 1 {
+        return a.checked_pow(u32::try_from(b).ok()?);
+    }
+    if b == 0 {
+        return Some(1);
+    }
+    if b == 1 || a == 1 {
+        return Some(a);
+    }
+    // 0↑ⁿb alternates 1, 0, 1, … since 0^0 = 1 and 0^1 = 0
+    if a == 0 {
+        return Some(b.is_multiple_of(2) as u128);
+    }
+    if a == 2 && b == 2 {
+        return Some(4);
+    }
+
+    let mut result = a;
+    for _ in 1..b {
+        let next = exact_up_arrow(a, arrows - 1, u64::try_from(result).ok()?)?;
+        if next == result {
+            break;
+        }
+        result = next;
+    }
+    Some(result)
+}
+
+/// a↑ⁿb = a↑ⁿ⁻¹(a↑ⁿ(b-1)) with the inner value exact, so that only the
+/// final power rounds; `None` if the inner value does not fit exactly or the
+/// result is beyond `BigFloat`.
+fn last_step_up_arrow(a: u128, arrows: u32, b: u64) -> Option<BigFloat> {
+    if arrows == 1 {
+        return Some(BigFloat::from_f64(a as f64).powi_u128(b as u128)).filter(BigFloat::is_finite);
+    }
+    let inner = exact_up_arrow(a, arrows, b.checked_sub(1)?)?;
+    last_step_up_arrow(a, arrows - 1, u64::try_from(inner).ok()?)
+}
+
+fn hyper_up_arrow(a: &HyperFloat, arrows: u32, b: u64) -> Option<HyperFloat> {
+    // Heights above this would push HyperFloat's layer count past u64
+    const MAX_HEIGHT: f64 = 1e18;
+
+    match arrows {
+        0 => return Some(a.clone() * HyperFloat::from_f64(b as f64)),
+        1 => return Some(a.pow(&HyperFloat::from_f64(b as f64))),
+        2 => return Some(a.tetrate(b as f64)).filter(|value| value.layer() < MAX_HEIGHT as u64),
+        _ => {}
+    }
+    if b == 0 {
+        return Some(HyperFloat::from_f64(1.0));
+    }
+
+    let mut result = a.clone();
+    for _ in 1..b {
+        let height = result.to_f64();
+        if !(0.0..=MAX_HEIGHT).contains(&height) {
+            return None;
+        }
+        let next = if arrows == 3 {
+            a.tetrate(height)
+        } else {
+            // Four or more arrows are only defined here for whole heights
+            hyper_up_arrow(a, arrows - 1, height.round() as u64)?
+        };
+        if next == result {
+            break;
+        }
+        result = next;
+    }
+    Some(result).filter(|value| value.layer() < MAX_HEIGHT as u64)
+}
+
+/// Drops everything from the first 1 after the head, since X→1→Y = X.
+fn truncate_chain(chain: &[u64]) -> &[u64] {
+    match chain.iter().skip(1).position(|&link| link == 1) {
+        Some(index) => &chain[..index + 1],
+        None => chain,
+    }
+}
+
+fn chain_exact(chain: &[u64]) -> Option<u128> {
+    let chain = truncate_chain(chain);
+    match *chain {
+        [] => None,
+        [a] => Some(a as u128),
+        [a, b] => exact_up_arrow(a as u128, 1, b),
+        [a, b, c] => exact_up_arrow(a as u128, u32::try_from(c).ok()?, b),
+        [2, 2, ..] => Some(4),
+        [ref head @ .., p, q] => {
+            // X→p→q = X→(X→(p-1)→q)→(q-1), unrolled from X→1→q = X
+            let mut value = chain_exact(head)?;
+            for _ in 1..p {
+                let mut next = head.to_vec();
+                next.extend([u64::try_from(value).ok()?, q - 1]);
+                value = chain_exact(&next)?;
+            }
+            Some(value)
+        }
+    }
+}
+
+fn chain_approximate(chain: &[u64]) -> HyperResult {
+    let chain = truncate_chain(chain);
+    match *chain {
+        [] => HyperResult::Value(BigFloat::from_f64(f64::NAN)),
+        [a, b] => up_arrow(&BigFloat::from_f64(a as f64), 1, b),
+        [a, b, c] => match u32::try_from(c) {
+            Ok(arrows) => up_arrow(&BigFloat::from_f64(a as f64), arrows, b),
+            Err(_) => HyperResult::TooLarge { arrows: u32::MAX },
+        },
+        // Longer chains that do not fit exactly already need more than u64 arrows
+        _ => HyperResult::TooLarge { arrows: u32::MAX },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_ln_basic() {
-        let bf = BigFloat::from_f64(2.718281828459045); // e
+        let bf = BigFloat::from_f64(std::f64::consts::E);
         let result = bf.ln();
         assert!((result.to_f64_lossy() - 1.0).abs() < 1e-10);
     }
@@ -164,7 +788,7 @@ mod tests {
     fn test_exp_basic() {
         let bf = BigFloat::from_f64(1.0);
         let result = bf.exp();
-        assert!((result.to_f64_lossy() - 2.718281828459045).abs() < 1e-10);
+        assert!((result.to_f64_lossy() - std::f64::consts::E).abs() < 1e-10);
     }
 
     #[test]
@@ -182,6 +806,136 @@ mod tests {
         assert!((result.to_f64_lossy() - 1024.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_pow_beyond_f64() {
+        // 2^5000 = 1.4124670321394260e1505
+        let result = BigFloat::from_f64(2.0).pow(&BigFloat::from_f64(5000.0));
+        assert!((result.mantissa() - 1.412467032139426).abs() < 1e-12);
+        assert_eq!(result.exponent(), 1505);
+
+        let result = BigFloat::from_f64(2.0).pow(&BigFloat::from_f64(-5000.0));
+        assert!((result.mantissa() - 7.079811261048173).abs() < 1e-12);
+        assert_eq!(result.exponent(), -1506);
+
+        // (1.5e300)^3 = 3.375e900
+        let result = BigFloat::new(1.5, 300).pow(&BigFloat::from_f64(3.0));
+        assert!((result.mantissa() - 3.375).abs() < 1e-12);
+        assert_eq!(result.exponent(), 900);
+    }
+
+    #[test]
+    fn test_pow_negative_base() {
+        let base = BigFloat::from_f64(-2.0);
+        assert_eq!(base.pow(&BigFloat::from_f64(3.0)), BigFloat::from_f64(-8.0));
+        assert_eq!(base.pow(&BigFloat::from_f64(2.0)), BigFloat::from_f64(4.0));
+        assert!(base.pow(&BigFloat::from_f64(0.5)).mantissa().is_nan());
+
+        // Integers too large to be odd are even
+        let result = base.pow(&BigFloat::new(1.0, 20));
+        assert!(result > BigFloat::from_f64(0.0));
+        assert_eq!(BigFloat::from_f64(-1.0).pow(&BigFloat::new(1.0, 100)), BigFloat::from_f64(1.0));
+    }
+
+    #[test]
+    fn test_pow_zero_base() {
+        let zero = BigFloat::from_f64(0.0);
+        assert_eq!(zero.pow(&BigFloat::from_f64(2.0)), zero);
+        assert_eq!(zero.pow(&BigFloat::from_f64(-2.0)).mantissa(), f64::INFINITY);
+        assert_eq!(zero.pow(&zero), BigFloat::from_f64(1.0));
+
+        let negative_zero = BigFloat::from_f64(-0.0);
+        assert_eq!(negative_zero.pow(&BigFloat::from_f64(-3.0)).mantissa(), f64::NEG_INFINITY);
+        assert!(negative_zero.pow(&BigFloat::from_f64(3.0)).is_sign_negative());
+        assert!(negative_zero.pow(&BigFloat::from_f64(2.0)).is_sign_positive());
+        assert!(negative_zero.pow(&BigFloat::new(1.0, -500)).is_sign_positive());
+    }
+
+    #[test]
+    fn test_powi_wide_integers() {
+        let base = BigFloat::from_f64(2.0);
+        let result = base.powi_i64(5000);
+        assert!((result.mantissa() - 1.412467032139426).abs() < 1e-12);
+        assert_eq!(result.exponent(), 1505);
+
+        let result = base.powi_i64(-3);
+        assert_eq!(result, BigFloat::from_f64(0.125));
+
+        assert_eq!(BigFloat::from_f64(-3.0).powi_u128(3), BigFloat::from_f64(-27.0));
+        assert!(BigFloat::from_f64(-3.0).powi_u128(5001) < BigFloat::from_f64(0.0));
+
+        // 2^(10^18) = 1.6358327350851000594e301029995663981195
+        let result = base.powi_i64(10_i64.pow(18));
+        assert_eq!(result.exponent(), 301029995663981195_i128);
+        assert!((result.mantissa() - 1.6358327350851).abs() < 1e-12, "{}", result);
+
+        // 1.0001^(10^30) = 2.2123200987667230027e43427276862664854727552078 for
+        // the f64 nearest 1.0001; the power's ~1e-7 rounding limits the mantissa
+        let result = BigFloat::from_f64(1.0001).powi_u128(10_u128.pow(30));
+        assert_eq!(result.exponent(), 43427276862664854727552078_i128);
+        assert!((result.mantissa() - 2.212320098766723).abs() < 1e-5, "{}", result);
+    }
+
+    #[test]
+    fn test_powi_huge_exponent_range() {
+        let result = BigFloat::new(1.0, 1_000_000).powi_u128(u128::MAX);
+        assert_eq!(result.exponent().depth(), 1);
+        let tiny = BigFloat::new(1.0, -1_000_000).powi_u128(u128::MAX);
+        assert!(tiny.exponent().is_negative());
+        assert!(tiny > BigFloat::from_f64(0.0));
+    }
+
+    #[test]
+    fn test_special_values() {
+        let nan = BigFloat::from_f64(f64::NAN);
+        assert!(nan.ln().is_nan() && nan.exp().is_nan() && nan.sqrt().is_nan());
+        assert_eq!(nan.pow(&BigFloat::from_f64(0.0)), BigFloat::from_f64(1.0));
+        assert_eq!(BigFloat::from_f64(1.0).pow(&nan), BigFloat::from_f64(1.0));
+        assert!(BigFloat::from_f64(2.0).pow(&nan).is_nan());
+
+        assert_eq!(BigFloat::from_f64(-0.0).ln().mantissa(), f64::NEG_INFINITY);
+        assert!(BigFloat::from_f64(-0.0).sqrt().is_sign_negative());
+        assert!(BigFloat::from_f64(f64::NEG_INFINITY).sqrt().is_nan());
+        assert_eq!(BigFloat::from_f64(f64::NEG_INFINITY).exp(), BigFloat::from_f64(0.0));
+    }
+
+    #[test]
+    fn test_try_ln_and_sqrt() {
+        assert_eq!(BigFloat::new(1.0, 0).try_ln(), Ok(BigFloat::from_f64(0.0)));
+        assert_eq!(
+            BigFloat::from_f64(-1.0).try_ln(),
+            Err(BigFloatError::Domain { operation: "ln of a negative number" })
+        );
+        assert!(BigFloat::from_f64(0.0).try_ln().is_err());
+        assert!(BigFloat::from_f64(f64::NAN).try_ln().is_err());
+
+        assert_eq!(BigFloat::new(4.0, 1000).try_sqrt(), Ok(BigFloat::new(2.0, 500)));
+        assert!(BigFloat::from_f64(-0.0).try_sqrt().unwrap().is_sign_negative());
+        assert!(matches!(
+            BigFloat::new(-4.0, 1000).try_sqrt(),
+            Err(BigFloatError::Domain { .. })
+        ));
+    }
+
+    #[test]
+    fn test_try_pow() {
+        let two = BigFloat::from_f64(2.0);
+        assert_eq!(two.try_pow(&BigFloat::from_f64(10.0)), Ok(BigFloat::from_f64(1024.0)));
+        assert_eq!(
+            BigFloat::from_f64(0.0).try_pow(&BigFloat::from_f64(-1.0)),
+            Err(BigFloatError::DivisionByZero)
+        );
+        assert!(matches!(
+            BigFloat::from_f64(-8.0).try_pow(&BigFloat::from_f64(1.0 / 3.0)),
+            Err(BigFloatError::Domain { .. })
+        ));
+
+        let mut tower = BigFloat::new(1.0, 100);
+        for _ in 0..crate::exponent::MAX_EXPONENT_DEPTH {
+            tower = BigFloat::new(1.0, tower);
+        }
+        assert_eq!(tower.try_pow(&tower), Err(BigFloatError::ExponentOverflow));
+    }
+
     #[test]
     fn test_sqrt_basic() {
         let bf = BigFloat::from_f64(9.0);
@@ -203,4 +957,351 @@ mod tests {
         assert_eq!(zero.log10().to_f64_lossy(), f64::NEG_INFINITY);
         assert_eq!(zero.exp().to_f64_lossy(), 1.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_small_number_log() {
+        let bf = BigFloat::new(1.0, -5000);
+        assert!((bf.log10().to_f64_lossy() + 5000.0).abs() < 1e-10);
+        assert!((bf.ln().to_f64_lossy() + 5000.0 * std::f64::consts::LN_10).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_exp_fraction() {
+        let bf = BigFloat::from_f64(0.5);
+        let result = bf.exp();
+        assert!((result.to_f64_lossy() - 0.5_f64.exp()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_log10_across_boundary() {
+        let googolplex: BigFloat = "1e1e100".parse().unwrap();
+        assert_eq!(googolplex.log10(), BigFloat::new(1.0, 100));
+
+        let ln = googolplex.ln();
+        assert!((ln.mantissa() - LN_10).abs() < 1e-10);
+        assert_eq!(ln.exponent(), 100);
+    }
+
+    #[test]
+    fn test_exp_large_argument() {
+        let result = BigFloat::from_f64(1000.0).exp();
+        assert!((result.mantissa() - 1.9700711140170).abs() < 1e-9);
+        assert_eq!(result.exponent(), 434);
+
+        // e^(1e100) = 10^(4.34e99), which needs a BigFloat exponent
+        let result = BigFloat::new(1.0, 100).exp();
+        assert_eq!(result.exponent().depth(), 1);
+        assert!((result.log10().mantissa() - LOG10_E * 10.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_exp_precision() {
+        // e^(1e10) = 10^4342944819.032518276511289...
+        let result = BigFloat::from_f64(1e10).exp();
+        assert_eq!(result.exponent(), 4342944819);
+        assert!((result.mantissa() - 10.0_f64.powf(0.032518276511289)).abs() < 1e-9);
+
+        let result = BigFloat::from_f64(-1e10).exp();
+        assert_eq!(result.exponent(), -4342944820);
+        assert!((result.mantissa() - 10.0_f64.powf(1.0 - 0.032518276511289)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_exp_tiny_results() {
+        // e^-1000 = 5.075958897549457e-435
+        let result = BigFloat::from_f64(-1000.0).exp();
+        assert!((result.mantissa() - 5.075958897549457).abs() < 1e-12);
+        assert_eq!(result.exponent(), -435);
+
+        // Subnormal range of f64: e^-720 = 2.03...e-313
+        let result = BigFloat::from_f64(-720.0).exp();
+        assert!((result.mantissa() - 2.032230802424293).abs() < 1e-9);
+        assert_eq!(result.exponent(), -313);
+
+        let result = "-1e100".parse::<BigFloat>().unwrap().exp();
+        assert!(result.exponent().is_negative());
+        assert!(result > BigFloat::from_f64(0.0));
+        assert!(result.mantissa() >= 1.0 && result.mantissa() < 10.0);
+    }
+
+    #[test]
+    fn test_log10_split() {
+        let bf = BigFloat::new(2.0, 1i128 << 60);
+        let (integer_part, fraction) = bf.log10_split();
+        assert_eq!(integer_part, 1i128 << 60);
+        assert!((fraction - 2.0_f64.log10()).abs() < 1e-16);
+        assert_eq!(BigFloat::new(10.0_f64.powf(fraction), integer_part), bf);
+
+        let (integer_part, fraction) = BigFloat::new(-5.0, -42).log10_split();
+        assert_eq!(integer_part, -42);
+        assert!((fraction - 5.0_f64.log10()).abs() < 1e-16);
+    }
+
+    #[test]
+    fn test_log10_round_trip() {
+        let values = [
+            BigFloat::new(2.5, 0),
+            BigFloat::new(3.7, 12345),
+            BigFloat::new(9.99, -987654),
+            BigFloat::new(1.5, 1_000_000_000),
+        ];
+        for value in values {
+            let log = value.log10();
+            let round_trip = log.exp10();
+            // Rounding the log once costs at most half an ulp of it
+            let tolerance = log.to_f64_lossy().abs() * f64::EPSILON * std::f64::consts::LN_10;
+            let relative = ((round_trip.clone() - value.clone()) / value.clone()).abs();
+            assert!(relative < BigFloat::from_f64(tolerance), "{} -> {}", value, round_trip);
+            assert_eq!(round_trip.exponent(), value.exponent());
+        }
+
+        // Past 2^53 only the double-double log keeps the mantissa
+        for exponent in [1_000_000_000_000_002_i128, (1 << 53) + 1, 1 << 60, -(1 << 54)] {
+            let value = BigFloat::new(1.23456, exponent);
+            if exponent.abs() > 1 << 53 {
+                assert_eq!(value.log10().exp10().mantissa(), 1.0);
+            }
+            let round_trip = BigFloat::exp10_dd(&value.log10_dd());
+            assert_eq!(round_trip.exponent(), value.exponent());
+            assert!((round_trip.mantissa() - 1.23456).abs() < 1e-12, "{} -> {}", value, round_trip);
+        }
+    }
+
+    #[test]
+    fn test_log10_rounds_once() {
+        // (2^53 + 1) + log10(2) must not lose the odd unit to an early rounding of
+        // the exponent, and ln must match (1000 + log10 2) * ln 10
+        let bf = BigFloat::new(2.0, (1i128 << 53) + 1);
+        assert_eq!(bf.log10().to_f64_lossy(), 9007199254740994.0);
+        let ln = BigFloat::new(2.0, 1000).ln().to_f64_lossy();
+        assert!((ln - 2303.278240174606).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_exp10() {
+        let result = BigFloat::from_f64(123.25).exp10();
+        assert!((result.mantissa() - 10.0_f64.powf(0.25)).abs() < 1e-14);
+        assert_eq!(result.exponent(), 123);
+
+        // The fraction survives even when the integer part is far past f64
+        let result = BigFloat::from_f64(-1e6 - 0.5).exp10();
+        assert!((result.mantissa() - 10.0_f64.sqrt()).abs() < 1e-9);
+        assert_eq!(result.exponent(), -1_000_001);
+
+        let result = BigFloat::new(1.0, 100).exp10();
+        assert_eq!(result, "1e1e100".parse::<BigFloat>().unwrap());
+
+        // An integral power that fits an i128 becomes the exponent unrounded
+        let result = BigFloat::new(1.0, 30).exp10();
+        assert_eq!(result.exponent(), 10_i128.pow(30));
+        assert_eq!(result.to_string(), "1e1000000000000000000000000000000");
+        assert_eq!(BigFloat::new(-2.5, 36).exp10(), BigFloat::new(1.0, -25 * 10_i128.pow(35)));
+
+        // Powers between 1e15 and 2^53 still carry a fraction
+        let power = 1500000000000000.5;
+        assert_eq!(BigFloat::from_f64(power).exp10(), BigFloat::from_log10(power));
+        assert!((BigFloat::from_f64(power).exp10().mantissa() - 10.0_f64.sqrt()).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_exp_and_exp2_past_1e15() {
+        // Consecutive powers differ by exactly one factor of the base
+        let x = BigFloat::from_f64(2e15 + 0.5);
+        let previous = BigFloat::from_f64(2e15 - 0.5);
+        let ratio = (x.exp() / previous.exp()).to_f64().unwrap();
+        assert!((ratio - std::f64::consts::E).abs() < 1e-9, "{}", ratio);
+        let ratio = (x.exp2() / previous.exp2()).to_f64().unwrap();
+        assert!((ratio - 2.0).abs() < 1e-9, "{}", ratio);
+    }
+
+    #[test]
+    fn test_exp2() {
+        assert_eq!(BigFloat::from_f64(10.0).exp2(), BigFloat::from_f64(1024.0));
+
+        // 2^-5000 = 7.0798112610481729e-1506
+        let result = BigFloat::from_f64(-5000.0).exp2();
+        assert!((result.mantissa() - 7.079811261048173).abs() < 1e-12);
+        assert_eq!(result.exponent(), -1506);
+
+        let result = BigFloat::new(1.0, 50).exp2();
+        assert_eq!(result.exponent().depth(), 1);
+    }
+
+    #[test]
+    fn test_from_log10() {
+        let result = BigFloat::from_log10(5000.5);
+        assert!((result.mantissa() - 10.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(result.exponent(), 5000);
+
+        let value = BigFloat::new(4.2, -321);
+        let (integer_part, fraction) = value.log10_split();
+        let result = BigFloat::from_log10(integer_part as f64 + fraction);
+        assert!((result.mantissa() - 4.2).abs() < 1e-12);
+        assert_eq!(result.exponent(), -321);
+
+        assert_eq!(BigFloat::from_log10(f64::NEG_INFINITY), BigFloat::from_f64(0.0));
+    }
+
+    #[test]
+    fn test_scale10() {
+        let value = BigFloat::new(1.2345678901234567, 10);
+        let scaled = value.scale10(1i128 << 100);
+        assert_eq!(scaled.mantissa(), value.mantissa());
+        assert_eq!(scaled.exponent(), 10 + (1i128 << 100));
+        assert_eq!(scaled.scale10(-(1i128 << 100)), value);
+
+        let promoted = BigFloat::new(3.0, i128::MAX - 5).scale10(10);
+        assert_eq!(promoted.mantissa(), 3.0);
+        assert_eq!(promoted.exponent().depth(), 1);
+        assert_eq!(BigFloat::from_f64(0.0).scale10(5), BigFloat::from_f64(0.0));
+    }
+
+    #[test]
+    fn test_ldexp() {
+        assert_eq!(BigFloat::from_f64(3.0).ldexp(4), BigFloat::from_f64(48.0));
+        assert_eq!(BigFloat::from_f64(3.0).ldexp(-1), BigFloat::from_f64(1.5));
+
+        let result = BigFloat::from_f64(1.0).ldexp(5000);
+        assert!((result.mantissa() - 1.412467032139426).abs() < 1e-12);
+        assert_eq!(result.exponent(), 1505);
+    }
+
+    #[test]
+    fn test_pow_across_boundary() {
+        let googol = BigFloat::new(1.0, 100);
+        let googolplex = BigFloat::from_f64(10.0).pow(&googol);
+        assert!((googolplex.log10().mantissa() - 1.0).abs() < 1e-10);
+        assert_eq!(googolplex.log10().exponent(), 100);
+
+        let squared = googolplex.pow(&BigFloat::from_f64(2.0));
+        assert!((squared.log10().mantissa() - 2.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_pow10() {
+        let result = pow10(&BigFloat::from_f64(2.5));
+        assert!((result.mantissa() - 10.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(result.exponent(), 2);
+
+        let result = pow10(&BigFloat::from_f64(-2.5));
+        assert!((result.mantissa() - 10.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(result.exponent(), -3);
+    }
+
+    #[test]
+    fn test_tetrate() {
+        let two = BigFloat::from_f64(2.0);
+        assert!((two.tetrate(4.0).to_f64_lossy() - 65536.0).abs() < 1e-6);
+
+        let ten = BigFloat::from_f64(10.0);
+        assert_eq!(ten.tetrate(3.0), "1e1e10".parse::<BigFloat>().unwrap());
+        assert!((ten.tetrate(1.5).to_f64_lossy() - 10.0_f64.powf(10.0_f64.sqrt())).abs() < 1e-6);
+
+        // Beyond BigFloat's nesting depth the result saturates
+        assert_eq!(ten.tetrate(1000.0).mantissa(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_slog() {
+        let ten = BigFloat::from_f64(10.0);
+        let tower: BigFloat = "1e1e10".parse().unwrap();
+        assert!((tower.slog(&ten) - 3.0).abs() < 1e-9);
+        assert!((ten.tetrate(2.75).slog(&ten) - 2.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ssqrt() {
+        let result = BigFloat::from_f64(3125.0).ssqrt();
+        assert!((result.to_f64_lossy() - 5.0).abs() < 1e-9);
+
+        let huge = BigFloat::new(1.0, 1000);
+        let root = huge.ssqrt();
+        let back = root.pow(&root);
+        assert!((back.log10().to_f64_lossy() - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_lambert_w() {
+        assert_eq!(lambert_w(0.0), 0.0);
+        assert!((lambert_w(std::f64::consts::E) - 1.0).abs() < 1e-15);
+        assert!((lambert_w(-1.0 / std::f64::consts::E) + 1.0).abs() < 1e-7);
+        let w = lambert_w(1e300);
+        assert!((w * w.exp() / 1e300 - 1.0).abs() < 1e-12);
+        assert!(lambert_w(-1.0).is_nan());
+    }
+
+    #[test]
+    fn test_hyperop_levels() {
+        let three = BigFloat::from_f64(3.0);
+        assert_eq!(hyperop(&three, 0, 4), BigFloat::from_f64(5.0));
+        assert_eq!(hyperop(&three, 1, 4), BigFloat::from_f64(7.0));
+        assert_eq!(hyperop(&three, 2, 4), BigFloat::from_f64(12.0));
+        assert_eq!(hyperop(&three, 3, 4), BigFloat::from_f64(81.0));
+        assert_eq!(hyperop(&three, 4, 3), BigFloat::from_f64(7625597484987.0));
+    }
+
+    #[test]
+    fn test_up_arrow_exact() {
+        let two = BigFloat::from_f64(2.0);
+        assert_eq!(up_arrow(&two, 2, 4), BigFloat::from_f64(65536.0));
+        assert_eq!(up_arrow(&two, 3, 3), BigFloat::from_f64(65536.0));
+        assert_eq!(up_arrow(&two, 1000, 2), BigFloat::from_f64(4.0));
+        assert_eq!(up_arrow(&BigFloat::from_f64(1.0), 5, u64::MAX), BigFloat::from_f64(1.0));
+        assert_eq!(up_arrow(&two, 7, 0), BigFloat::from_f64(1.0));
+    }
+
+    #[test]
+    fn test_up_arrow_zero_base() {
+        let zero = BigFloat::from_f64(0.0);
+        assert_eq!(up_arrow(&zero, 2, 2), BigFloat::from_f64(1.0));
+        assert_eq!(up_arrow(&zero, 2, 3), BigFloat::from_f64(0.0));
+        assert_eq!(up_arrow(&zero, 2, u64::MAX), BigFloat::from_f64(0.0));
+        assert_eq!(up_arrow(&zero, 4, u64::MAX - 1), BigFloat::from_f64(1.0));
+        assert_eq!(conway_chain(&[0, 2_000_000_000, 3]), BigFloat::from_f64(1.0));
+    }
+
+    #[test]
+    fn test_up_arrow_compare_googology() {
+        // 3↑↑4 = 3^7625597484987 = 1.2580142906274913e3638334640024
+        let three_tower = up_arrow(&BigFloat::from_f64(3.0), 2, 4);
+        let value = three_tower.as_bigfloat().unwrap();
+        assert_eq!(value.exponent(), 3638334640024_i128);
+        assert!((value.mantissa() - 1.2580142906274913).abs() < 1e-12, "{}", value);
+
+        // 2↑↑↑4 = 2↑↑65536 is far beyond BigFloat but still a tower
+        let two_pentated = up_arrow(&BigFloat::from_f64(2.0), 3, 4);
+        assert!(matches!(two_pentated, HyperResult::Tower(_)));
+        assert!(two_pentated > three_tower);
+        assert!(two_pentated > "1e1e1e100".parse::<BigFloat>().unwrap());
+    }
+
+    #[test]
+    fn test_up_arrow_too_large() {
+        let result = up_arrow(&BigFloat::from_f64(3.0), 3, 4);
+        assert!(result.is_too_large());
+        assert!(result > "1e1e1e1e100".parse::<BigFloat>().unwrap());
+        assert!(result < BigFloat::from_f64(f64::INFINITY));
+        assert!(result.partial_cmp(&BigFloat::from_f64(f64::NAN)).is_none());
+        assert!(result.partial_cmp(&result).is_none());
+        assert!(result > up_arrow(&BigFloat::from_f64(2.0), 3, 4));
+    }
+
+    #[test]
+    fn test_conway_chain() {
+        assert_eq!(conway_chain(&[3, 4]), BigFloat::from_f64(81.0));
+        assert_eq!(conway_chain(&[3, 3, 2]), BigFloat::from_f64(7625597484987.0));
+        assert_eq!(conway_chain(&[5, 1, 100, 100]), BigFloat::from_f64(5.0));
+        assert_eq!(conway_chain(&[2, 2, 9, 9, 9]), BigFloat::from_f64(4.0));
+        // 2→3→1→2 truncates to 2→3
+        assert_eq!(conway_chain(&[2, 3, 1, 2]), BigFloat::from_f64(8.0));
+        assert_eq!(conway_chain(&[3, 2, 2]), BigFloat::from_f64(27.0));
+        assert!(conway_chain(&[3, 3, 3, 3]).is_too_large());
+        // 3→3→3 = 3↑↑↑3 = 3↑↑7625597484987 is still a tower
+        assert!(matches!(conway_chain(&[3, 3, 3]), HyperResult::Tower(_)));
+
+        // Zero links past the first two are undefined rather than a panic
+        assert!(conway_chain(&[2, 3, 0]).as_bigfloat().unwrap().is_nan());
+        assert!(conway_chain(&[2, 3, 4, 0]).as_bigfloat().unwrap().is_nan());
+        assert_eq!(conway_chain(&[2, 0, 3]), BigFloat::from_f64(1.0));
+    }
+}
@@ -0,0 +1,106 @@
+//! Operator forms shared by `BigFloatBase2`, `BigFloatDD` and `BigDecimal`.
+
+/// Implements the borrowed, primitive (`f64`, `i32`, `i64`) and assignment
+/// forms of `+`, `-`, `*` and `/` for `$type` by forwarding to its owned
+/// `Add`, `Sub`, `Mul` and `Div`. `$lift(primitive, &other)` turns a
+/// primitive operand into a `$type` to combine with `other`.
+macro_rules! impl_forwarded_ops {
+    ($type:ty, $lift:path) => {
+        $crate::operators::impl_forwarded_ops!(@op $type, $lift, Add, add, AddAssign, add_assign);
+        $crate::operators::impl_forwarded_ops!(@op $type, $lift, Sub, sub, SubAssign, sub_assign);
+        $crate::operators::impl_forwarded_ops!(@op $type, $lift, Mul, mul, MulAssign, mul_assign);
+        $crate::operators::impl_forwarded_ops!(@op $type, $lift, Div, div, DivAssign, div_assign);
+    };
+    (@op $type:ty, $lift:path, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl std::ops::$trait<&$type> for $type {
+            type Output = $type;
+
+            fn $method(self, other: &$type) -> $type {
+                std::ops::$trait::$method(self, other.clone())
+            }
+        }
+
+        impl std::ops::$trait<$type> for &$type {
+            type Output = $type;
+
+            fn $method(self, other: $type) -> $type {
+                std::ops::$trait::$method(self.clone(), other)
+            }
+        }
+
+        impl std::ops::$trait<&$type> for &$type {
+            type Output = $type;
+
+            fn $method(self, other: &$type) -> $type {
+                std::ops::$trait::$method(self.clone(), other.clone())
+            }
+        }
+
+        impl std::ops::$assign_trait for $type {
+            fn $assign_method(&mut self, other: $type) {
+                *self = std::ops::$trait::$method(std::mem::take(self), other);
+            }
+        }
+
+        impl std::ops::$assign_trait<&$type> for $type {
+            fn $assign_method(&mut self, other: &$type) {
+                *self = std::ops::$trait::$method(std::mem::take(self), other.clone());
+            }
+        }
+
+        $crate::operators::impl_forwarded_ops!(
+            @primitive $type, $lift, $trait, $method, $assign_trait, $assign_method; f64, i32, i64
+        );
+    };
+    (@primitive $type:ty, $lift:path, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident; $($t:ty),*) => {
+        $(
+            impl std::ops::$trait<$t> for $type {
+                type Output = $type;
+
+                fn $method(self, other: $t) -> $type {
+                    let other = $lift(other, &self);
+                    std::ops::$trait::$method(self, other)
+                }
+            }
+
+            impl std::ops::$trait<$t> for &$type {
+                type Output = $type;
+
+                fn $method(self, other: $t) -> $type {
+                    std::ops::$trait::$method(self.clone(), $lift(other, self))
+                }
+            }
+
+            impl std::ops::$trait<$type> for $t {
+                type Output = $type;
+
+                fn $method(self, other: $type) -> $type {
+                    std::ops::$trait::$method($lift(self, &other), other)
+                }
+            }
+
+            impl std::ops::$trait<&$type> for $t {
+                type Output = $type;
+
+                fn $method(self, other: &$type) -> $type {
+                    std::ops::$trait::$method($lift(self, other), other.clone())
+                }
+            }
+
+            impl std::ops::$assign_trait<$t> for $type {
+                fn $assign_method(&mut self, other: $t) {
+                    let other = $lift(other, self);
+                    *self = std::ops::$trait::$method(std::mem::take(self), other);
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_forwarded_ops;
+
+/// The `$lift` of `impl_forwarded_ops!` for types whose primitive operands
+/// convert on their own.
+pub(crate) fn convert<T: From<P>, P>(value: P, _other: &T) -> T {
+    T::from(value)
+}
@@ -0,0 +1,142 @@
+use crate::bigfloat::BigFloat;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// A `BigFloat` with a lawful total order, for use as a `BTreeMap`/`HashMap`
+/// key or when sorting and deduplicating.
+///
+/// Agrees with `partial_cmp` wherever that is defined, so -0 and +0 are the
+/// same key, and every NaN is one key that sorts above +∞. `BigFloat` itself
+/// is only `PartialEq`/`PartialOrd`, like f64; here equality agrees with the
+/// ordering and the hash, so NaN equals itself. See `total_cmp` for IEEE
+/// totalOrder.
+#[derive(Debug, Clone, Default)]
+pub struct OrderedBigFloat(pub BigFloat);
+
+impl OrderedBigFloat {
+    pub fn into_inner(self) -> BigFloat {
+        self.0
+    }
+}
+
+impl PartialEq for OrderedBigFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedBigFloat {}
+
+impl PartialOrd for OrderedBigFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedBigFloat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0.is_nan(), other.0.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+impl Hash for OrderedBigFloat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl Deref for OrderedBigFloat {
+    type Target = BigFloat;
+
+    fn deref(&self) -> &BigFloat {
+        &self.0
+    }
+}
+
+impl From<BigFloat> for OrderedBigFloat {
+    fn from(value: BigFloat) -> Self {
+        OrderedBigFloat(value)
+    }
+}
+
+impl From<OrderedBigFloat> for BigFloat {
+    fn from(value: OrderedBigFloat) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for OrderedBigFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeMap, HashSet};
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_hash_matches_equality() {
+        assert_eq!(hash_of(&BigFloat::from_f64(0.0)), hash_of(&BigFloat::from_f64(-0.0)));
+        assert_eq!(hash_of(&BigFloat::new(0.5, 3)), hash_of(&BigFloat::new(50.0, 1)));
+        assert_ne!(hash_of(&BigFloat::new(1.0, 3)), hash_of(&BigFloat::new(1.0, 4)));
+
+        let googolplex: BigFloat = "1e1e100".parse().unwrap();
+        assert_eq!(hash_of(&googolplex), hash_of(&BigFloat::from_f64(10.0).pow(&BigFloat::new(1.0, 100))));
+    }
+
+    #[test]
+    fn test_nan_is_one_key() {
+        let nan = OrderedBigFloat::from(BigFloat::from_f64(f64::NAN));
+        let negative_nan = OrderedBigFloat::from(BigFloat::from_f64(-f64::NAN));
+        assert_eq!(nan, negative_nan);
+        assert_eq!(hash_of(&nan), hash_of(&negative_nan));
+        assert!(nan > OrderedBigFloat::from(BigFloat::from_f64(f64::INFINITY)));
+        let zero = OrderedBigFloat::from(BigFloat::from_f64(0.0));
+        assert_eq!(zero.cmp(&OrderedBigFloat::from(BigFloat::from_f64(-0.0))), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_sort_and_dedup() {
+        let mut values: Vec<OrderedBigFloat> = ["1e1e100", "NaN", "-0", "3", "0", "-inf", "NaN", "1e-500", "3"]
+            .iter()
+            .map(|s| s.parse::<BigFloat>().unwrap().into())
+            .collect();
+        values.sort();
+        values.dedup();
+
+        let sorted: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+        assert_eq!(sorted, ["-∞", "-0", "1e-500", "3", "1e1e100", "NaN"]);
+
+        let unique: HashSet<OrderedBigFloat> = values.into_iter().collect();
+        assert_eq!(unique.len(), 6);
+    }
+
+    #[test]
+    fn test_btreemap_thresholds() {
+        let mut tiers = BTreeMap::new();
+        tiers.insert(OrderedBigFloat(BigFloat::new(1.0, 3)), "bronze");
+        tiers.insert(OrderedBigFloat(BigFloat::new(1.0, 100)), "silver");
+        tiers.insert(OrderedBigFloat("1e1e10".parse().unwrap()), "gold");
+
+        let score = OrderedBigFloat(BigFloat::new(5.0, 5000));
+        let (threshold, tier) = tiers.range(..=score).next_back().unwrap();
+        assert_eq!(*tier, "silver");
+        assert_eq!(threshold.exponent(), 100);
+    }
+}
@@ -0,0 +1,385 @@
+//! Powers of ten for normalization, without `log10` or `powi`.
+//!
+//! `POWERS` holds 10^0 through 10^308 as double-doubles. Up to 10^22 the
+//! f64 part is exact, so scaling by it is a single correctly rounded
+//! multiplication or division; past that the low part makes the product or
+//! quotient accurate to about 2^-104 before it is rounded once. The decade
+//! of a value is estimated from its binary exponent and then corrected
+//! against the scaled result.
+
+use crate::double_double::{two_product, DoubleDouble};
+
+/// The largest power of ten that is exact in an f64.
+pub(crate) const MAX_EXACT: usize = 22;
+
+// Powers past this scale any f64 to zero or infinity
+const MAX_POWER: i128 = 1000;
+
+// floor(log10 2 · 2^18), so (e · LOG10_2_SCALED) >> 18 is within one of
+// floor(e · log10 2) for every f64 binary exponent
+pub(crate) const LOG10_2_SCALED: i32 = 78913;
+
+/// 10^`power` if it is exact in an f64, i.e. for 0 <= power <= 22.
+pub(crate) fn exact(power: u128) -> Option<f64> {
+    match usize::try_from(power) {
+        Ok(index) if index <= MAX_EXACT => Some(POWERS[index].0),
+        _ => None,
+    }
+}
+
+/// `value` × 10^`power`, rounded once. Powers beyond ±1000 saturate like the
+/// f64 result would.
+pub(crate) fn scale_pow10(value: f64, power: i128) -> f64 {
+    let magnitude = power.unsigned_abs();
+    if let Some(factor) = exact(magnitude) {
+        return if power >= 0 { value * factor } else { value / factor };
+    }
+    match usize::try_from(magnitude).ok().and_then(|index| POWERS.get(index)) {
+        Some(&(hi, lo)) if power > 0 => {
+            let (product, error) = two_product(value, hi);
+            if !product.is_finite() {
+                return product;
+            }
+            product + (error + value * lo)
+        }
+        Some(&(hi, lo)) => {
+            // One step of long division by hi + lo
+            let quotient = value / hi;
+            let (product, error) = two_product(quotient, hi);
+            let remainder = value - product - error - quotient * lo;
+            quotient + remainder / hi
+        }
+        None => {
+            let power = power.clamp(-MAX_POWER, MAX_POWER) as i32;
+            DoubleDouble::from_f64(value).scale10(power).to_f64()
+        }
+    }
+}
+
+/// Splits a positive finite value into a mantissa in [1, 10) and its decimal
+/// exponent, with the mantissa rounded once from the exact quotient.
+pub(crate) fn split_decade(value: f64) -> (f64, i128) {
+    let mut exponent = decade_estimate(value) as i128;
+    let mut mantissa = scale_pow10(value, -exponent);
+
+    // Correction step: the estimate can be one decade off, and a quotient
+    // just below a power of ten can round up onto it
+    if mantissa < 1.0 {
+        exponent -= 1;
+        mantissa = scale_pow10(value, -exponent);
+    }
+    if mantissa >= 10.0 {
+        exponent += 1;
+        mantissa = scale_pow10(value, -exponent).max(1.0);
+    }
+    (mantissa, exponent)
+}
+
+/// floor(log10 2^e) for the binary exponent e of a positive finite value:
+/// the decade of `value`, or one below it.
+pub(crate) fn decade_estimate(value: f64) -> i32 {
+    (binary_exponent(value) * LOG10_2_SCALED) >> 18
+}
+
+/// `value` × 10^`power` if that is exactly an integer that fits an `i128`.
+pub(crate) fn integer_value(value: f64, power: i128) -> Option<i128> {
+    if value == 0.0 {
+        return Some(0);
+    }
+    if !value.is_finite() || power < 0 {
+        return None;
+    }
+
+    // value = odd × 2^shift, so value × 10^power = odd × 5^power × 2^(power + shift)
+    let bits = value.to_bits();
+    let biased = ((bits >> 52) & 0x7ff) as i128;
+    let fraction = bits & ((1 << 52) - 1);
+    let (significand, exponent) = if biased == 0 { (fraction, -1074) } else { (fraction | 1 << 52, biased - 1075) };
+    let zeros = significand.trailing_zeros();
+    let (odd, shift) = (significand >> zeros, exponent + zeros as i128 + power);
+    if shift < 0 {
+        return None;
+    }
+
+    let product = (odd as u128).checked_mul(5_u128.checked_pow(u32::try_from(power).ok()?)?)?;
+    if shift >= product.leading_zeros() as i128 {
+        return None;
+    }
+    let magnitude = (product << shift) as i128;
+    Some(if value < 0.0 { -magnitude } else { magnitude })
+}
+
+fn binary_exponent(value: f64) -> i32 {
+    let biased = ((value.to_bits() >> 52) & 0x7ff) as i32;
+    if biased == 0 {
+        // Subnormal: scaling by 2^64 is exact and makes it normal
+        return binary_exponent(value * 18446744073709551616.0) - 64;
+    }
+    biased - 1023
+}
+
+/// 10^k as (hi, lo) for 0 <= k <= 308: hi is the nearest f64 and lo the
+/// nearest f64 to the remainder 10^k - hi.
+pub(crate) const POWERS: [(f64, f64); 309] = [
+    (1e0, 0.0), (1e1, 0.0), (1e2, 0.0),
+    (1e3, 0.0), (1e4, 0.0), (1e5, 0.0),
+    (1e6, 0.0), (1e7, 0.0), (1e8, 0.0),
+    (1e9, 0.0), (1e10, 0.0), (1e11, 0.0),
+    (1e12, 0.0), (1e13, 0.0), (1e14, 0.0),
+    (1e15, 0.0), (1e16, 0.0), (1e17, 0.0),
+    (1e18, 0.0), (1e19, 0.0), (1e20, 0.0),
+    (1e21, 0.0), (1e22, 0.0), (1e23, 8388608.0),
+    (1e24, 16777216.0), (1e25, -905969664.0), (1e26, -4764729344.0),
+    (1e27, -13287555072.0), (1e28, 416880263168.0), (1e29, 8566849142784.0),
+    (1e30, -19884624838656.0), (1e31, 364103705034752.0), (1e32, -5366162204393472.0),
+    (1e33, 5.442476901295718e16), (1e34, 5.4424769012957184e17), (1e35, 3.1366338920820244e18),
+    (1e36, -4.242063737401796e19), (1e37, 4.6123734179787886e20), (1e38, 2.251190176543966e21),
+    (1e39, 6.029083362839682e22), (1e40, -3.037860284270037e23), (1e41, -6.200086450407783e23),
+    (1e42, -4.488571267807592e25), (1e43, -1.393721169594141e26), (1e44, -8.821361405306423e27),
+    (1e45, 7.024271097546445e28), (1e46, 6.860180964052979e28), (1e47, -4.38458430450762e30),
+    (1e48, -4.38458430450762e31), (1e49, 5.3509723052451824e32), (1e50, -7.629769841091887e33),
+    (1e51, 6.779051325638372e33), (1e52, 6.779051325638372e34), (1e53, 6.779051325638373e35),
+    (1e54, -7.829154040459625e37), (1e55, -1.0235067020408552e38), (1e56, -9.190283508143379e39),
+    (1e57, -4.834669211555366e40), (1e58, 5.618805100255864e41), (1e59, 2.831211950439536e42),
+    (1e60, 5.061286470292598e43), (1e61, 5.061286470292598e44), (1e62, -3.5021996859431613e45),
+    (1e63, -5.785795994272697e46), (1e64, -2.1320419009454396e47), (1e65, 7.909613737163662e47),
+    (1e66, 5.467766613175255e49), (1e67, 1.726322421608144e50), (1e68, 4.719477774861833e51),
+    (1e69, -7.253143638152923e52), (1e70, -7.253143638152923e53), (1e71, -4.1881525564211456e54),
+    (1e72, 5.619818905120543e55), (1e73, 1.6966303205038675e56), (1e74, 4.8351811881972075e57),
+    (1e75, 7.34602188235188e58), (1e76, -4.706013449590547e59), (1e77, 1.721738727445414e60),
+    (1e78, -8.493621433689703e60), (1e79, 3.2643992499340446e62), (1e80, -2.6609864708367274e61),
+    (1e81, 7.871812010433421e64), (1e82, 3.6593203436911345e65), (1e83, -3.0806663230965258e66),
+    (1e84, -5.77666098981159e67), (1e85, -1.4630695230674873e68), (1e86, -1.4630695230674873e69),
+    (1e87, 4.058327554364964e70), (1e88, 4.0583275543649637e71), (1e89, 5.246334248081951e71),
+    (1e90, 3.35158872845361e73), (1e91, -7.95623248612805e74), (1e92, -4.337729697461919e75),
+    (1e93, -4.3377296974619187e76), (1e94, -2.0218879127155946e77), (1e95, -2.0218879127155947e78),
+    (1e96, -4.9861653971908895e79), (1e97, -7.357587384771125e80), (1e98, 2.309629754856292e80),
+    (1e99, 3.266383119588331e82), (1e100, -1.5902891109759918e83), (1e101, 2.295048673475466e84),
+    (1e102, 2.2950486734754662e85), (1e103, -1.915675085734669e85), (1e104, -1.9156750857346687e86),
+    (1e105, 6.174169917471802e88), (1e106, -9.103599905036844e89), (1e107, 3.118615952970073e90),
+    (1e108, -3.399899171300283e91), (1e109, 1.814912928116002e92), (1e110, -2.3569367514170256e93),
+    (1e111, 4.318022735835818e94), (1e112, 6.988006530736956e95), (1e113, -1.5559416129466842e96),
+    (1e114, -1.5559416129466843e97), (1e115, -1.5559416129466843e98), (1e116, -1.5559416129466843e99),
+    (1e117, -5.0555427725995036e100), (1e118, 3.343500010567262e101), (1e119, 5.583244752745067e102),
+    (1e120, 1.9996531652605798e103), (1e121, -3.734093374714599e104), (1e122, -1.4405947587245274e105),
+    (1e123, 2.229003026859587e106), (1e124, 5.1646812553268785e107), (1e125, 7.513223838100712e108),
+    (1e126, 7.513223838100712e109), (1e127, 4.5070893321502055e110), (1e128, -7.51744869165182e111),
+    (1e129, 1.7825564358147585e111), (1e130, -5.978307824605161e113), (1e131, 8.797444499042768e114),
+    (1e132, 9.170432597638724e114), (1e133, -2.235117235947686e116), (1e134, 7.851796350329301e117),
+    (1e135, 3.817030915818506e118), (1e136, -5.866406127007401e119), (1e137, -3.2841562489204925e120),
+    (1e138, -3.2841562489204927e121), (1e139, -3.2841562489204925e122), (1e140, -5.928380124081487e123),
+    (1e141, -1.697621923823896e124), (1e142, -5.082228484029969e125), (1e143, -2.3745432358651105e126),
+    (1e144, -2.3745432358651106e127), (1e145, 1.091293881785908e128), (1e146, 6.636633270027537e129),
+    (1e147, 2.200361759434234e130), (1e148, -4.897672657515052e131), (1e149, -4.897672657515052e132),
+    (1e150, 1.9164403827562624e133), (1e151, -1.717753238721772e134), (1e152, -4.6251081359041995e135),
+    (1e153, 2.6659699587684626e134), (1e154, -3.6947545688058227e137), (1e155, -7.176231540910168e137),
+    (1e156, 1.6640819776808277e139), (1e157, 1.6640819776808279e140), (1e158, 4.712664546348789e141),
+    (1e159, 7.151530601283158e142), (1e160, -6.528407745068227e142), (1e161, -3.774589324822815e144),
+    (1e162, 6.21500603618836e145), (1e163, 6.21500603618836e146), (1e164, -1.7833499485879184e146),
+    (1e165, 1.0051010654816651e149), (1e166, 5.959272394946475e149), (1e167, -3.860899428741951e150),
+    (1e168, 6.613950516525703e151), (1e169, 6.613950516525703e152), (1e170, -3.441905430931245e153),
+    (1e171, 4.602779327034313e154), (1e172, -8.2687162857105805e155), (1e173, -1.4039186255799706e156),
+    (1e174, -6.895756753684458e157), (1e175, 6.284654753766313e158), (1e176, -7.44898050207432e158),
+    (1e177, -7.44898050207432e159), (1e178, -5.243811844750628e161), (1e179, 1.954450226518486e162),
+    (1e180, -9.248546019891598e162), (1e181, 8.288920849235307e164), (1e182, -6.453119872723839e165),
+    (1e183, 5.340512704843477e166), (1e184, -1.735666841696913e167), (1e185, 2.0382955831246285e168),
+    (1e186, 2.0382955831246284e169), (1e187, 9.284303438781988e170), (1e188, -2.309309130269787e171),
+    (1e189, -2.309309130269787e172), (1e190, -7.255917159731878e173), (1e191, -7.2559171597318776e174),
+    (1e192, -4.09008802087614e175), (1e193, -6.622751331960731e176), (1e194, 5.534032561245304e177),
+    (1e195, 2.292223523057028e178), (1e196, 4.885670753607649e179), (1e197, 4.885670753607649e180),
+    (1e198, -1.75355415660194e181), (1e199, -9.720624048853447e182), (1e200, 3.0266877787489637e183),
+    (1e201, -3.771878529305655e184), (1e202, 9.825254086803583e185), (1e203, 1.1230892124936706e186),
+    (1e204, 1.1230892124936706e187), (1e205, -1.6616035472855014e188), (1e206, -3.889357755108839e189),
+    (1e207, -3.889357755108839e190), (1e208, 1.8136930169189052e191), (1e209, -7.311188218325486e192),
+    (1e210, 7.288621758065539e193), (1e211, 4.368659762787335e194), (1e212, 9.040598955232462e195),
+    (1e213, 1.5654962473202578e196), (1e214, 4.55553733048514e197), (1e215, 9.33960306354895e198),
+    (1e216, -2.142154695804196e199), (1e217, 3.9814494425174824e200), (1e218, -8.265758834125874e201),
+    (1e219, 3.491561111451748e202), (1e220, 3.562757926310489e202), (1e221, -4.6601807174820695e204),
+    (1e222, -4.66018071748207e205), (1e223, -4.6601807174820696e206), (1e224, 3.0450964820516807e207),
+    (1e225, 7.154577655136347e208), (1e226, 3.866992716668614e209), (1e227, -9.28334703720232e210),
+    (1e228, 7.549087847752475e211), (1e229, 8.161138937705572e211), (1e230, -9.956644432600512e213),
+    (1e231, -5.647541102052084e214), (1e232, -5.647541102052084e215), (1e233, 2.6259372926008967e216),
+    (1e234, -1.7865845178806931e217), (1e235, -5.316601966265965e218), (1e236, -5.316601966265965e219),
+    (1e237, 5.979453868566905e220), (1e238, -4.86475973287265e221), (1e239, 9.188208545617794e221),
+    (1e240, -1.3946113804119925e223), (1e241, -5.096102956370027e224), (1e242, -5.0961029563700274e225),
+    (1e243, -7.46505756498317e226), (1e244, -7.4650575649831695e227), (1e245, -4.432795665958348e228),
+    (1e246, -6.858605185178205e229), (1e247, 4.785280507077112e230), (1e248, -4.529828046727142e231),
+    (1e249, 7.89031669167853e232), (1e250, 7.89031669167853e233), (1e251, -4.827911520448878e234),
+    (1e252, -9.915202805299841e235), (1e253, 6.364129306223241e236), (1e254, 6.364129306223241e237),
+    (1e255, 1.1547430305358546e238), (1e256, -3.012765990014054e239), (1e257, -3.0127659900140542e240),
+    (1e258, -5.679971763165996e241), (1e259, 7.122615947963324e242), (1e260, -6.5334776105746174e243),
+    (1e261, 7.122615947963324e244), (1e262, -1.6172839295009582e245), (1e263, -1.6172839295009584e246),
+    (1e264, -4.414051890289529e247), (1e265, -6.651466258920385e248), (1e266, -3.071603269111015e249),
+    (1e267, 2.6561775145839772e250), (1e268, 2.6561775145839774e251), (1e269, -4.675381888545613e252),
+    (1e270, -4.675381888545613e253), (1e271, 4.709014147460262e254), (1e272, -6.552261095746788e255),
+    (1e273, 5.459765830340733e256), (1e274, 7.862171215558236e257), (1e275, 4.01832259921023e258),
+    (1e276, -5.2069140800249854e259), (1e277, -2.8678785109953724e259), (1e278, 3.6493131320408215e261),
+    (1e279, -5.797329227496039e262), (1e280, -3.27822459828621e263), (1e281, -3.2782245982862097e264),
+    (1e282, -3.27822459828621e265), (1e283, 4.460464822646387e266), (1e284, -7.921438250845768e267),
+    (1e285, 1.9840842079479558e268), (1e286, -3.2988611034086966e269), (1e287, -7.525217352494019e270),
+    (1e288, -7.6304735395750355e270), (1e289, -6.172783352786716e272), (1e290, -6.172783352786716e273),
+    (1e291, 4.2139097649653716e274), (1e292, -1.3256598978357416e275), (1e293, 7.53765156264604e276),
+    (1e294, -6.64364677412481e277), (1e295, 1.8651322279376996e278), (1e296, 1.8651322279376996e279),
+    (1e297, -1.765280146275638e280), (1e298, 4.043379652465702e281), (1e299, -5.250476025520442e282),
+    (1e300, -5.250476025520442e283), (1e301, -5.250476025520442e284), (1e302, -7.629703079084895e285),
+    (1e303, -1.6176507678645645e284), (1e304, 6.0746447494463536e287), (1e305, 6.074644749446354e288),
+    (1e306, -1.7216064596736455e289), (1e307, 1.3968940239743542e290), (1e308, -1.0979063629440455e291),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    // The previous normalization, kept as the baseline for the accuracy comparison
+    fn split_decade_log10(value: f64) -> (f64, i128) {
+        let mut exponent = value.log10().floor() as i128;
+        let power = -exponent as i32;
+        let mut mantissa = if power >= 0 { value * 10.0_f64.powi(power) } else { value / 10.0_f64.powi(-power) };
+        if mantissa >= 10.0 {
+            mantissa /= 10.0;
+            exponent += 1;
+        } else if mantissa < 1.0 {
+            mantissa *= 10.0;
+            exponent -= 1;
+        }
+        (mantissa, exponent)
+    }
+
+    /// The mantissa and exponent read off the exact decimal expansion, which
+    /// never needs more than 767 significant digits.
+    fn split_decade_reference(value: f64) -> (f64, i128) {
+        let digits = format!("{:.767e}", value);
+        let (mantissa, exponent) = digits.split_once('e').unwrap();
+        let (mantissa, exponent): (f64, i128) = (mantissa.parse().unwrap(), exponent.parse().unwrap());
+        // A mantissa just below 10 can round up to it
+        if mantissa == 10.0 { (1.0, exponent + 1) } else { (mantissa, exponent) }
+    }
+
+    /// Values spread over the whole f64 range, subnormals included.
+    fn sample_values(count: usize) -> Vec<f64> {
+        let mut rng = XorShift::new(0x2545_f491_4f6c_dd1d);
+        (0..count)
+            .map(|_| f64::from_bits(rng.next_u64() >> 1))
+            .filter(|value| value.is_finite() && *value != 0.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_table() {
+        for (power, &(hi, lo)) in POWERS.iter().enumerate() {
+            assert_eq!(hi, format!("1e{}", power).parse::<f64>().unwrap());
+            assert!(lo.abs() <= hi * f64::EPSILON / 2.0);
+            if power <= MAX_EXACT {
+                assert_eq!(hi as u128, 10_u128.pow(power as u32));
+                assert_eq!(lo, 0.0);
+            }
+        }
+        // 10^38 = 99999999999999997748809823456034029568 + 2251190176543965970432
+        assert_eq!(POWERS[38], (1e38, 2251190176543965970432.0));
+        assert_eq!(exact(22), Some(1e22));
+        assert_eq!(exact(23), None);
+        assert_eq!(exact(u128::MAX), None);
+    }
+
+    #[test]
+    fn test_decade_boundaries_are_exact() {
+        for exponent in -323..=308 {
+            // Most f64 powers of ten are a little above or below the real
+            // one, so check them and their neighbours against the reference
+            let value: f64 = format!("1e{}", exponent).parse().unwrap();
+            for value in [f64::from_bits(value.to_bits() - 1), value, f64::from_bits(value.to_bits() + 1)] {
+                let (mantissa, decade) = split_decade(value);
+                assert!((1.0..10.0).contains(&mantissa), "{:e}: {}", value, mantissa);
+                assert_eq!((mantissa, decade), split_decade_reference(value), "{:e}", value);
+            }
+            if (0..=22).contains(&exponent) {
+                assert_eq!(split_decade(value), (1.0, exponent));
+            }
+        }
+        assert_eq!(split_decade(1000.0), (1.0, 3));
+        assert_eq!(split_decade(0.001), (1.0, -3));
+    }
+
+    #[test]
+    fn test_matches_correctly_rounded_reference() {
+        for value in sample_values(20_000) {
+            let value = value.abs();
+            assert_eq!(split_decade(value), split_decade_reference(value), "{:e}", value);
+        }
+        for value in [f64::MAX, f64::MIN_POSITIVE, 5e-324, 1e23, 0.7953400502740153] {
+            assert_eq!(split_decade(value), split_decade_reference(value), "{:e}", value);
+        }
+    }
+
+    #[test]
+    fn test_scale_pow10_range_checks() {
+        assert_eq!(scale_pow10(1.5, 22), 1.5e22);
+        assert_eq!(scale_pow10(1.5, -22), 1.5e-22);
+        assert_eq!(scale_pow10(1.0, 308), 1e308);
+        assert_eq!(scale_pow10(1.0, -323), 1e-323);
+        assert_eq!(scale_pow10(1e-300, 600), 1e300);
+        assert_eq!(scale_pow10(1.0, 309), f64::INFINITY);
+        assert_eq!(scale_pow10(-1.0, i128::MAX), f64::NEG_INFINITY);
+        assert_eq!(scale_pow10(1.0, i128::MIN), 0.0);
+    }
+
+    #[test]
+    fn test_integer_value() {
+        assert_eq!(integer_value(1.0, 35), Some(10_i128.pow(35)));
+        assert_eq!(integer_value(-1.5, 1), Some(-15));
+        assert_eq!(integer_value(1.25, 2), Some(125));
+        assert_eq!(integer_value(1.5, 37), Some(15 * 10_i128.pow(36)));
+        // 1.2345 and 1.7 are binary fractions a little off their decimal digits
+        assert_eq!(integer_value(1.2345, 4), None);
+        assert_eq!(integer_value(1.7, 37), None);
+        assert_eq!(integer_value(2.0, 38), None);
+        assert_eq!(integer_value(0.5, 0), None);
+        assert_eq!(integer_value(5.0, -1), None);
+        assert_eq!(integer_value(0.0, -1), Some(0));
+        assert_eq!(integer_value(f64::INFINITY, 0), None);
+    }
+
+    #[test]
+    fn test_more_accurate_than_log10_baseline() {
+        let (mut table_misses, mut log10_misses) = (0, 0);
+        for value in sample_values(20_000) {
+            let value = value.abs();
+            let reference = split_decade_reference(value);
+            table_misses += (split_decade(value) != reference) as usize;
+            log10_misses += (split_decade_log10(value) != reference) as usize;
+        }
+        assert_eq!(table_misses, 0);
+        assert!(log10_misses > 0);
+    }
+
+    /// Timing of the table against the `log10`/`powi` baseline. It only
+    /// reports, since wall-clock numbers depend on the machine; run it with
+    /// `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_split_decade_against_log10() {
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        let values: Vec<f64> = sample_values(1_000_000).into_iter().map(f64::abs).collect();
+        let time = |split: fn(f64) -> (f64, i128)| {
+            let start = Instant::now();
+            for _ in 0..10 {
+                for &value in &values {
+                    black_box(split(black_box(value)));
+                }
+            }
+            start.elapsed()
+        };
+        let table = time(split_decade);
+        let log10 = time(split_decade_log10);
+        println!(
+            "split_decade over {} values: table {:?}, log10/powi {:?} ({:.2}x)",
+            10 * values.len(),
+            table,
+            log10,
+            log10.as_secs_f64() / table.as_secs_f64()
+        );
+    }
+}
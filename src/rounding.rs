@@ -0,0 +1,70 @@
+/// How a result that falls between two representable values is rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /// To the nearest value, ties to the one with an even last digit.
+    #[default]
+    NearestEven,
+    /// To the nearest value, ties away from zero ("round half up").
+    NearestAway,
+    /// Toward zero (truncation).
+    TowardZero,
+    /// Away from zero.
+    AwayFromZero,
+    /// Toward +∞.
+    Up,
+    /// Toward -∞.
+    Down,
+}
+
+impl RoundingMode {
+    /// Whether a magnitude should be rounded up to the next representable
+    /// value, given the sign, whether the kept part is odd, and how the
+    /// discarded part compares with half a unit in the last place.
+    pub(crate) fn rounds_away(self, negative: bool, odd: bool, discarded: Discarded) -> bool {
+        if discarded == Discarded::Zero {
+            return false;
+        }
+        match self {
+            RoundingMode::NearestEven => {
+                discarded == Discarded::AboveHalf || discarded == Discarded::Half && odd
+            }
+            RoundingMode::NearestAway => discarded >= Discarded::Half,
+            RoundingMode::TowardZero => false,
+            RoundingMode::AwayFromZero => true,
+            RoundingMode::Up => !negative,
+            RoundingMode::Down => negative,
+        }
+    }
+}
+
+/// The part of a value that rounding discards, relative to half a unit in
+/// the last kept place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Discarded {
+    Zero,
+    BelowHalf,
+    Half,
+    AboveHalf,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounds_away() {
+        use Discarded::*;
+        use RoundingMode::*;
+
+        assert!(!NearestEven.rounds_away(false, false, Half));
+        assert!(NearestEven.rounds_away(false, true, Half));
+        assert!(NearestEven.rounds_away(true, false, AboveHalf));
+        assert!(NearestAway.rounds_away(false, false, Half));
+        assert!(!NearestAway.rounds_away(false, true, BelowHalf));
+        assert!(!TowardZero.rounds_away(true, true, AboveHalf));
+        assert!(AwayFromZero.rounds_away(true, false, BelowHalf));
+        assert!(Up.rounds_away(false, false, BelowHalf) && !Up.rounds_away(true, false, AboveHalf));
+        assert!(Down.rounds_away(true, false, BelowHalf) && !Down.rounds_away(false, false, AboveHalf));
+        assert!(!AwayFromZero.rounds_away(false, false, Zero));
+    }
+}
@@ -0,0 +1,29 @@
+//! Helpers shared by the unit tests.
+
+/// xorshift64: reproducible pseudo-random samples without a dependency.
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self {
+        XorShift(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// A value in [0, 1) from the top 53 bits of `bits`.
+pub(crate) fn unit(bits: u64) -> f64 {
+    (bits >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+/// A mantissa in [1, 10) from the top 53 bits of `bits`, negated when the
+/// lowest bit is set.
+pub(crate) fn mantissa(bits: u64) -> f64 {
+    let value = 1.0 + unit(bits) * 9.0;
+    if bits & 1 == 1 { -value } else { value }
+}
@@ -1,16 +1,20 @@
+use crate::accumulator::Accumulator;
 use crate::bigfloat::BigFloat;
+use crate::mantissa::Mantissa;
 use std::cmp::Ordering;
+use std::iter::{Product, Sum};
+use std::hash::{Hash, Hasher};
 
-impl PartialOrd for BigFloat {
+impl<M: Mantissa> PartialOrd for BigFloat<M> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         // Handle NaN cases
-        if self.mantissa.is_nan() || other.mantissa.is_nan() {
+        if self.is_nan() || other.is_nan() {
             return None;
         }
 
         // Handle infinity cases
-        if self.mantissa.is_infinite() || other.mantissa.is_infinite() {
-            return self.mantissa.partial_cmp(&other.mantissa);
+        if self.is_infinite() || other.is_infinite() {
+            return self.mantissa().partial_cmp(&other.mantissa());
         }
 
         // Handle zero cases
@@ -18,15 +22,15 @@ impl PartialOrd for BigFloat {
             return Some(Ordering::Equal);
         }
         if self.is_zero() {
-            return Some(if other.mantissa > 0.0 { Ordering::Less } else { Ordering::Greater });
+            return Some(if other.mantissa() > M::default() { Ordering::Less } else { Ordering::Greater });
         }
         if other.is_zero() {
-            return Some(if self.mantissa > 0.0 { Ordering::Greater } else { Ordering::Less });
+            return Some(if self.mantissa() > M::default() { Ordering::Greater } else { Ordering::Less });
         }
 
         // Compare signs first
-        let self_sign = self.mantissa.is_sign_positive();
-        let other_sign = other.mantissa.is_sign_positive();
+        let self_sign = self.mantissa().to_f64().is_sign_positive();
+        let other_sign = other.mantissa().to_f64().is_sign_positive();
         
         match (self_sign, other_sign) {
             (true, false) => return Some(Ordering::Greater),
@@ -39,12 +43,8 @@ impl PartialOrd for BigFloat {
         
         match exp_cmp {
             Ordering::Equal => {
-                // Same exponent, compare mantissas
-                if self_sign {
-                    self.mantissa.partial_cmp(&other.mantissa)
-                } else {
-                    other.mantissa.partial_cmp(&self.mantissa) // Flip for negative numbers
-                }
+                // Same exponent, compare mantissas (the sign is already part of them)
+                self.mantissa().partial_cmp(&other.mantissa())
             }
             Ordering::Greater => {
                 // self has larger exponent
@@ -66,50 +66,92 @@ impl PartialOrd for BigFloat {
     }
 }
 
-impl Eq for BigFloat {}
-
-impl Ord for BigFloat {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+// Values are canonical, so hashing the parts matches `==`; the zeros and the
+// NaNs are the only values with several representations
+impl Hash for BigFloat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.is_zero() {
+            0.0_f64.to_bits().hash(state);
+        } else if self.is_nan() {
+            f64::NAN.to_bits().hash(state);
+        } else {
+            self.mantissa().to_bits().hash(state);
+        }
+        self.exponent_ref().hash(state);
     }
 }
 
+/// The kind of value a `BigFloat` holds, after `std::num::FpCategory`.
+///
+/// There is no subnormal category: the exponent never runs out of range
+/// below the mantissa, so every finite non-zero value is normal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Nan,
+    Infinite,
+    Zero,
+    Normal,
+}
+
 impl BigFloat {
-    pub fn abs(&self) -> BigFloat {
-        if self.mantissa < 0.0 {
-            BigFloat {
-                mantissa: -self.mantissa,
-                exponent: self.exponent.clone(),
-            }
+    pub fn classify(&self) -> Category {
+        if self.is_nan() {
+            Category::Nan
+        } else if self.is_infinite() {
+            Category::Infinite
+        } else if self.is_zero() {
+            Category::Zero
         } else {
-            self.clone()
+            Category::Normal
         }
     }
 
+    /// IEEE 754 totalOrder: -NaN < -∞ < negative values < -0 < +0 <
+    /// positive values < +∞ < +NaN.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        if self.is_nan() || other.is_nan() {
+            // Stand in ±1 for ordinary values and let f64 order the NaNs
+            let proxy = |value: &BigFloat| {
+                if value.is_nan() { value.mantissa() } else { 1.0_f64.copysign(value.mantissa()) }
+            };
+            return proxy(self).total_cmp(&proxy(other));
+        }
+        if self.is_zero() && other.is_zero() {
+            return self.mantissa().total_cmp(&other.mantissa());
+        }
+        // Neither is NaN, so the partial order is total here
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+    }
+
+    pub fn abs(&self) -> BigFloat {
+        BigFloat::new(self.mantissa().abs(), self.exponent())
+    }
+
+    /// ±1 for non-zero values; zeros and NaN are returned unchanged.
     pub fn signum(&self) -> BigFloat {
-        if self.mantissa > 0.0 {
-            BigFloat::from_f64(1.0)
-        } else if self.mantissa < 0.0 {
-            BigFloat::from_f64(-1.0)
+        if self.is_zero() || self.is_nan() {
+            BigFloat::from_f64(self.mantissa())
         } else {
-            BigFloat::from_f64(0.0)
+            BigFloat::from_f64(self.mantissa().signum())
         }
     }
 
     pub fn is_sign_positive(&self) -> bool {
-        self.mantissa.is_sign_positive()
+        self.mantissa().is_sign_positive()
     }
 
     pub fn is_sign_negative(&self) -> bool {
-        self.mantissa.is_sign_negative()
+        self.mantissa().is_sign_negative()
     }
 
+    /// The smaller value; as with `f64::min`, a NaN operand is ignored.
     pub fn min(self, other: BigFloat) -> BigFloat {
-        if self <= other { self } else { other }
+        if self.is_nan() || other < self { other } else { self }
     }
 
+    /// The larger value; as with `f64::max`, a NaN operand is ignored.
     pub fn max(self, other: BigFloat) -> BigFloat {
-        if self >= other { self } else { other }
+        if self.is_nan() || other > self { other } else { self }
     }
 }
 
@@ -123,41 +165,104 @@ impl Default for BigFloat {
 // Add assignment operators
 use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign};
 
-impl AddAssign for BigFloat {
-    fn add_assign(&mut self, other: BigFloat) {
-        *self = self.clone() + other;
+macro_rules! impl_assign_ops {
+    ($($trait:ident, $method:ident, $op:tt);*) => {
+        $(
+            impl $trait for BigFloat {
+                fn $method(&mut self, other: BigFloat) {
+                    *self = self.clone() $op other;
+                }
+            }
+
+            impl $trait<&BigFloat> for BigFloat {
+                fn $method(&mut self, other: &BigFloat) {
+                    *self = self.clone() $op other.clone();
+                }
+            }
+
+            impl_assign_ops!(@primitive $trait, $method, $op; f64, f32, i32, i64, u64);
+        )*
+    };
+    (@primitive $trait:ident, $method:ident, $op:tt; $($t:ty),*) => {
+        $(
+            impl $trait<$t> for BigFloat {
+                fn $method(&mut self, other: $t) {
+                    *self = self.clone() $op BigFloat::from(other);
+                }
+            }
+        )*
+    };
+}
+
+impl_assign_ops!(AddAssign, add_assign, +; SubAssign, sub_assign, -; MulAssign, mul_assign, *; DivAssign, div_assign, /);
+
+// Comparisons against primitives convert the primitive, on either side
+macro_rules! impl_primitive_cmp {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq<$t> for BigFloat {
+                fn eq(&self, other: &$t) -> bool {
+                    self.partial_cmp(other) == Some(Ordering::Equal)
+                }
+            }
+
+            impl PartialEq<BigFloat> for $t {
+                fn eq(&self, other: &BigFloat) -> bool {
+                    other.partial_cmp(self) == Some(Ordering::Equal)
+                }
+            }
+
+            impl PartialOrd<$t> for BigFloat {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    self.partial_cmp(&BigFloat::from(*other))
+                }
+            }
+
+            impl PartialOrd<BigFloat> for $t {
+                fn partial_cmp(&self, other: &BigFloat) -> Option<Ordering> {
+                    BigFloat::from(*self).partial_cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_cmp!(f64, f32, i32, i64, u64);
+
+// Sums are compensated so that small terms are not lost against large ones
+impl Sum for BigFloat {
+    fn sum<I: Iterator<Item = BigFloat>>(iter: I) -> BigFloat {
+        iter.collect::<Accumulator>().total()
     }
 }
 
-impl SubAssign for BigFloat {
-    fn sub_assign(&mut self, other: BigFloat) {
-        *self = self.clone() - other;
+impl<'a> Sum<&'a BigFloat> for BigFloat {
+    fn sum<I: Iterator<Item = &'a BigFloat>>(iter: I) -> BigFloat {
+        iter.collect::<Accumulator>().total()
     }
 }
 
-impl MulAssign for BigFloat {
-    fn mul_assign(&mut self, other: BigFloat) {
-        *self = self.clone() * other;
+impl Product for BigFloat {
+    fn product<I: Iterator<Item = BigFloat>>(iter: I) -> BigFloat {
+        iter.fold(BigFloat::from_f64(1.0), |product, value| product * value)
     }
 }
 
-impl DivAssign for BigFloat {
-    fn div_assign(&mut self, other: BigFloat) {
-        *self = self.clone() / other;
+impl<'a> Product<&'a BigFloat> for BigFloat {
+    fn product<I: Iterator<Item = &'a BigFloat>>(iter: I) -> BigFloat {
+        iter.fold(BigFloat::from_f64(1.0), |product, value| product * value)
     }
 }
 
 // Implement Neg
 use std::ops::Neg;
 
-impl Neg for BigFloat {
-    type Output = BigFloat;
+impl<M: Mantissa> Neg for BigFloat<M> {
+    type Output = BigFloat<M>;
 
-    fn neg(self) -> BigFloat {
-        BigFloat {
-            mantissa: -self.mantissa,
-            exponent: self.exponent,
-        }
+    fn neg(self) -> BigFloat<M> {
+        let (mantissa, exponent) = self.into_parts();
+        BigFloat::from_mantissa(-mantissa, exponent)
     }
 }
 
@@ -189,6 +294,14 @@ mod tests {
         assert!(b > a);
     }
 
+    #[test]
+    fn test_partial_ord_negative_same_exponent() {
+        let a = BigFloat::new(-2.5, 2); // -250
+        let b = BigFloat::new(-1.5, 2); // -150
+        assert!(a < b);
+        assert!(b > a);
+    }
+
     #[test]
     fn test_partial_ord_zero() {
         let zero = BigFloat::from_f64(0.0);
@@ -218,6 +331,8 @@ mod tests {
         assert_eq!(positive.signum(), BigFloat::from_f64(1.0));
         assert_eq!(negative.signum(), BigFloat::from_f64(-1.0));
         assert_eq!(zero.signum(), BigFloat::from_f64(0.0));
+        assert!(BigFloat::from_f64(-0.0).signum().is_sign_negative());
+        assert!(BigFloat::from_f64(f64::NAN).signum().is_nan());
     }
 
     #[test]
@@ -227,6 +342,61 @@ mod tests {
         
         assert_eq!(a.clone().min(b.clone()), b);
         assert_eq!(a.clone().max(b.clone()), a);
+
+        let nan = BigFloat::from_f64(f64::NAN);
+        assert_eq!(nan.clone().min(a.clone()), a);
+        assert_eq!(a.clone().max(nan), a);
+    }
+
+    #[test]
+    fn test_signed_zero() {
+        let zero = BigFloat::from_f64(0.0);
+        let negative_zero = BigFloat::from_f64(-0.0);
+        assert!(negative_zero.is_zero() && negative_zero.is_sign_negative());
+        assert_eq!(negative_zero, zero);
+        assert_eq!(negative_zero.partial_cmp(&zero), Some(Ordering::Equal));
+        assert!(negative_zero.abs().is_sign_positive());
+        assert!((-zero).is_sign_negative());
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(BigFloat::from_f64(f64::NAN).classify(), Category::Nan);
+        assert_eq!(BigFloat::from_f64(f64::NEG_INFINITY).classify(), Category::Infinite);
+        assert_eq!(BigFloat::from_f64(-0.0).classify(), Category::Zero);
+        assert_eq!(BigFloat::new(1.0, -5000).classify(), Category::Normal);
+        assert_eq!("1e1e100".parse::<BigFloat>().unwrap().classify(), Category::Normal);
+    }
+
+    #[test]
+    fn test_nan_is_unordered() {
+        let nan = BigFloat::from_f64(f64::NAN);
+        let inf = BigFloat::from_f64(f64::INFINITY);
+        assert_ne!(nan, nan);
+        assert!(nan.partial_cmp(&nan).is_none());
+        assert!(nan.partial_cmp(&inf).is_none());
+        assert_eq!(nan.total_cmp(&inf), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_total_cmp() {
+        let ordered = [
+            BigFloat::from_f64(-f64::NAN),
+            BigFloat::from_f64(f64::NEG_INFINITY),
+            "-1e1e100".parse::<BigFloat>().unwrap(),
+            BigFloat::new(-1.0, -5000),
+            BigFloat::from_f64(-0.0),
+            BigFloat::from_f64(0.0),
+            "1e-1e100".parse::<BigFloat>().unwrap(),
+            BigFloat::new(2.0, 0),
+            BigFloat::from_f64(f64::INFINITY),
+            BigFloat::from_f64(f64::NAN),
+        ];
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(a.total_cmp(b), i.cmp(&j), "{} vs {}", a, b);
+            }
+        }
     }
 
     #[test]
@@ -247,6 +417,48 @@ mod tests {
         assert_eq!(a, BigFloat::new(1.0, 1));
     }
 
+    #[test]
+    fn test_assignment_with_references_and_primitives() {
+        let mut a = BigFloat::new(1.0, 1);
+        let b = BigFloat::new(2.0, 1);
+
+        a += &b;
+        assert_eq!(a, 30);
+        a *= 2;
+        assert_eq!(a, 60.0);
+        a -= 0.5_f32;
+        assert_eq!(a, 59.5);
+        a /= &b;
+        assert_eq!(a, 2.975);
+    }
+
+    #[test]
+    fn test_compare_with_primitives() {
+        let x = BigFloat::new(2.5, 0);
+        assert_eq!(x, 2.5);
+        assert_eq!(2.5, x);
+        assert!(x > 2 && 3_u64 > x);
+        assert!(x < 2.6_f32 && -1_i64 < x);
+        assert!(BigFloat::new(1.0, 400) > f64::MAX);
+        let nan = f64::NAN;
+        assert!(BigFloat::from_f64(nan).partial_cmp(&nan).is_none());
+        assert!(BigFloat::from_f64(-0.0) == 0);
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let values = vec![BigFloat::new(1.0, 30), BigFloat::from_f64(1.0), BigFloat::new(-1.0, 30)];
+        assert_eq!(values.iter().sum::<BigFloat>(), BigFloat::from_f64(1.0));
+        assert_eq!(values.into_iter().sum::<BigFloat>(), BigFloat::from_f64(1.0));
+
+        let values = vec![BigFloat::new(2.0, 100), BigFloat::new(3.0, 200), BigFloat::from_f64(0.5)];
+        assert_eq!(values.iter().product::<BigFloat>(), BigFloat::new(3.0, 300));
+        assert_eq!(values.into_iter().product::<BigFloat>(), BigFloat::new(3.0, 300));
+
+        assert_eq!(Vec::<BigFloat>::new().iter().sum::<BigFloat>(), BigFloat::from_f64(0.0));
+        assert_eq!(Vec::<BigFloat>::new().iter().product::<BigFloat>(), BigFloat::from_f64(1.0));
+    }
+
     #[test]
     fn test_neg() {
         let positive = BigFloat::new(1.5, 2);
@@ -261,4 +473,17 @@ mod tests {
         let default_bf = BigFloat::default();
         assert_eq!(default_bf, BigFloat::from_f64(0.0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_partial_ord_across_boundary() {
+        let googolplex: BigFloat = "1e1e100".parse().unwrap();
+        let huge = BigFloat::new(9.9, i128::MAX);
+        let tiny: BigFloat = "1e-1e100".parse().unwrap();
+
+        assert!(googolplex > huge);
+        assert!(-googolplex.clone() < -huge.clone());
+        assert!(tiny < BigFloat::new(1.0, i128::MIN));
+        assert!(tiny > BigFloat::from_f64(0.0));
+        assert!(googolplex < "1e1e101".parse::<BigFloat>().unwrap());
+    }
+}